@@ -0,0 +1,330 @@
+use core::alloc::Layout;
+use core::ptr;
+use core::ptr::NonNull;
+
+use crate::error::AllocError;
+use crate::heap::alloc;
+use crate::heap::alloc_zeroed;
+use crate::heap::dealloc;
+
+/// An allocator for the backing arrays of a table.
+///
+/// Modeled on the unstable `core::alloc::Allocator` trait, as exposed by the
+/// `allocator-api2` crate, and selected through [`Params::ALLOCATOR`]. Every
+/// table allocates its arrays once when it is created and frees them when it
+/// is dropped; entries themselves are always boxed by the global allocator.
+///
+/// # Safety
+///
+/// Memory returned by [`allocate`] must remain valid until it is passed to
+/// [`deallocate`], must fit `layout` and must not overlap any other live
+/// allocation.
+///
+/// # Examples
+///
+/// ```
+/// use core::alloc::Layout;
+/// use core::ptr::NonNull;
+/// use core::sync::atomic::{AtomicUsize, Ordering};
+///
+/// use ptab::PTab;
+/// use ptab::config::{AllocError, Allocator, Capacity, Global, Params};
+///
+/// static IN_USE: AtomicUsize = AtomicUsize::new(0);
+///
+/// struct Tracked;
+///
+/// unsafe impl Allocator for Tracked {
+///   fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+///     IN_USE.fetch_add(layout.size(), Ordering::Relaxed);
+///     Global.allocate(layout)
+///   }
+///
+///   unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+///     IN_USE.fetch_sub(layout.size(), Ordering::Relaxed);
+///     unsafe { Global.deallocate(ptr, layout) }
+///   }
+/// }
+///
+/// struct TrackedParams;
+///
+/// impl Params for TrackedParams {
+///   const LENGTH: Capacity = Capacity::MIN;
+///   const ALLOCATOR: &'static (dyn Allocator + Sync) = &Tracked;
+/// }
+///
+/// let table: PTab<u64, TrackedParams> = PTab::new();
+/// assert!(IN_USE.load(Ordering::Relaxed) > 0);
+///
+/// drop(table);
+/// assert_eq!(IN_USE.load(Ordering::Relaxed), 0);
+/// ```
+///
+/// [`Params::ALLOCATOR`]: crate::params::Params::ALLOCATOR
+/// [`allocate`]: Self::allocate
+/// [`deallocate`]: Self::deallocate
+pub unsafe trait Allocator {
+  /// Attempts to allocate a block of memory described by `layout`.
+  ///
+  /// The returned block may be larger than requested.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`AllocError`] if the memory could not be allocated.
+  fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError>;
+
+  /// Behaves like [`allocate`], but also ensures that the returned memory is
+  /// zero-initialized.
+  ///
+  /// Tables are allocated through this method, so allocators that hand out
+  /// fresh pages should override it to skip the explicit zeroing.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`AllocError`] if the memory could not be allocated.
+  ///
+  /// [`allocate`]: Self::allocate
+  fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+    let block: NonNull<[u8]> = self.allocate(layout)?;
+
+    // SAFETY: `allocate` returns a block valid for writes of `block.len()`
+    //         bytes.
+    unsafe { block.cast::<u8>().write_bytes(0, block.len()) }
+
+    Ok(block)
+  }
+
+  /// Deallocates the memory referenced by `ptr`.
+  ///
+  /// # Safety
+  ///
+  /// `ptr` must denote a block of memory currently allocated by this
+  /// allocator, and `layout` must be the layout it was allocated with.
+  unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+}
+
+/// The global memory allocator.
+///
+/// The default [`Params::ALLOCATOR`].
+///
+/// [`Params::ALLOCATOR`]: crate::params::Params::ALLOCATOR
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Global;
+
+// SAFETY: Delegates to the global allocator, which upholds the same contract.
+unsafe impl Allocator for Global {
+  #[inline]
+  fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+    // SAFETY: `alloc` is only called for layouts with a non-zero size.
+    allocate_with(layout, |layout| unsafe { alloc(layout) })
+  }
+
+  #[inline]
+  fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+    // SAFETY: `alloc_zeroed` is only called for layouts with a non-zero size.
+    allocate_with(layout, |layout| unsafe { alloc_zeroed(layout) })
+  }
+
+  #[inline]
+  unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+    if layout.size() == 0 {
+      return;
+    }
+
+    if layout.align() <= BASE_ALIGN {
+      // SAFETY: The caller guarantees `ptr` was allocated with `layout`.
+      unsafe { dealloc(ptr.as_ptr(), layout) }
+      return;
+    }
+
+    // SAFETY:
+    // - The caller guarantees `ptr` was allocated with `layout`, so it was
+    //   over-allocated by `allocate_with` and preceded by the base pointer.
+    // - `outer_layout` succeeded for the same layout during allocation.
+    unsafe {
+      let base: *mut u8 = ptr.cast::<*mut u8>().sub(1).read();
+      dealloc(base, outer_layout(layout).unwrap_unchecked());
+    }
+  }
+}
+
+/// The largest alignment requested from the global allocator.
+///
+/// Global allocators typically only hand out untouched, lazily committed pages
+/// for zeroed requests with a small alignment, so larger alignments are
+/// emulated by over-allocating instead.
+const BASE_ALIGN: usize = align_of::<usize>();
+
+/// Returns the layout over-allocated for an alignment above [`BASE_ALIGN`].
+///
+/// The extra `layout.align()` bytes leave room to align the block and to store
+/// the base pointer right in front of it.
+#[inline]
+fn outer_layout(layout: Layout) -> Result<Layout, AllocError> {
+  let size: usize = layout
+    .size()
+    .checked_add(layout.align())
+    .ok_or(AllocError)?;
+  Layout::from_size_align(size, BASE_ALIGN).map_err(|_| AllocError)
+}
+
+#[inline]
+fn allocate_with<F>(layout: Layout, f: F) -> Result<NonNull<[u8]>, AllocError>
+where
+  F: FnOnce(Layout) -> *mut u8,
+{
+  if layout.size() == 0 {
+    let dangling: *mut u8 = ptr::without_provenance_mut(layout.align());
+
+    // SAFETY: Alignments are never zero.
+    let dangling: NonNull<u8> = unsafe { NonNull::new_unchecked(dangling) };
+
+    return Ok(NonNull::slice_from_raw_parts(dangling, 0));
+  }
+
+  if layout.align() <= BASE_ALIGN {
+    return match NonNull::new(f(layout)) {
+      Some(ptr) => Ok(NonNull::slice_from_raw_parts(ptr, layout.size())),
+      None => Err(AllocError),
+    };
+  }
+
+  let Some(base) = NonNull::new(f(outer_layout(layout)?)) else {
+    return Err(AllocError);
+  };
+
+  // `base` is aligned to `BASE_ALIGN`, so the block starts at least one
+  // pointer past it.
+  let offset: usize = layout.align() - (base.addr().get() & (layout.align() - 1));
+
+  // SAFETY:
+  // - `offset <= layout.align()`, so the block fits the outer allocation.
+  // - The slot in front of the block is in bounds and aligned for a pointer.
+  unsafe {
+    let block: NonNull<u8> = base.add(offset);
+    block.cast::<*mut u8>().sub(1).write(base.as_ptr());
+    Ok(NonNull::slice_from_raw_parts(block, layout.size()))
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(not(any(loom, shuttle)))]
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cfg(test)]
+mod tests {
+  use core::alloc::Layout;
+  use core::ptr::NonNull;
+  use core::sync::atomic::AtomicUsize;
+  use core::sync::atomic::Ordering;
+
+  use crate::allocator::Allocator;
+  use crate::allocator::Global;
+  use crate::error::AllocError;
+  use crate::local::LocalPTab;
+  use crate::padded::Line;
+  use crate::params::CACHE_LINE;
+  use crate::params::Capacity;
+  use crate::params::Params;
+  use crate::params::ParamsExt;
+  use crate::public::PTab;
+  use crate::table::Volatile;
+
+  static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+  static RELEASED: AtomicUsize = AtomicUsize::new(0);
+
+  struct Counting;
+
+  unsafe impl Allocator for Counting {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+      ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+      Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+      RELEASED.fetch_add(layout.size(), Ordering::Relaxed);
+      unsafe { Global.deallocate(ptr, layout) }
+    }
+  }
+
+  struct CountingParams;
+
+  impl Params for CountingParams {
+    const LENGTH: Capacity = Capacity::MIN;
+    const ALLOCATOR: &'static (dyn Allocator + Sync) = &Counting;
+  }
+
+  const LINE: Layout = Line::<Volatile<CountingParams>, CountingParams>::LAYOUT;
+
+  #[test]
+  fn global_zero_sized() {
+    let layout: Layout = Layout::from_size_align(0, CACHE_LINE).unwrap();
+    let block: NonNull<[u8]> = Global.allocate(layout).unwrap();
+
+    assert_eq!(block.len(), 0);
+    assert_eq!(block.cast::<u8>().as_ptr().addr() % CACHE_LINE, 0);
+
+    unsafe { Global.deallocate(block.cast(), layout) }
+  }
+
+  #[test]
+  fn global_over_aligned() {
+    let layout: Layout = Layout::from_size_align(CACHE_LINE * 3, CACHE_LINE * 4).unwrap();
+
+    for _ in 0..16 {
+      let block: NonNull<[u8]> = Global.allocate_zeroed(layout).unwrap();
+
+      assert_eq!(block.len(), layout.size());
+      assert_eq!(block.cast::<u8>().as_ptr().addr() % layout.align(), 0);
+      assert!(unsafe { block.as_ref() }.iter().all(|byte| *byte == 0));
+
+      unsafe { Global.deallocate(block.cast(), layout) }
+    }
+  }
+
+  #[test]
+  fn table_arrays() {
+    let table: PTab<usize, CountingParams> = PTab::new();
+    let memory: usize = CountingParams::MEMORY * 2 + LINE.size();
+
+    table.insert(1).unwrap();
+
+    // One array for the entries, one for the free-id ring and one line for
+    // the allocator state.
+    assert_eq!(ALLOCATED.load(Ordering::Relaxed), memory);
+    assert_eq!(RELEASED.load(Ordering::Relaxed), 0);
+
+    drop(table);
+
+    assert_eq!(RELEASED.load(Ordering::Relaxed), memory);
+
+    // Single-threaded tables keep their state inline.
+    let local: LocalPTab<usize, CountingParams> = LocalPTab::new();
+
+    local.insert(1).unwrap();
+
+    let memory: usize = memory + CountingParams::MEMORY * 2;
+
+    assert_eq!(ALLOCATED.load(Ordering::Relaxed), memory);
+
+    // Conversions move the arrays, so only the line for the allocator state
+    // is allocated and released.
+    let table: PTab<usize, CountingParams> = PTab::from(local);
+
+    assert_eq!(ALLOCATED.load(Ordering::Relaxed), memory + LINE.size());
+
+    let local: LocalPTab<usize, CountingParams> = LocalPTab::from(table);
+
+    assert_eq!(ALLOCATED.load(Ordering::Relaxed), memory + LINE.size());
+    assert_eq!(
+      RELEASED.load(Ordering::Relaxed),
+      memory - CountingParams::MEMORY * 2 + LINE.size()
+    );
+
+    drop(local);
+
+    assert_eq!(RELEASED.load(Ordering::Relaxed), memory + LINE.size());
+  }
+}
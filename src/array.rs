@@ -1,12 +1,11 @@
 use core::marker::PhantomData;
 use core::mem::ManuallyDrop;
+#[cfg(any(test, loom, shuttle))]
 use core::mem::MaybeUninit;
 use core::ptr::NonNull;
 use core::slice;
 
-use crate::alloc::alloc;
-use crate::alloc::dealloc;
-use crate::alloc::handle_alloc_error;
+use crate::heap::handle_alloc_error;
 use crate::index::Concrete;
 use crate::params::Params;
 use crate::params::ParamsExt;
@@ -27,6 +26,7 @@ where
 {
   /// Creates an array where each element is produced by calling `init` with
   /// that element’s index while walking forward through the array.
+  #[cfg(any(test, loom, shuttle))]
   #[inline]
   pub(crate) fn new<F>(init: F) -> Self
   where
@@ -51,23 +51,73 @@ where
   }
 
   /// Constructs a new array with uninitialized contents.
+  #[cfg(any(test, loom, shuttle))]
   #[inline]
   pub(crate) fn new_uninit() -> Array<MaybeUninit<T>, P> {
-    // SAFETY:
-    // - `P::LAYOUT` describes a non-zero-sized allocation.
-    // - Its size and alignment have been validated when constructing the
-    //   associated `Params` implementation.
-    let raw: *mut u8 = unsafe { alloc(P::LAYOUT) };
+    // `P::LAYOUT` describes a non-zero-sized allocation whose size and
+    // alignment have been validated when constructing the associated `Params`
+    // implementation.
+    Array {
+      nonnull: match P::ALLOCATOR.allocate(P::LAYOUT) {
+        Ok(ptr) => ptr.cast(),
+        Err(_) => handle_alloc_error(P::LAYOUT),
+      },
+      phantom: PhantomData,
+    }
+  }
 
+  /// Constructs a new array with every byte set to zero.
+  ///
+  /// Large allocations are typically served by fresh pages, which the system
+  /// only commits once they are touched.
+  ///
+  /// # Safety
+  ///
+  /// The all-zero bit pattern must be a valid value of `T`.
+  #[inline]
+  pub(crate) unsafe fn new_zeroed() -> Self {
     Array {
-      nonnull: match NonNull::new(raw.cast()) {
-        Some(ptr) => ptr,
-        None => handle_alloc_error(P::LAYOUT),
+      nonnull: match P::ALLOCATOR.allocate_zeroed(P::LAYOUT) {
+        Ok(ptr) => ptr.cast(),
+        Err(_) => handle_alloc_error(P::LAYOUT),
       },
       phantom: PhantomData,
     }
   }
 
+  /// Converts every element with `f`, reusing the allocation.
+  ///
+  /// `U` must have the same size and alignment as `T`, which is checked at
+  /// compile time. The allocation is leaked if `f` panics.
+  #[inline]
+  pub(crate) fn map<U, F>(self, mut f: F) -> Array<U, P>
+  where
+    F: FnMut(T) -> U,
+  {
+    const {
+      assert!(size_of::<T>() == size_of::<U>() && align_of::<T>() == align_of::<U>());
+    }
+
+    let nonnull: NonNull<T> = ManuallyDrop::new(self).as_non_null();
+
+    for index in 0..P::LENGTH.as_usize() {
+      // SAFETY:
+      // - `index` is strictly less than `P::LENGTH`, so the element is in
+      //   bounds and initialized.
+      // - Each element is read exactly once and overwritten by a `U` of the
+      //   same size and alignment.
+      unsafe {
+        let element: NonNull<T> = nonnull.add(index);
+        element.cast::<U>().write(f(element.read()));
+      }
+    }
+
+    Array {
+      nonnull: nonnull.cast(),
+      phantom: PhantomData,
+    }
+  }
+
   /// Returns a `NonNull` pointer to the array's buffer.
   #[inline]
   pub(crate) const fn as_non_null(&self) -> NonNull<T> {
@@ -75,7 +125,6 @@ where
   }
 
   /// Returns a raw pointer to the array's buffer.
-  #[cfg(test)]
   #[inline]
   pub(crate) const fn as_ptr(&self) -> *const T {
     self.as_non_null().as_ptr()
@@ -88,7 +137,6 @@ where
   }
 
   /// Extracts a slice containing the entire array.
-  #[cfg(test)]
   #[inline]
   pub(crate) const fn as_slice(&self) -> &[T] {
     // SAFETY:
@@ -139,6 +187,7 @@ where
   }
 }
 
+#[cfg(any(test, loom, shuttle))]
 impl<T, P> Array<MaybeUninit<T>, P>
 where
   P: Params + ?Sized,
@@ -170,11 +219,11 @@ where
 {
   fn drop(&mut self) {
     // SAFETY:
-    // - The allocation was created with `alloc(P::LAYOUT)` in `new_uninit`.
+    // - The allocation was created by `P::ALLOCATOR` in `new_uninit`.
     // - `P::LAYOUT` is the exact layout used for allocation.
     // - `self.nonnull` still points to the original allocation.
     unsafe {
-      dealloc(self.as_non_null().cast().as_ptr(), P::LAYOUT);
+      P::ALLOCATOR.deallocate(self.as_non_null().cast(), P::LAYOUT);
     }
   }
 }
@@ -0,0 +1,91 @@
+use core::mem;
+use core::mem::ManuallyDrop;
+use core::mem::MaybeUninit;
+use core::ptr;
+
+use crate::index::Detached;
+use crate::watch::Watchers;
+
+/// A table value along with the bookkeeping needed to retire it.
+///
+/// Entries are allocated and reclaimed by the memory reclamation backend;
+/// concurrent readers only ever observe the wrapped value.
+pub(crate) struct Entry<T> {
+  key: Detached,
+  watchers: Watchers,
+  value: ManuallyDrop<T>,
+}
+
+impl<T> Entry<T> {
+  /// Initializes the entry for `key` in place, forwarding the value to `init`.
+  ///
+  /// Returns a reference to the initialized value.
+  #[inline]
+  pub(crate) fn init<F>(uninit: &mut MaybeUninit<Self>, key: Detached, init: F) -> &T
+  where
+    F: FnOnce(&mut MaybeUninit<T>),
+  {
+    let this: *mut Self = uninit.as_mut_ptr();
+
+    // SAFETY:
+    // - `this` is valid for writes and properly aligned.
+    // - `ManuallyDrop<T>` is `repr(transparent)`, so it has the same layout as
+    //   `MaybeUninit<T>`.
+    // - The `init` closure is required to fully initialize the value.
+    unsafe {
+      ptr::addr_of_mut!((*this).key).write(key);
+      ptr::addr_of_mut!((*this).watchers).write(Watchers::new());
+      init(&mut *ptr::addr_of_mut!((*this).value).cast::<MaybeUninit<T>>());
+      &(*this).value
+    }
+  }
+
+  /// Returns the index this entry was inserted at.
+  #[inline]
+  pub(crate) const fn key(&self) -> Detached {
+    self.key
+  }
+
+  /// Returns the observers notified when this entry is removed.
+  #[inline]
+  pub(crate) const fn watchers(&self) -> &Watchers {
+    &self.watchers
+  }
+
+  /// Returns a reference to the wrapped value.
+  #[inline]
+  pub(crate) fn get(&self) -> &T {
+    &self.value
+  }
+
+  /// Drops the observers of this entry as if the table was dropped.
+  ///
+  /// Used when the entry moves to a table that does not track observers.
+  #[inline]
+  pub(crate) fn clear_watchers(&mut self) {
+    drop(mem::replace(&mut self.watchers, Watchers::new()));
+  }
+
+  /// Moves the wrapped value out of the entry.
+  ///
+  /// The entry may only be dropped afterwards.
+  #[inline]
+  pub(crate) fn take(&mut self) -> T {
+    debug_assert!(!self.watchers.is_marked(), "Entry<T> is taken!");
+
+    // The mark keeps `drop` from accessing the value again.
+    self.watchers.mark();
+
+    // SAFETY: The value is initialized and was not taken before.
+    unsafe { ManuallyDrop::take(&mut self.value) }
+  }
+}
+
+impl<T> Drop for Entry<T> {
+  fn drop(&mut self) {
+    if !self.watchers.is_marked() {
+      // SAFETY: The value is initialized and never accessed again.
+      unsafe { ManuallyDrop::drop(&mut self.value) }
+    }
+  }
+}
@@ -0,0 +1,113 @@
+use core::error::Error;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+/// The error returned when an [`Allocator`] cannot satisfy a request.
+///
+/// [`Allocator`]: crate::allocator::Allocator
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct AllocError;
+
+impl Display for AllocError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.write_str("memory allocation failed")
+  }
+}
+
+impl Error for AllocError {}
+
+/// The error returned by [`PTab::insert_at`].
+///
+/// [`PTab::insert_at`]: crate::public::PTab::insert_at
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InsertAtError {
+  /// The slot addressed by the index already holds an entry.
+  Occupied,
+  /// The index is of an older generation than the table would issue next for
+  /// its slot.
+  ///
+  /// Claiming it would let an index that was already removed refer to an
+  /// entry again.
+  Stale,
+  /// The table is at capacity.
+  Full,
+  /// The index was issued by another table.
+  ///
+  /// Only reported when table ids are enabled, see
+  /// [`Params::TABLE_ID_BITS`](crate::config::Params::TABLE_ID_BITS).
+  Foreign,
+}
+
+impl Display for InsertAtError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    match self {
+      Self::Occupied => f.write_str("slot is occupied"),
+      Self::Stale => f.write_str("index generation is stale"),
+      Self::Full => f.write_str("table is full"),
+      Self::Foreign => f.write_str("index belongs to another table"),
+    }
+  }
+}
+
+impl Error for InsertAtError {}
+
+/// The error returned when parsing a [`Detached`] index or its [`Parts`].
+///
+/// [`Detached`]: crate::index::Detached
+/// [`Parts`]: crate::index::Parts
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseIndexError {
+  /// The input is not in the expected format.
+  Invalid,
+  /// A component does not fit the table parameters.
+  OutOfRange,
+}
+
+impl Display for ParseIndexError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    match self {
+      Self::Invalid => f.write_str("invalid index syntax"),
+      Self::OutOfRange => f.write_str("index out of range"),
+    }
+  }
+}
+
+impl Error for ParseIndexError {}
+
+/// The error returned by [`ExternalPid`] operations.
+///
+/// [`ExternalPid`]: crate::etf::ExternalPid
+#[cfg(feature = "etf")]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PidError {
+  /// The node name is empty or longer than 255 characters.
+  InvalidNode,
+  /// The input ended before the term was complete.
+  UnexpectedEnd,
+  /// The input contains an unsupported tag or version byte.
+  InvalidTag(u8),
+  /// The encoded pid does not fit in an index on this platform.
+  OutOfRange,
+  /// The input continues after the term.
+  TrailingBytes,
+}
+
+#[cfg(feature = "etf")]
+impl Display for PidError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    match self {
+      Self::InvalidNode => f.write_str("invalid node name"),
+      Self::UnexpectedEnd => f.write_str("unexpected end of input"),
+      Self::InvalidTag(tag) => write!(f, "invalid tag {tag}"),
+      Self::OutOfRange => f.write_str("pid out of range"),
+      Self::TrailingBytes => f.write_str("trailing bytes after term"),
+    }
+  }
+}
+
+#[cfg(feature = "etf")]
+impl Error for PidError {}
@@ -0,0 +1,396 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::error::PidError;
+use crate::index::Detached;
+
+/// The version byte that starts every external term.
+const VERSION: u8 = 131;
+
+const NEW_PID_EXT: u8 = 88;
+const ATOM_EXT: u8 = 100;
+const SMALL_ATOM_EXT: u8 = 115;
+const ATOM_UTF8_EXT: u8 = 118;
+const SMALL_ATOM_UTF8_EXT: u8 = 119;
+
+/// The maximum number of characters in an atom.
+const MAX_ATOM_CHARS: usize = 255;
+
+/// An index paired with the node that owns it, as seen by Erlang.
+///
+/// Encodes to and decodes from the `NEW_PID_EXT` term of the [external term
+/// format], so a table can serve as the process table of a node that talks to
+/// real Erlang nodes.
+///
+/// The raw bits of the index are split across the `ID` (low 32 bits) and
+/// `Serial` (high 32 bits) fields of the pid, which together form the 64-bit
+/// pid number since OTP 24. The mapping is lossless, so decoding a pid yields
+/// the original index.
+///
+/// # Examples
+///
+/// ```
+/// use ptab::{ExternalPid, PTab};
+///
+/// let table: PTab<u64> = PTab::new();
+/// let index = table.insert(1).unwrap();
+///
+/// let pid = ExternalPid::new("rust@localhost", index, 1).unwrap();
+/// let data = pid.encode();
+///
+/// assert_eq!(ExternalPid::decode(&data), Ok(pid));
+/// ```
+///
+/// [external term format]: https://www.erlang.org/doc/apps/erts/erl_ext_dist.html
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ExternalPid {
+  node: String,
+  index: Detached,
+  creation: u32,
+}
+
+impl ExternalPid {
+  /// Creates a pid for `index` on the node named `node`.
+  ///
+  /// `creation` distinguishes incarnations of a node with the same name.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`PidError::InvalidNode`] if `node` is empty or longer than an
+  /// atom may be.
+  ///
+  /// [`PidError::InvalidNode`]: crate::error::PidError::InvalidNode
+  pub fn new(node: impl Into<String>, index: Detached, creation: u32) -> Result<Self, PidError> {
+    let node: String = node.into();
+
+    if node.is_empty() || node.chars().count() > MAX_ATOM_CHARS {
+      return Err(PidError::InvalidNode);
+    }
+
+    Ok(Self {
+      node,
+      index,
+      creation,
+    })
+  }
+
+  /// Returns the name of the node owning the pid.
+  #[inline]
+  pub fn node(&self) -> &str {
+    &self.node
+  }
+
+  /// Returns the index identified by the pid.
+  #[inline]
+  pub const fn index(&self) -> Detached {
+    self.index
+  }
+
+  /// Returns the creation of the node owning the pid.
+  #[inline]
+  pub const fn creation(&self) -> u32 {
+    self.creation
+  }
+
+  /// Encodes the pid as a complete external term, as produced by
+  /// `erlang:term_to_binary/1`.
+  pub fn encode(&self) -> Vec<u8> {
+    let mut data: Vec<u8> = Vec::with_capacity(self.node.len() + 16);
+
+    data.push(VERSION);
+    self.encode_into(&mut data);
+    data
+  }
+
+  /// Appends the `NEW_PID_EXT` term to `data`, without the version byte.
+  ///
+  /// Use this to embed the pid in a larger term.
+  pub fn encode_into(&self, data: &mut Vec<u8>) {
+    let bits: u64 = self.index.into_bits() as u64;
+
+    data.push(NEW_PID_EXT);
+
+    if let Ok(len) = u8::try_from(self.node.len()) {
+      data.push(SMALL_ATOM_UTF8_EXT);
+      data.push(len);
+    } else {
+      data.push(ATOM_UTF8_EXT);
+      // Atoms are at most 255 characters, so at most 1020 bytes.
+      data.extend_from_slice(&(self.node.len() as u16).to_be_bytes());
+    }
+
+    data.extend_from_slice(self.node.as_bytes());
+    data.extend_from_slice(&(bits as u32).to_be_bytes());
+    data.extend_from_slice(&((bits >> 32) as u32).to_be_bytes());
+    data.extend_from_slice(&self.creation.to_be_bytes());
+  }
+
+  /// Decodes a pid from a complete external term.
+  ///
+  /// # Errors
+  ///
+  /// Returns a [`PidError`] if `data` is not a single `NEW_PID_EXT` term, or
+  /// if the pid does not fit in an index on this platform.
+  ///
+  /// [`PidError`]: crate::error::PidError
+  pub fn decode(data: &[u8]) -> Result<Self, PidError> {
+    let mut reader: Reader<'_> = Reader { data };
+
+    match reader.u8()? {
+      VERSION => {}
+      tag => return Err(PidError::InvalidTag(tag)),
+    }
+
+    let this: Self = Self::decode_from(&mut reader)?;
+
+    if !reader.data.is_empty() {
+      return Err(PidError::TrailingBytes);
+    }
+
+    Ok(this)
+  }
+
+  /// Decodes a `NEW_PID_EXT` term without the version byte from the front of
+  /// `data`, returning the pid and the remaining input.
+  ///
+  /// # Errors
+  ///
+  /// See [`decode`].
+  ///
+  /// [`decode`]: Self::decode
+  pub fn decode_prefix(data: &[u8]) -> Result<(Self, &[u8]), PidError> {
+    let mut reader: Reader<'_> = Reader { data };
+    let this: Self = Self::decode_from(&mut reader)?;
+
+    Ok((this, reader.data))
+  }
+
+  fn decode_from(reader: &mut Reader<'_>) -> Result<Self, PidError> {
+    match reader.u8()? {
+      NEW_PID_EXT => {}
+      tag => return Err(PidError::InvalidTag(tag)),
+    }
+
+    let node: String = match reader.u8()? {
+      SMALL_ATOM_UTF8_EXT => {
+        let len: usize = usize::from(reader.u8()?);
+        utf8(reader.take(len)?)?
+      }
+      ATOM_UTF8_EXT => {
+        let len: usize = usize::from(reader.u16()?);
+        utf8(reader.take(len)?)?
+      }
+      SMALL_ATOM_EXT => {
+        let len: usize = usize::from(reader.u8()?);
+        latin1(reader.take(len)?)
+      }
+      ATOM_EXT => {
+        let len: usize = usize::from(reader.u16()?);
+        latin1(reader.take(len)?)
+      }
+      tag => return Err(PidError::InvalidTag(tag)),
+    };
+
+    let id: u64 = u64::from(reader.u32()?);
+    let serial: u64 = u64::from(reader.u32()?);
+    let creation: u32 = reader.u32()?;
+
+    let Ok(bits) = usize::try_from((serial << 32) | id) else {
+      return Err(PidError::OutOfRange);
+    };
+
+    Self::new(node, Detached::from_bits(bits), creation)
+  }
+}
+
+fn utf8(data: &[u8]) -> Result<String, PidError> {
+  match str::from_utf8(data) {
+    Ok(node) => Ok(node.to_owned()),
+    Err(_) => Err(PidError::InvalidNode),
+  }
+}
+
+fn latin1(data: &[u8]) -> String {
+  data.iter().copied().map(char::from).collect()
+}
+
+// -----------------------------------------------------------------------------
+// Reader
+// -----------------------------------------------------------------------------
+
+struct Reader<'a> {
+  data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+  fn take(&mut self, len: usize) -> Result<&'a [u8], PidError> {
+    let Some((head, tail)) = self.data.split_at_checked(len) else {
+      return Err(PidError::UnexpectedEnd);
+    };
+
+    self.data = tail;
+
+    Ok(head)
+  }
+
+  fn array<const N: usize>(&mut self) -> Result<[u8; N], PidError> {
+    let mut data: [u8; N] = [0; N];
+    data.copy_from_slice(self.take(N)?);
+    Ok(data)
+  }
+
+  fn u8(&mut self) -> Result<u8, PidError> {
+    self.array().map(u8::from_be_bytes)
+  }
+
+  fn u16(&mut self) -> Result<u16, PidError> {
+    self.array().map(u16::from_be_bytes)
+  }
+
+  fn u32(&mut self) -> Result<u32, PidError> {
+    self.array().map(u32::from_be_bytes)
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cfg(test)]
+mod tests {
+  use crate::error::PidError;
+  use crate::etf::ExternalPid;
+  use crate::index::Detached;
+
+  // term_to_binary(c:pid(0, 85, 0)) on 'nonode@nohost' (creation 0)
+  const NONODE: &[u8] = &[
+    131, 88, 119, 13, b'n', b'o', b'n', b'o', b'd', b'e', b'@', b'n', b'o', b'h', b'o', b's', b't',
+    0, 0, 0, 85, 0, 0, 0, 0, 0, 0, 0, 0,
+  ];
+
+  // A pid on 'a@b' with ID 1, Serial 2, and creation 0x01020304
+  const SERIAL: &[u8] = &[
+    131, 88, 119, 3, b'a', b'@', b'b', 0, 0, 0, 1, 0, 0, 0, 2, 1, 2, 3, 4,
+  ];
+
+  #[test]
+  fn encode() {
+    let pid: ExternalPid = ExternalPid::new("nonode@nohost", Detached::from_bits(85), 0).unwrap();
+
+    assert_eq!(pid.encode(), NONODE);
+  }
+
+  #[test]
+  fn decode() {
+    let pid: ExternalPid = ExternalPid::decode(NONODE).unwrap();
+
+    assert_eq!(pid.node(), "nonode@nohost");
+    assert_eq!(pid.index(), Detached::from_bits(85));
+    assert_eq!(pid.creation(), 0);
+  }
+
+  #[cfg(target_pointer_width = "64")]
+  #[test]
+  fn serial() {
+    let index: Detached = Detached::from_bits((2 << 32) | 1);
+    let pid: ExternalPid = ExternalPid::new("a@b", index, 0x0102_0304).unwrap();
+
+    assert_eq!(pid.encode(), SERIAL);
+    assert_eq!(ExternalPid::decode(SERIAL), Ok(pid));
+  }
+
+  #[cfg(target_pointer_width = "32")]
+  #[test]
+  fn serial() {
+    assert_eq!(ExternalPid::decode(SERIAL), Err(PidError::OutOfRange));
+  }
+
+  #[test]
+  fn roundtrip() {
+    for bits in [0, 1, u32::MAX as usize, usize::MAX] {
+      let pid: ExternalPid = ExternalPid::new("node@host", Detached::from_bits(bits), 7).unwrap();
+
+      assert_eq!(ExternalPid::decode(&pid.encode()), Ok(pid));
+    }
+  }
+
+  #[test]
+  fn long_node() {
+    // 100 three-byte characters need the long atom encoding.
+    let node: String = "\u{2603}".repeat(100);
+    let pid: ExternalPid = ExternalPid::new(node.as_str(), Detached::from_bits(1), 0).unwrap();
+    let data: Vec<u8> = pid.encode();
+
+    assert_eq!(data[2], 118);
+    assert_eq!(data[3..5], 300_u16.to_be_bytes());
+    assert_eq!(ExternalPid::decode(&data), Ok(pid));
+  }
+
+  #[test]
+  fn latin1_node() {
+    // The same pid with a legacy SMALL_ATOM_EXT node name.
+    let mut data: Vec<u8> = NONODE.to_vec();
+    data[2] = 115;
+
+    assert_eq!(ExternalPid::decode(&data), ExternalPid::decode(NONODE));
+
+    // ...and with ATOM_EXT.
+    let mut data: Vec<u8> = vec![131, 88, 100, 0, 13];
+    data.extend_from_slice(&NONODE[4..]);
+
+    assert_eq!(ExternalPid::decode(&data), ExternalPid::decode(NONODE));
+  }
+
+  #[test]
+  fn prefix() {
+    let mut data: Vec<u8> = Vec::new();
+    let pid: ExternalPid = ExternalPid::new("a@b", Detached::from_bits(3), 1).unwrap();
+
+    pid.encode_into(&mut data);
+    data.push(106);
+
+    assert_eq!(ExternalPid::decode_prefix(&data), Ok((pid, &[106][..])));
+  }
+
+  #[test]
+  fn invalid_node() {
+    let node: String = "a".repeat(256);
+
+    assert_eq!(
+      ExternalPid::new("", Detached::from_bits(0), 0),
+      Err(PidError::InvalidNode)
+    );
+    assert_eq!(
+      ExternalPid::new(node, Detached::from_bits(0), 0),
+      Err(PidError::InvalidNode)
+    );
+
+    let mut data: Vec<u8> = SERIAL.to_vec();
+    data[4] = 0xFF;
+
+    assert_eq!(ExternalPid::decode(&data), Err(PidError::InvalidNode));
+  }
+
+  #[test]
+  fn invalid_input() {
+    let mut trailing: Vec<u8> = NONODE.to_vec();
+    trailing.push(0);
+
+    assert_eq!(ExternalPid::decode(&[]), Err(PidError::UnexpectedEnd));
+    assert_eq!(
+      ExternalPid::decode(&NONODE[..20]),
+      Err(PidError::UnexpectedEnd)
+    );
+    assert_eq!(
+      ExternalPid::decode(&NONODE[1..]),
+      Err(PidError::InvalidTag(88))
+    );
+    assert_eq!(
+      ExternalPid::decode(&[131, 103]),
+      Err(PidError::InvalidTag(103))
+    );
+    assert_eq!(ExternalPid::decode(&trailing), Err(PidError::TrailingBytes));
+  }
+}
@@ -0,0 +1,470 @@
+use core::mem;
+use core::sync::atomic::AtomicU64;
+use core::sync::atomic::Ordering::Relaxed;
+use core::time::Duration;
+use std::sync::Weak;
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Instant;
+
+use crate::index::Detached;
+use crate::params::Params;
+use crate::public::PTab;
+use crate::secondary::SecondaryTable;
+use crate::utils::Mutex;
+use crate::utils::MutexGuard;
+
+/// The resolution of expiration deadlines, in milliseconds.
+const TICK_MILLIS: u64 = 10;
+
+/// The resolution of expiration deadlines.
+const TICK: Duration = Duration::from_millis(TICK_MILLIS);
+
+/// The number of buckets in the timer wheel; one rotation spans `WHEEL` ticks.
+const WHEEL: usize = 256;
+
+// -----------------------------------------------------------------------------
+// Expiry
+// -----------------------------------------------------------------------------
+
+/// Expiration state of a table, created on the first insert with a TTL.
+///
+/// Deadlines live in a [`SecondaryTable`] next to the entries, so [`touch`]
+/// only updates an atomic. The timer wheel holds indices hashed by deadline
+/// and is only consulted by [`expired`]; entries whose deadline was extended
+/// since they were scheduled are rescheduled lazily.
+///
+/// [`touch`]: Self::touch
+/// [`expired`]: Self::expired
+pub(crate) struct Expiry<P>
+where
+  P: Params + ?Sized,
+{
+  origin: Instant,
+  leases: SecondaryTable<Lease, P>,
+  wheel: Mutex<Wheel>,
+}
+
+impl<P> Expiry<P>
+where
+  P: Params + ?Sized,
+{
+  #[inline]
+  pub(crate) fn new() -> Self {
+    Self {
+      origin: Instant::now(),
+      leases: SecondaryTable::new(),
+      wheel: Mutex::new(Wheel::new()),
+    }
+  }
+
+  /// Schedules the entry at `key` to expire `ttl` after `now`.
+  pub(crate) fn schedule(&self, key: Detached, ttl: Duration, now: Instant) {
+    let deadline: u64 = self.deadline(now, ttl);
+
+    let lease: Lease = Lease {
+      ttl,
+      deadline: AtomicU64::new(deadline),
+    };
+
+    // The entry was already removed and its slot reused by a newer entry.
+    if self.leases.insert(key, lease).is_err() {
+      return;
+    }
+
+    self.lock().push(key, deadline);
+  }
+
+  /// Extends the lease of the entry at `key` by its TTL, starting at `now`.
+  ///
+  /// Returns `false` if the entry was not inserted with a TTL.
+  #[inline]
+  pub(crate) fn touch(&self, key: Detached, now: Instant) -> bool {
+    self
+      .leases
+      .with(key, |lease| {
+        lease
+          .deadline
+          .fetch_max(self.deadline(now, lease.ttl), Relaxed);
+      })
+      .is_some()
+  }
+
+  /// Returns the remaining lifetime of the entry at `key` as of `now`.
+  #[inline]
+  pub(crate) fn remaining(&self, key: Detached, now: Instant) -> Option<Duration> {
+    self.leases.with(key, |lease| {
+      let deadline: u64 = lease.deadline.load(Relaxed);
+      let elapsed: Duration = now.saturating_duration_since(self.origin);
+
+      // Scaled in milliseconds, since `Duration::saturating_mul` only takes a
+      // `u32` and would cut deadlines beyond about 497 days.
+      Duration::from_millis(deadline.saturating_mul(TICK_MILLIS)).saturating_sub(elapsed)
+    })
+  }
+
+  /// Collects the indices of entries whose lease expired at or before `now`.
+  ///
+  /// Expired leases are released; the caller is responsible for removing the
+  /// entries themselves.
+  pub(crate) fn expired(&self, now: Instant) -> Vec<Detached> {
+    let target: u64 = self.ticks(now);
+    let mut wheel: MutexGuard<'_, Wheel> = self.lock();
+    let mut expired: Vec<Detached> = Vec::new();
+
+    while let Some(bucket) = wheel.advance(target) {
+      for key in bucket {
+        match self.leases.with(key, |lease| lease.deadline.load(Relaxed)) {
+          // Removed and replaced since it was scheduled.
+          None => {}
+          Some(deadline) if deadline <= target => {
+            self.leases.remove(key);
+            expired.push(key);
+          }
+          Some(deadline) => wheel.push(key, deadline),
+        }
+      }
+    }
+
+    expired
+  }
+
+  #[inline]
+  fn lock(&self) -> MutexGuard<'_, Wheel> {
+    self.wheel.lock()
+  }
+
+  /// Returns the number of whole ticks elapsed between the origin and `time`.
+  #[inline]
+  fn ticks(&self, time: Instant) -> u64 {
+    let elapsed: Duration = time.saturating_duration_since(self.origin);
+    u64::try_from(elapsed.as_nanos() / TICK.as_nanos()).unwrap_or(u64::MAX)
+  }
+
+  /// Returns the first tick at or after `ttl` from `now`.
+  #[inline]
+  fn deadline(&self, now: Instant, ttl: Duration) -> u64 {
+    let elapsed: Duration = now.saturating_duration_since(self.origin);
+    let nanos: u128 = elapsed.saturating_add(ttl).as_nanos();
+    u64::try_from(nanos.div_ceil(TICK.as_nanos())).unwrap_or(u64::MAX)
+  }
+}
+
+/// The expiration state of a single entry.
+struct Lease {
+  ttl: Duration,
+  deadline: AtomicU64,
+}
+
+// -----------------------------------------------------------------------------
+// Timer Wheel
+// -----------------------------------------------------------------------------
+
+/// A hashed timer wheel of entry indices.
+///
+/// Bucket `n` holds the entries whose deadline is congruent to `n` modulo
+/// [`WHEEL`]; entries due in a later rotation are skipped and pushed back.
+struct Wheel {
+  buckets: Box<[Vec<Detached>]>,
+  /// The last tick whose bucket was processed.
+  current: u64,
+  /// The number of buckets left to process before reaching `target`.
+  pending: usize,
+}
+
+impl Wheel {
+  fn new() -> Self {
+    Self {
+      buckets: (0..WHEEL).map(|_| Vec::new()).collect(),
+      current: 0,
+      pending: 0,
+    }
+  }
+
+  /// Schedules `key` in the bucket for `deadline`.
+  ///
+  /// Deadlines that already passed are scheduled for the next tick.
+  #[inline]
+  fn push(&mut self, key: Detached, deadline: u64) {
+    let tick: u64 = deadline.max(self.current + 1);
+    self.buckets[bucket(tick)].push(key);
+  }
+
+  /// Takes the next bucket due at or before `target`.
+  fn advance(&mut self, target: u64) -> Option<Vec<Detached>> {
+    if self.pending == 0 {
+      // Visiting more than one rotation's worth of buckets is redundant.
+      let elapsed: u64 = target.saturating_sub(self.current);
+      self.pending = usize::try_from(elapsed).map_or(WHEEL, |elapsed| elapsed.min(WHEEL));
+
+      if self.pending == 0 {
+        return None;
+      }
+
+      self.current = target - self.pending as u64;
+    }
+
+    self.pending -= 1;
+    self.current += 1;
+
+    Some(mem::take(&mut self.buckets[bucket(self.current)]))
+  }
+}
+
+#[inline]
+const fn bucket(tick: u64) -> usize {
+  (tick % WHEEL as u64) as usize
+}
+
+// -----------------------------------------------------------------------------
+// Sweeper
+// -----------------------------------------------------------------------------
+
+/// A background thread that periodically removes expired entries.
+///
+/// Created by [`PTab::sweeper`]. The thread holds only a weak reference to the
+/// table and exits once the table is dropped, or when the `Sweeper` is
+/// dropped or [stopped].
+///
+/// [`PTab::sweeper`]: crate::public::PTab::sweeper
+/// [stopped]: Self::stop
+#[derive(Debug)]
+pub struct Sweeper {
+  signal: Option<Sender<()>>,
+  thread: Option<JoinHandle<()>>,
+}
+
+impl Sweeper {
+  pub(crate) fn spawn<T, P>(table: Weak<PTab<T, P>>, interval: Duration) -> Self
+  where
+    T: Send + 'static,
+    P: Params + ?Sized + 'static,
+  {
+    let (signal, receiver) = mpsc::channel::<()>();
+
+    let thread: JoinHandle<()> = thread::spawn(move || {
+      while let Err(RecvTimeoutError::Timeout) = receiver.recv_timeout(interval) {
+        let Some(table) = table.upgrade() else {
+          break;
+        };
+
+        table.sweep(Instant::now());
+      }
+    });
+
+    Self {
+      signal: Some(signal),
+      thread: Some(thread),
+    }
+  }
+
+  /// Stops the sweeper thread and waits for it to exit.
+  #[inline]
+  pub fn stop(mut self) {
+    self.shutdown();
+  }
+
+  fn shutdown(&mut self) {
+    // Disconnecting the channel wakes the thread immediately.
+    drop(self.signal.take());
+
+    if let Some(thread) = self.thread.take() {
+      // Propagating a panic from the sweeper would abort in `Drop`.
+      let _ignore = thread.join();
+    }
+  }
+}
+
+impl Drop for Sweeper {
+  fn drop(&mut self) {
+    self.shutdown();
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(not(any(loom, shuttle)))]
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cfg(test)]
+mod tests {
+  use core::time::Duration;
+  use std::sync::Arc;
+  use std::thread;
+  use std::time::Instant;
+
+  use crate::expiry::TICK;
+  use crate::expiry::WHEEL;
+  use crate::index::Detached;
+  use crate::params::Capacity;
+  use crate::params::ConstParams;
+  use crate::public::PTab;
+  use crate::reason::Reason;
+
+  type DefParams = ConstParams<{ Capacity::DEF.as_usize() }>;
+
+  const TTL: Duration = Duration::from_mins(1);
+
+  #[test]
+  fn sweep_expired() {
+    let table: PTab<usize, DefParams> = PTab::new();
+    let index: Detached = table.insert_with_ttl(1, TTL).unwrap();
+    let now: Instant = Instant::now();
+
+    assert_eq!(table.sweep(now), 0);
+    assert!(table.exists(index));
+
+    assert_eq!(table.sweep(now + TTL + TICK), 1);
+    assert!(!table.exists(index));
+  }
+
+  #[test]
+  fn sweep_not_early() {
+    let table: PTab<usize, DefParams> = PTab::new();
+    let index: Detached = table.insert_with_ttl(1, TTL).unwrap();
+    let now: Instant = Instant::now();
+
+    assert_eq!(table.sweep(now + TTL.saturating_sub(TICK * 2)), 0);
+    assert!(table.exists(index));
+  }
+
+  #[test]
+  fn sweep_many_rotations() {
+    let table: PTab<usize, DefParams> = PTab::new();
+    let ttl: Duration = TICK * (WHEEL as u32 * 3 + 7);
+    let index: Detached = table.insert_with_ttl(1, ttl).unwrap();
+    let now: Instant = Instant::now();
+
+    for rotation in 1..=3 {
+      assert_eq!(table.sweep(now + TICK * (WHEEL as u32 * rotation)), 0);
+      assert!(table.exists(index));
+    }
+
+    assert_eq!(table.sweep(now + ttl + TICK), 1);
+  }
+
+  #[test]
+  fn sweep_without_ttl() {
+    let table: PTab<usize, DefParams> = PTab::new();
+    let plain: Detached = table.insert(1).unwrap();
+
+    assert_eq!(table.sweep(Instant::now() + TTL), 0);
+
+    let index: Detached = table.insert_with_ttl(2, Duration::ZERO).unwrap();
+
+    assert_eq!(table.sweep(Instant::now() + TTL), 1);
+    assert!(table.exists(plain));
+    assert!(!table.exists(index));
+  }
+
+  #[test]
+  fn touch() {
+    let table: PTab<usize, DefParams> = PTab::new();
+    let index: Detached = table.insert_with_ttl(1, TTL).unwrap();
+    let plain: Detached = table.insert(2).unwrap();
+
+    thread::sleep(TICK * 5);
+
+    assert!(table.touch(index));
+    assert!(!table.touch(plain));
+
+    let now: Instant = Instant::now();
+
+    // The original deadline passes, but the entry was touched after it was
+    // inserted, so it is rescheduled rather than removed.
+    assert_eq!(table.sweep(now + TTL.saturating_sub(TICK * 2)), 0);
+    assert!(table.exists(index));
+
+    assert_eq!(table.sweep(now + TTL + TICK), 1);
+  }
+
+  #[test]
+  fn touch_removed() {
+    let table: PTab<usize, DefParams> = PTab::new();
+    let index: Detached = table.insert_with_ttl(1, TTL).unwrap();
+
+    assert!(table.remove(index));
+    assert!(!table.touch(index));
+    assert_eq!(table.sweep(Instant::now() + TTL * 2), 0);
+  }
+
+  #[test]
+  fn ttl() {
+    let table: PTab<usize, DefParams> = PTab::new();
+    let index: Detached = table.insert_with_ttl(1, TTL).unwrap();
+    let plain: Detached = table.insert(2).unwrap();
+
+    let remaining: Duration = table.ttl(index).unwrap();
+
+    assert!(remaining <= TTL + TICK);
+    assert!(remaining > TTL.saturating_sub(TICK * 100));
+    assert_eq!(table.ttl(plain), None);
+  }
+
+  #[test]
+  fn ttl_long() {
+    let table: PTab<usize, DefParams> = PTab::new();
+    let ttl: Duration = Duration::from_hours(24 * 1000);
+    let index: Detached = table.insert_with_ttl(1, ttl).unwrap();
+
+    let remaining: Duration = table.ttl(index).unwrap();
+
+    assert!(remaining <= ttl + TICK);
+    assert!(remaining > ttl.saturating_sub(TICK * 100));
+  }
+
+  #[test]
+  fn reused_slot() {
+    let table: PTab<usize, ConstParams<{ Capacity::MIN.as_usize() }>> = PTab::new();
+    let old: Detached = table.insert_with_ttl(1, Duration::ZERO).unwrap();
+
+    assert!(table.remove(old));
+
+    let keys: Vec<Detached> = (0..table.capacity())
+      .map(|value| table.insert(value).unwrap())
+      .collect();
+
+    // The stale lease must not expire the entry now occupying its slot.
+    assert_eq!(table.sweep(Instant::now() + TTL), 0);
+    assert_eq!(table.len(), keys.len());
+  }
+
+  #[test]
+  fn monitor_reason() {
+    let table: PTab<usize, DefParams> = PTab::new();
+    let index: Detached = table.insert_with_ttl(1, Duration::ZERO).unwrap();
+    let monitor = table.monitor(index);
+
+    assert_eq!(table.sweep(Instant::now() + TICK), 1);
+    assert_eq!(monitor.reason(), Some(Reason::Expired));
+  }
+
+  #[test]
+  fn sweeper() {
+    let table: Arc<PTab<usize, DefParams>> = Arc::new(PTab::new());
+    let index: Detached = table.insert_with_ttl(1, TICK).unwrap();
+    let monitor = table.monitor(index);
+    let sweeper = PTab::sweeper(&table, TICK);
+
+    assert_eq!(
+      monitor.wait_timeout(Duration::from_secs(10)),
+      Some(Reason::Expired)
+    );
+
+    sweeper.stop();
+  }
+
+  #[test]
+  fn sweeper_outlives_table() {
+    let table: Arc<PTab<usize, DefParams>> = Arc::new(PTab::new());
+    let sweeper = PTab::sweeper(&table, TICK);
+
+    drop(table);
+    thread::sleep(TICK * 2);
+    drop(sweeper);
+  }
+}
@@ -0,0 +1,189 @@
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::ops::BitAnd;
+use core::ops::BitOr;
+use core::ops::Not;
+use core::ops::Sub;
+
+/// A set of per-entry flag bits.
+///
+/// Every entry carries two flag bits, stored in the tag bits of the pointer to
+/// the entry, so they can be read and updated atomically without replacing
+/// the value. Their meaning is up to the application, e.g. marking an entry as
+/// "exiting" or "suspended" like BEAM process flags.
+///
+/// Flags start out [empty] when an entry is inserted.
+///
+/// # Examples
+///
+/// ```
+/// use ptab::{Flags, PTab};
+///
+/// const SUSPENDED: Flags = Flags::FIRST;
+///
+/// let table: PTab<&str> = PTab::new();
+/// let idx = table.insert("worker").unwrap();
+///
+/// assert_eq!(table.set_flags(idx, SUSPENDED), Some(Flags::EMPTY));
+/// assert_eq!(table.with_flags(idx, |_, flags| flags.contains(SUSPENDED)), Some(true));
+/// ```
+///
+/// [empty]: Self::EMPTY
+#[derive(Clone, Copy, Default, Hash, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Flags(u8);
+
+impl Flags {
+  /// No flags set.
+  pub const EMPTY: Self = Self(0b00);
+
+  /// The first flag bit.
+  pub const FIRST: Self = Self(0b01);
+
+  /// The second flag bit.
+  pub const SECOND: Self = Self(0b10);
+
+  /// Both flag bits.
+  pub const ALL: Self = Self(0b11);
+
+  /// Creates a set of flags from raw bits, or returns [`None`] if `bits`
+  /// contains unknown bits.
+  #[inline]
+  pub const fn from_bits(bits: u8) -> Option<Self> {
+    if bits & !Self::ALL.0 == 0 {
+      Some(Self(bits))
+    } else {
+      None
+    }
+  }
+
+  /// Creates a set of flags from raw bits, discarding unknown bits.
+  #[inline]
+  pub const fn from_bits_truncate(bits: u8) -> Self {
+    Self(bits & Self::ALL.0)
+  }
+
+  /// Returns the raw bits of the flags.
+  #[inline]
+  pub const fn bits(self) -> u8 {
+    self.0
+  }
+
+  /// Returns `true` if no flags are set.
+  #[inline]
+  pub const fn is_empty(self) -> bool {
+    self.0 == 0
+  }
+
+  /// Returns `true` if all flags in `other` are set.
+  #[inline]
+  pub const fn contains(self, other: Self) -> bool {
+    self.0 & other.0 == other.0
+  }
+
+  /// Returns the flags set in either `self` or `other`.
+  #[inline]
+  #[must_use]
+  pub const fn union(self, other: Self) -> Self {
+    Self(self.0 | other.0)
+  }
+
+  /// Returns the flags set in both `self` and `other`.
+  #[inline]
+  #[must_use]
+  pub const fn intersection(self, other: Self) -> Self {
+    Self(self.0 & other.0)
+  }
+
+  /// Returns the flags set in `self` but not in `other`.
+  #[inline]
+  #[must_use]
+  pub const fn difference(self, other: Self) -> Self {
+    Self(self.0 & !other.0)
+  }
+}
+
+impl Debug for Flags {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    match *self {
+      Self::EMPTY => f.write_str("Flags(EMPTY)"),
+      Self::FIRST => f.write_str("Flags(FIRST)"),
+      Self::SECOND => f.write_str("Flags(SECOND)"),
+      _ => f.write_str("Flags(FIRST | SECOND)"),
+    }
+  }
+}
+
+impl BitOr for Flags {
+  type Output = Self;
+
+  #[inline]
+  fn bitor(self, other: Self) -> Self::Output {
+    self.union(other)
+  }
+}
+
+impl BitAnd for Flags {
+  type Output = Self;
+
+  #[inline]
+  fn bitand(self, other: Self) -> Self::Output {
+    self.intersection(other)
+  }
+}
+
+impl Sub for Flags {
+  type Output = Self;
+
+  #[inline]
+  fn sub(self, other: Self) -> Self::Output {
+    self.difference(other)
+  }
+}
+
+impl Not for Flags {
+  type Output = Self;
+
+  #[inline]
+  fn not(self) -> Self::Output {
+    Self::ALL.difference(self)
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cfg(test)]
+mod tests {
+  use crate::flags::Flags;
+
+  #[test]
+  fn from_bits() {
+    assert_eq!(Flags::from_bits(0b00), Some(Flags::EMPTY));
+    assert_eq!(Flags::from_bits(0b11), Some(Flags::ALL));
+    assert_eq!(Flags::from_bits(0b100), None);
+    assert_eq!(Flags::from_bits_truncate(0b111), Flags::ALL);
+  }
+
+  #[test]
+  fn operations() {
+    assert_eq!(Flags::FIRST | Flags::SECOND, Flags::ALL);
+    assert_eq!(Flags::ALL & Flags::FIRST, Flags::FIRST);
+    assert_eq!(Flags::ALL - Flags::FIRST, Flags::SECOND);
+    assert_eq!(!Flags::FIRST, Flags::SECOND);
+    assert_eq!(!Flags::EMPTY, Flags::ALL);
+
+    assert!(Flags::ALL.contains(Flags::SECOND));
+    assert!(!Flags::FIRST.contains(Flags::ALL));
+    assert!(Flags::EMPTY.is_empty());
+  }
+
+  #[test]
+  fn debug() {
+    assert_eq!(format!("{:?}", Flags::EMPTY), "Flags(EMPTY)");
+    assert_eq!(format!("{:?}", Flags::ALL), "Flags(FIRST | SECOND)");
+  }
+}
@@ -0,0 +1,327 @@
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+
+use crate::entry::Entry;
+use crate::index::Detached;
+use crate::reason::Reason;
+use crate::reclaim::Evicted;
+
+/// Callbacks invoked on the lifecycle events of table entries.
+///
+/// Attach an implementation with [`PTab::with_hooks`] to keep auditing and
+/// resource accounting next to the table instead of at every call site. All
+/// methods have empty default implementations, so only the events of interest
+/// need to be overridden.
+///
+/// # Ordering
+///
+/// For any single entry, the events are delivered in order:
+///
+/// 1. [`on_insert`] runs before the entry becomes visible to other threads.
+/// 2. [`on_remove`] runs after the entry is unlinked from the table, while
+///    concurrent readers may still hold a reference to it.
+/// 3. [`on_reclaim`] runs once no reader can observe the value anymore, or
+///    when the table itself is dropped.
+///
+/// Every value inserted into a table with hooks is passed to [`on_reclaim`]
+/// exactly once, unless it is leaked by a panicking initializer.
+///
+/// # Requirements
+///
+/// Hooks are invoked from whichever thread performs the operation (or runs
+/// memory reclamation), so implementations must be [`Send`] and [`Sync`].
+/// Hooks **should not** panic and **should** avoid recursive table operations.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// use ptab::{Detached, Hooks, PTab};
+///
+/// #[derive(Default)]
+/// struct Live(Arc<AtomicUsize>);
+///
+/// impl Hooks<String> for Live {
+///   fn on_insert(&self, _index: Detached, _value: &String) {
+///     self.0.fetch_add(1, Ordering::Relaxed);
+///   }
+///
+///   fn on_reclaim(&self, _value: String) {
+///     self.0.fetch_sub(1, Ordering::Relaxed);
+///   }
+/// }
+///
+/// let live: Arc<AtomicUsize> = Arc::default();
+/// let table: PTab<String> = PTab::with_hooks(Live(Arc::clone(&live)));
+///
+/// table.insert("hello".to_string());
+/// assert_eq!(live.load(Ordering::Relaxed), 1);
+///
+/// drop(table);
+/// assert_eq!(live.load(Ordering::Relaxed), 0);
+/// ```
+///
+/// [`PTab::with_hooks`]: crate::public::PTab::with_hooks
+/// [`on_insert`]: Self::on_insert
+/// [`on_remove`]: Self::on_remove
+/// [`on_reclaim`]: Self::on_reclaim
+pub trait Hooks<T>: Send + Sync {
+  /// Called after `value` is initialized at `index`, before it is published.
+  #[inline]
+  fn on_insert(&self, _index: Detached, _value: &T) {}
+
+  /// Called after the entry at `index` is removed from the table for `reason`.
+  #[inline]
+  fn on_remove(&self, _index: Detached, _value: &T, _reason: Reason) {}
+
+  /// Called with ownership of a value once the table releases it.
+  ///
+  /// The default implementation drops `value`. See [Ordering](Hooks#ordering).
+  #[inline]
+  fn on_reclaim(&self, value: T) {
+    drop(value);
+  }
+}
+
+/// A type-erased set of [`Hooks`] shared by a table and its retired entries.
+pub(crate) struct Handler<T> {
+  hooks: Box<dyn Hooks<T>>,
+  // Instantiated where `T: 'static` is known, so removals can defer the
+  // reclamation of entries without requiring that bound themselves.
+  defer: fn(Arc<Self>, Evicted<Entry<T>>),
+}
+
+impl<T> Handler<T> {
+  #[inline]
+  pub(crate) fn new<H>(hooks: H) -> Self
+  where
+    T: 'static,
+    H: Hooks<T> + 'static,
+  {
+    Self {
+      hooks: Box::new(hooks),
+      defer: |handler, entry| entry.defer(move |entry| handler.on_reclaim(entry.take())),
+    }
+  }
+
+  /// Passes the value of a removed entry to [`Hooks::on_reclaim`] once no
+  /// reader can observe it anymore.
+  #[inline]
+  pub(crate) fn retire(self: &Arc<Self>, entry: Evicted<Entry<T>>) {
+    (self.defer)(Arc::clone(self), entry);
+  }
+
+  #[inline]
+  pub(crate) fn on_insert(&self, index: Detached, value: &T) {
+    self.hooks.on_insert(index, value);
+  }
+
+  #[inline]
+  pub(crate) fn on_remove(&self, index: Detached, value: &T, reason: Reason) {
+    self.hooks.on_remove(index, value, reason);
+  }
+
+  #[inline]
+  pub(crate) fn on_reclaim(&self, value: T) {
+    self.hooks.on_reclaim(value);
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(not(any(loom, shuttle)))]
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+  use std::sync::Mutex;
+
+  use crate::entry::Entry;
+  use crate::hooks::Hooks;
+  use crate::index::Detached;
+  use crate::params::Capacity;
+  use crate::params::ConstParams;
+  use crate::reason::Reason;
+  use crate::reclaim;
+  use crate::table::Table;
+
+  type DefParams = ConstParams<{ Capacity::DEF.as_usize() }>;
+
+  #[derive(Debug, PartialEq, Eq)]
+  enum Event {
+    Insert(Detached, usize),
+    Remove(Detached, usize, Reason),
+    Reclaim(usize),
+  }
+
+  #[derive(Clone, Default)]
+  struct Record {
+    events: Arc<Mutex<Vec<Event>>>,
+  }
+
+  impl Record {
+    fn take(&self) -> Vec<Event> {
+      std::mem::take(&mut *self.events.lock().unwrap())
+    }
+  }
+
+  impl Hooks<usize> for Record {
+    fn on_insert(&self, index: Detached, value: &usize) {
+      self
+        .events
+        .lock()
+        .unwrap()
+        .push(Event::Insert(index, *value));
+    }
+
+    fn on_remove(&self, index: Detached, value: &usize, reason: Reason) {
+      self
+        .events
+        .lock()
+        .unwrap()
+        .push(Event::Remove(index, *value, reason));
+    }
+
+    fn on_reclaim(&self, value: usize) {
+      self.events.lock().unwrap().push(Event::Reclaim(value));
+    }
+  }
+
+  struct Nothing;
+
+  impl Hooks<usize> for Nothing {}
+
+  #[test]
+  fn on_insert() {
+    let record: Record = Record::default();
+    let table: Table<usize, DefParams> = Table::with_hooks(record.clone());
+    let index: Detached = table.insert(123).unwrap();
+
+    assert_eq!(record.take(), [Event::Insert(index, 123)]);
+  }
+
+  #[test]
+  fn on_insert_write() {
+    let record: Record = Record::default();
+    let table: Table<usize, DefParams> = Table::with_hooks(record.clone());
+
+    let index: Detached = table
+      .write(|uninit, index| {
+        uninit.write(index.into_bits());
+      })
+      .unwrap();
+
+    assert_eq!(record.take(), [Event::Insert(index, index.into_bits())]);
+  }
+
+  #[test]
+  fn on_remove_and_reclaim() {
+    let record: Record = Record::default();
+    let table: Table<usize, DefParams> = Table::with_hooks(record.clone());
+    let index: Detached = table.insert(123).unwrap();
+
+    assert!(table.remove(index));
+    assert!(!table.remove(index));
+
+    reclaim::try_reclaim();
+
+    assert_eq!(
+      record.take(),
+      [
+        Event::Insert(index, 123),
+        Event::Remove(index, 123, Reason::Normal),
+        Event::Reclaim(123),
+      ],
+    );
+  }
+
+  #[test]
+  fn on_remove_linked() {
+    let record: Record = Record::default();
+    let table: Table<usize, DefParams> = Table::with_hooks(record.clone());
+    let a: Detached = table.insert(1).unwrap();
+    let b: Detached = table.insert(2).unwrap();
+
+    assert!(table.link(a, b));
+    assert!(table.remove(a));
+
+    // Reclamation may interleave with the linked removal.
+    let events: Vec<Event> = record
+      .take()
+      .into_iter()
+      .filter(|event| !matches!(event, Event::Reclaim(_)))
+      .collect();
+
+    assert_eq!(
+      events[2..4],
+      [
+        Event::Remove(a, 1, Reason::Normal),
+        Event::Remove(b, 2, Reason::Linked(a)),
+      ],
+    );
+  }
+
+  #[test]
+  fn on_reclaim_table_drop() {
+    let record: Record = Record::default();
+    let table: Table<usize, DefParams> = Table::with_hooks(record.clone());
+
+    for index in 0..4 {
+      assert!(table.insert(index).is_some());
+    }
+
+    assert_eq!(record.take().len(), 4);
+
+    drop(table);
+
+    let mut events: Vec<Event> = record.take();
+
+    events.sort_by_key(|event| match event {
+      Event::Reclaim(value) => *value,
+      _ => usize::MAX,
+    });
+
+    assert_eq!(
+      events,
+      [
+        Event::Reclaim(0),
+        Event::Reclaim(1),
+        Event::Reclaim(2),
+        Event::Reclaim(3),
+      ],
+    );
+  }
+
+  #[test]
+  fn on_reclaim_outlives_table() {
+    let record: Record = Record::default();
+    let table: Table<usize, DefParams> = Table::with_hooks(record.clone());
+    let index: Detached = table.insert(123).unwrap();
+
+    assert!(table.remove(index));
+    drop(table);
+
+    reclaim::try_reclaim();
+
+    assert_eq!(record.take().last(), Some(&Event::Reclaim(123)));
+  }
+
+  #[test]
+  fn entry_size() {
+    // The handler and the counters are stored once per table, not in every
+    // entry.
+    assert_eq!(size_of::<Entry<usize>>(), 3 * size_of::<usize>());
+  }
+
+  #[test]
+  fn default_hooks() {
+    let table: Table<usize, DefParams> = Table::with_hooks(Nothing);
+    let index: Detached = table.insert(123).unwrap();
+
+    assert!(table.remove(index));
+  }
+}
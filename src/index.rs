@@ -1,10 +1,23 @@
+use alloc::string::String;
+use alloc::vec::Vec;
 use core::fmt::Debug;
 use core::fmt::Display;
 use core::fmt::Formatter;
 use core::fmt::Result;
+use core::marker::PhantomData;
+use core::str::FromStr;
 
+use crate::error::ParseIndexError;
+use crate::params::DefaultParams;
 use crate::params::Params;
 use crate::params::ParamsExt;
+use crate::params::SlotLayout;
+
+/// The URL-safe alphabet used by [`Detached::to_compact`].
+const COMPACT: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// The number of bits encoded by each compact digit.
+const COMPACT_BITS: u32 = COMPACT.len().trailing_zeros();
 
 macro_rules! internal_index {
   ($name:ident) => {
@@ -101,9 +114,17 @@ macro_rules! internal_index {
 /// assert_eq!(table.read(idx2), Some(42));
 /// ```
 ///
+/// # Formatting
+///
+/// [`Display`] prints the raw bits. Use [`parts`] for the readable
+/// `<shard.slot.generation>` form, or [`to_compact`] for a short URL-safe
+/// encoding.
+///
 /// [`PTab`]: crate::public::PTab
 /// [`PTab::insert`]: crate::public::PTab::insert
 /// [`PTab::write`]: crate::public::PTab::write
+/// [`parts`]: Self::parts
+/// [`to_compact`]: Self::to_compact
 /// [ABA problem]: https://en.wikipedia.org/wiki/ABA_problem
 #[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[repr(transparent)]
@@ -148,7 +169,126 @@ impl Display for Detached {
   }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Detached {
+  #[inline]
+  fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    serializer.serialize_u64(self.bits as u64)
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Detached {
+  #[inline]
+  fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+  where
+    D: serde::Deserializer<'de>,
+  {
+    <usize as serde::Deserialize<'de>>::deserialize(deserializer).map(Self::from_bits)
+  }
+}
+
 impl Detached {
+  /// Splits this index into its components under the parameters `P`.
+  ///
+  /// The returned [`Parts`] format as `<shard.slot.generation>`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::{DefaultParams, PTab};
+  ///
+  /// let table: PTab<u64> = PTab::new();
+  /// let index = table.insert(1).unwrap();
+  /// let parts = index.parts::<DefaultParams>();
+  ///
+  /// assert_eq!(parts.to_string(), "<0.0.0>");
+  /// assert_eq!(parts.generation(), 0);
+  /// ```
+  #[inline]
+  pub const fn parts<P>(self) -> Parts<P>
+  where
+    P: Params + ?Sized,
+  {
+    let entry: usize = self.bits & P::ID_MASK_ENTRY;
+
+    Parts {
+      shard: entry >> P::ID_SHIFT_BLOCK,
+      slot: entry & P::ID_MASK_INDEX,
+      generation: self.bits >> P::ID_MASK_BITS,
+      marker: PhantomData,
+    }
+  }
+
+  /// Encodes this index as a short, URL-safe string.
+  ///
+  /// Uses the unpadded base64url alphabet, most significant digit first. See
+  /// [`from_compact`] to decode it.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::Detached;
+  ///
+  /// let index = Detached::from_bits(4096);
+  ///
+  /// assert_eq!(index.to_compact(), "BAA");
+  /// assert_eq!(Detached::from_compact("BAA"), Ok(index));
+  /// ```
+  ///
+  /// [`from_compact`]: Self::from_compact
+  pub fn to_compact(self) -> String {
+    let mut bits: usize = self.bits;
+    let mut data: Vec<u8> = Vec::new();
+
+    loop {
+      data.push(COMPACT[bits % COMPACT.len()]);
+      bits >>= COMPACT_BITS;
+
+      if bits == 0 {
+        break;
+      }
+    }
+
+    data.iter().rev().map(|byte| char::from(*byte)).collect()
+  }
+
+  /// Decodes an index produced by [`to_compact`].
+  ///
+  /// # Errors
+  ///
+  /// Returns [`ParseIndexError::Invalid`] if `input` is empty or contains a
+  /// character outside the alphabet, and [`ParseIndexError::OutOfRange`] if
+  /// the value does not fit in an index.
+  ///
+  /// [`to_compact`]: Self::to_compact
+  /// [`ParseIndexError::Invalid`]: crate::error::ParseIndexError::Invalid
+  /// [`ParseIndexError::OutOfRange`]: crate::error::ParseIndexError::OutOfRange
+  pub fn from_compact(input: &str) -> core::result::Result<Self, ParseIndexError> {
+    if input.is_empty() {
+      return Err(ParseIndexError::Invalid);
+    }
+
+    let mut bits: usize = 0;
+
+    for byte in input.bytes() {
+      let Some(digit) = COMPACT.iter().position(|item| *item == byte) else {
+        return Err(ParseIndexError::Invalid);
+      };
+
+      if bits > usize::MAX >> COMPACT_BITS {
+        return Err(ParseIndexError::OutOfRange);
+      }
+
+      bits = (bits << COMPACT_BITS) | digit;
+    }
+
+    Ok(Self::from_bits(bits))
+  }
+
   #[inline]
   pub(crate) const fn from_abstract<P>(other: Abstract<P>) -> Self
   where
@@ -156,6 +296,208 @@ impl Detached {
   {
     abstract_to_detached(other)
   }
+
+  /// Returns `true` if this index resolves to the entry stored under `other`.
+  ///
+  /// The table id bits are only compared where [`Params::TABLE_ID_BITS`] takes
+  /// effect.
+  #[inline]
+  pub(crate) const fn matches<P>(self, other: Self) -> bool
+  where
+    P: Params + ?Sized,
+  {
+    (self.bits ^ other.bits) & P::ID_MASK_CHECK == 0
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Index Parts
+// -----------------------------------------------------------------------------
+
+/// The components of a [`Detached`] index under the parameters `P`.
+///
+/// Returned by [`Detached::parts`]. Formats and parses as
+/// `<shard.slot.generation>`, modeled after BEAM pids:
+///
+/// - `shard`: the cache line holding the entry.
+/// - `slot`: the position of the entry within its cache line.
+/// - `generation`: incremented each time the slot is reused.
+///
+/// Parsing validates every component against `P`, so a handle printed by one
+/// table configuration is rejected by an incompatible one.
+///
+/// # Examples
+///
+/// ```
+/// use ptab::{ConstParams, Detached, PTab, Parts};
+///
+/// type Params = ConstParams<256>;
+///
+/// let table: PTab<u64, Params> = PTab::new();
+/// let index = table.insert(1).unwrap();
+///
+/// let text = index.parts::<Params>().to_string();
+/// let parts: Parts<Params> = text.parse().unwrap();
+///
+/// assert_eq!(Detached::from(parts), index);
+/// assert!("<99.0.0>".parse::<Parts<Params>>().is_err());
+/// ```
+pub struct Parts<P = DefaultParams>
+where
+  P: ?Sized,
+{
+  shard: usize,
+  slot: usize,
+  generation: usize,
+  marker: PhantomData<fn(P)>,
+}
+
+impl<P> Parts<P>
+where
+  P: Params + ?Sized,
+{
+  /// Creates the components of an index, or returns [`None`] if any of them
+  /// is out of range for `P`.
+  #[inline]
+  pub const fn new(shard: usize, slot: usize, generation: usize) -> Option<Self> {
+    if shard > P::ID_MASK_BLOCK || slot > P::ID_MASK_INDEX {
+      return None;
+    }
+
+    // Tables smaller than a cache line use a prefix of it.
+    if ((shard << P::ID_SHIFT_BLOCK) | slot) > P::ID_MASK_ENTRY {
+      return None;
+    }
+
+    if generation > usize::MAX >> P::ID_MASK_BITS {
+      return None;
+    }
+
+    Some(Self {
+      shard,
+      slot,
+      generation,
+      marker: PhantomData,
+    })
+  }
+
+  /// Returns the cache line holding the entry.
+  #[inline]
+  pub const fn shard(&self) -> usize {
+    self.shard
+  }
+
+  /// Returns the position of the entry within its cache line.
+  #[inline]
+  pub const fn slot(&self) -> usize {
+    self.slot
+  }
+
+  /// Returns the generation of the slot.
+  #[inline]
+  pub const fn generation(&self) -> usize {
+    self.generation
+  }
+
+  /// Reassembles the [`Detached`] index.
+  #[inline]
+  pub const fn into_detached(self) -> Detached {
+    let mut bits: usize = self.generation << P::ID_MASK_BITS;
+    bits |= self.shard << P::ID_SHIFT_BLOCK;
+    bits |= self.slot;
+    Detached::from_bits(bits)
+  }
+}
+
+impl<P> Clone for Parts<P>
+where
+  P: ?Sized,
+{
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<P> Copy for Parts<P> where P: ?Sized {}
+
+impl<P> PartialEq for Parts<P>
+where
+  P: ?Sized,
+{
+  #[inline]
+  fn eq(&self, other: &Self) -> bool {
+    self.shard == other.shard && self.slot == other.slot && self.generation == other.generation
+  }
+}
+
+impl<P> Eq for Parts<P> where P: ?Sized {}
+
+impl<P> Debug for Parts<P>
+where
+  P: ?Sized,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    f.debug_struct("Parts")
+      .field("shard", &self.shard)
+      .field("slot", &self.slot)
+      .field("generation", &self.generation)
+      .finish()
+  }
+}
+
+impl<P> Display for Parts<P>
+where
+  P: ?Sized,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    write!(f, "<{}.{}.{}>", self.shard, self.slot, self.generation)
+  }
+}
+
+impl<P> FromStr for Parts<P>
+where
+  P: Params + ?Sized,
+{
+  type Err = ParseIndexError;
+
+  fn from_str(input: &str) -> core::result::Result<Self, Self::Err> {
+    let Some(inner) = input
+      .strip_prefix('<')
+      .and_then(|inner| inner.strip_suffix('>'))
+    else {
+      return Err(ParseIndexError::Invalid);
+    };
+
+    let mut split = inner.split('.').map(parse_component);
+
+    let (Some(shard), Some(slot), Some(generation), None) =
+      (split.next(), split.next(), split.next(), split.next())
+    else {
+      return Err(ParseIndexError::Invalid);
+    };
+
+    Self::new(shard?, slot?, generation?).ok_or(ParseIndexError::OutOfRange)
+  }
+}
+
+impl<P> From<Parts<P>> for Detached
+where
+  P: Params + ?Sized,
+{
+  #[inline]
+  fn from(other: Parts<P>) -> Self {
+    other.into_detached()
+  }
+}
+
+/// Parses a decimal component of [`Parts`].
+fn parse_component(input: &str) -> core::result::Result<usize, ParseIndexError> {
+  if input.is_empty() || !input.bytes().all(|byte| byte.is_ascii_digit()) {
+    return Err(ParseIndexError::Invalid);
+  }
+
+  input.parse().map_err(|_| ParseIndexError::OutOfRange)
 }
 
 // -----------------------------------------------------------------------------
@@ -205,10 +547,9 @@ const fn detached_to_abstract<P>(detached: Detached) -> Abstract<P>
 where
   P: Params + ?Sized,
 {
-  let mut value: usize = detached.into_bits() & !P::ID_MASK_ENTRY;
-  value |= (detached.into_bits() >> P::ID_SHIFT_BLOCK) & P::ID_MASK_BLOCK;
-  value |= (detached.into_bits() & P::ID_MASK_INDEX) << P::ID_SHIFT_INDEX;
-  Abstract::new(value)
+  let value: usize = detached.into_bits() & !P::ID_MASK_ENTRY;
+  let entry: usize = slot_to_id::<P>(detached.into_bits() & P::ID_MASK_ENTRY);
+  Abstract::new(value | entry)
 }
 
 /// Extracts the [`Concrete`] cache-aware index from a [`Detached`] index.
@@ -226,10 +567,63 @@ const fn abstract_to_concrete<P>(abstract_idx: Abstract<P>) -> Concrete<P>
 where
   P: Params + ?Sized,
 {
-  let mut value: usize = 0;
-  value += (abstract_idx.get() & P::ID_MASK_BLOCK) << P::ID_SHIFT_BLOCK;
-  value += (abstract_idx.get() >> P::ID_SHIFT_INDEX) & P::ID_MASK_INDEX;
-  Concrete::new(value)
+  Concrete::new(id_to_slot::<P>(abstract_idx.get() & P::ID_MASK_ENTRY))
+}
+
+/// Maps the low bits of an id to its slot, according to [`Params::SLOT_LAYOUT`].
+#[inline]
+const fn id_to_slot<P>(id: usize) -> usize
+where
+  P: Params + ?Sized,
+{
+  match P::ID_LAYOUT {
+    SlotLayout::Striped => {
+      let mut value: usize = 0;
+      value += (id & P::ID_MASK_BLOCK) << P::ID_SHIFT_BLOCK;
+      value += (id >> P::ID_SHIFT_INDEX) & P::ID_MASK_INDEX;
+      value
+    }
+    SlotLayout::Sequential => id,
+    SlotLayout::Custom(map) => permute(id, map, false),
+  }
+}
+
+/// The inverse of [`id_to_slot`].
+#[inline]
+pub(crate) const fn slot_to_id<P>(slot: usize) -> usize
+where
+  P: Params + ?Sized,
+{
+  match P::ID_LAYOUT {
+    SlotLayout::Striped => {
+      let mut value: usize = 0;
+      value |= (slot >> P::ID_SHIFT_BLOCK) & P::ID_MASK_BLOCK;
+      value |= (slot & P::ID_MASK_INDEX) << P::ID_SHIFT_INDEX;
+      value
+    }
+    SlotLayout::Sequential => slot,
+    SlotLayout::Custom(map) => permute(slot, map, true),
+  }
+}
+
+/// Moves bit `i` of `value` to bit `map[i]`, or back if `inverse` is set.
+#[inline]
+const fn permute(value: usize, map: &[u32], inverse: bool) -> usize {
+  let mut output: usize = 0;
+  let mut index: usize = 0;
+
+  while index < map.len() {
+    let (from, to): (u32, u32) = if inverse {
+      (map[index], index as u32)
+    } else {
+      (index as u32, map[index])
+    };
+
+    output |= ((value >> from) & 1) << to;
+    index += 1;
+  }
+
+  output
 }
 
 /// Converts an [`Abstract`] sequential index to a [`Detached`] index.
@@ -254,14 +648,35 @@ where
 mod tests {
   use std::collections::HashSet;
 
+  use crate::error::ParseIndexError;
   use crate::index::Abstract;
   use crate::index::Concrete;
   use crate::index::Detached;
+  use crate::index::Parts;
   use crate::params::CACHE_LINE_SLOTS;
+  use crate::params::Capacity;
+  use crate::params::ConstParams;
   use crate::params::Params;
   use crate::params::ParamsExt;
+  use crate::params::SlotLayout;
   use crate::utils::each_capacity;
 
+  type MinParams = ConstParams<{ Capacity::MIN.as_usize() }>;
+
+  struct SequentialParams;
+
+  impl Params for SequentialParams {
+    const LENGTH: Capacity = Capacity::MIN;
+    const SLOT_LAYOUT: SlotLayout = SlotLayout::Sequential;
+  }
+
+  struct ReversedParams;
+
+  impl Params for ReversedParams {
+    const LENGTH: Capacity = Capacity::MIN;
+    const SLOT_LAYOUT: SlotLayout = SlotLayout::Custom(&[3, 2, 1, 0]);
+  }
+
   #[expect(clippy::clone_on_copy)]
   #[test]
   fn abstract_clone_copy() {
@@ -326,6 +741,114 @@ mod tests {
     assert_eq!(data, bits);
   }
 
+  #[cfg(feature = "serde")]
+  #[test]
+  fn detached_serde_roundtrip() {
+    let index: Detached = Detached::from_bits(usize::MAX >> 2);
+    let value: String = serde_json::to_string(&index).unwrap();
+
+    assert_eq!(value, (usize::MAX >> 2).to_string());
+    assert_eq!(serde_json::from_str::<Detached>(&value).unwrap(), index);
+  }
+
+  #[test]
+  fn parts_roundtrip() {
+    each_capacity!({
+      for generation in [0, 1, usize::MAX >> P::ID_MASK_BITS] {
+        for index in (0..P::LENGTH.as_usize()).step_by(7) {
+          let detached: Detached = Detached::from_bits((generation << P::ID_MASK_BITS) | index);
+          let parts: Parts<P> = detached.parts();
+          let text: String = parts.to_string();
+
+          assert_eq!(parts.generation(), generation);
+          assert_eq!(parts.shard() * CACHE_LINE_SLOTS + parts.slot(), index);
+          assert_eq!(text.parse::<Parts<P>>(), Ok(parts));
+          assert_eq!(Detached::from(parts), detached);
+        }
+      }
+    });
+  }
+
+  #[test]
+  fn parts_display() {
+    type P = ConstParams<{ Capacity::DEF.as_usize() }>;
+
+    let detached: Detached = Detached::from_bits((3 << P::ID_MASK_BITS) | (CACHE_LINE_SLOTS + 2));
+
+    assert_eq!(detached.parts::<P>().to_string(), "<1.2.3>");
+  }
+
+  #[test]
+  fn parts_parse_invalid() {
+    type P = ConstParams<{ Capacity::DEF.as_usize() }>;
+
+    for input in [
+      "",
+      "<>",
+      "<1.2>",
+      "<1.2.3.4>",
+      "1.2.3",
+      "<1.2.3",
+      "<a.2.3>",
+      "<+1.2.3>",
+      "<1..3>",
+    ] {
+      assert_eq!(
+        input.parse::<Parts<P>>(),
+        Err(ParseIndexError::Invalid),
+        "{input}"
+      );
+    }
+  }
+
+  #[test]
+  fn parts_parse_out_of_range() {
+    type P = ConstParams<{ Capacity::DEF.as_usize() }>;
+
+    let shard: String = format!("<{}.0.0>", P::BLOCKS.get());
+    let slot: String = format!("<0.{CACHE_LINE_SLOTS}.0>");
+    let generation: String = format!("<0.0.{}>", (usize::MAX >> P::ID_MASK_BITS) + 1);
+    let overflow: String = format!("<0.0.{}0>", usize::MAX);
+
+    for input in [shard, slot, generation, overflow] {
+      assert_eq!(
+        input.parse::<Parts<P>>(),
+        Err(ParseIndexError::OutOfRange),
+        "{input}"
+      );
+    }
+  }
+
+  #[test]
+  fn compact_roundtrip() {
+    for bits in [0, 1, 63, 64, 4095, 4096, usize::MAX >> 1, usize::MAX] {
+      let detached: Detached = Detached::from_bits(bits);
+      let compact: String = detached.to_compact();
+
+      assert!(
+        compact
+          .bytes()
+          .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_')
+      );
+      assert_eq!(Detached::from_compact(&compact), Ok(detached));
+    }
+
+    assert_eq!(Detached::from_bits(0).to_compact(), "A");
+    assert_eq!(Detached::from_bits(63).to_compact(), "_");
+  }
+
+  #[test]
+  fn compact_invalid() {
+    let overflow: String = format!("B{}", Detached::from_bits(usize::MAX).to_compact());
+
+    assert_eq!(Detached::from_compact(""), Err(ParseIndexError::Invalid));
+    assert_eq!(Detached::from_compact("A=B"), Err(ParseIndexError::Invalid));
+    assert_eq!(
+      Detached::from_compact(&overflow),
+      Err(ParseIndexError::OutOfRange)
+    );
+  }
+
   #[test]
   fn abstract_to_concrete_covers_all_slots() {
     each_capacity!({
@@ -413,4 +936,49 @@ mod tests {
       }
     });
   }
+
+  #[test]
+  fn sequential_layout() {
+    for index in 0..SequentialParams::LENGTH.as_usize() * 4 {
+      let abstract_idx: Abstract<SequentialParams> = Abstract::new(index);
+      let concrete_idx: Concrete<SequentialParams> = Concrete::from_abstract(abstract_idx);
+      let detached_idx: Detached = Detached::from_abstract(abstract_idx);
+
+      assert_eq!(
+        concrete_idx.get(),
+        index % SequentialParams::LENGTH.as_usize()
+      );
+      assert_eq!(detached_idx.into_bits(), index);
+      assert_eq!(Abstract::from_detached(detached_idx), abstract_idx);
+    }
+  }
+
+  #[test]
+  fn custom_layout() {
+    let mut used: HashSet<usize> = HashSet::new();
+
+    for index in 0..ReversedParams::LENGTH.as_usize() * 4 {
+      let abstract_idx: Abstract<ReversedParams> = Abstract::new(index);
+      let concrete_idx: Concrete<ReversedParams> = Concrete::from_abstract(abstract_idx);
+      let detached_idx: Detached = Detached::from_abstract(abstract_idx);
+
+      // The low four bits are reversed.
+      let low: usize = index % ReversedParams::LENGTH.as_usize();
+      assert_eq!(
+        concrete_idx.get(),
+        (low.reverse_bits() >> (usize::BITS - 4))
+      );
+      assert_eq!(Abstract::from_detached(detached_idx), abstract_idx);
+
+      used.insert(concrete_idx.get());
+    }
+
+    assert_eq!(used.len(), ReversedParams::LENGTH.as_usize());
+  }
+
+  #[test]
+  fn striped_layout_is_default() {
+    assert_eq!(MinParams::ID_LAYOUT, SlotLayout::Striped);
+    assert_eq!(SequentialParams::ID_LAYOUT, SlotLayout::Sequential);
+  }
 }
@@ -0,0 +1,355 @@
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::fmt::Result;
+use core::mem::MaybeUninit;
+
+use crate::entry::Entry;
+use crate::index::Detached;
+use crate::key::Handle;
+use crate::padded::CachePadded;
+use crate::params::ConstParams;
+use crate::params::Params;
+use crate::params::ParamsExt;
+use crate::params::StaticParams;
+use crate::reclaim::Atomic;
+use crate::reclaim::Guard;
+use crate::sync::atomic::AtomicUsize;
+use crate::table::Storage;
+use crate::table::Table;
+use crate::table::Volatile;
+use crate::table::WeakKeys;
+
+/// A lock-free concurrent table that stores its arrays inline.
+///
+/// Behaves like [`PTab`], but can be created in `const` contexts and never
+/// allocates its arrays or state on the heap, so it can be placed in a
+/// `static` and used as a global table without lazy initialization.
+///
+/// # Allocation
+///
+/// `StaticPTab` is not allocation-free and still needs a global allocator:
+/// every inserted value is boxed by the memory reclamation backend, which
+/// also allocates to defer dropping removed values. Only the table itself is
+/// inline, so creating one allocates nothing.
+///
+/// The capacity `N` of [`ConstParams`] must be an exact power of two in the
+/// range <code>[Capacity::MIN]..=[Capacity::MAX]</code>; other values fail to
+/// compile instead of being rounded. Statically allocated tables have no
+/// [`Hooks`] and always use table id `0`, see [`Params::TABLE_ID_BITS`].
+///
+/// # Examples
+///
+/// ```
+/// use ptab::{ConstParams, StaticPTab};
+///
+/// static PROCS: StaticPTab<&str, ConstParams<1024>> = StaticPTab::new();
+///
+/// let index = PROCS.insert("init").unwrap();
+///
+/// assert_eq!(PROCS.read(index), Some("init"));
+/// assert!(PROCS.remove(index));
+/// ```
+///
+/// ```compile_fail
+/// use ptab::{ConstParams, StaticPTab};
+///
+/// // error: 1000 is not a power of two
+/// static PROCS: StaticPTab<u64, ConstParams<1000>> = StaticPTab::new();
+/// ```
+///
+/// [`PTab`]: crate::public::PTab
+/// [`ConstParams`]: crate::params::ConstParams
+/// [`Hooks`]: crate::hooks::Hooks
+/// [`Params::TABLE_ID_BITS`]: crate::params::Params::TABLE_ID_BITS
+/// [Capacity::MIN]: crate::params::Capacity::MIN
+/// [Capacity::MAX]: crate::params::Capacity::MAX
+pub struct StaticPTab<T, P>
+where
+  P: StaticParams,
+{
+  inner: Table<T, P, Inline<T, P>>,
+}
+
+impl<T, const N: usize> StaticPTab<T, ConstParams<N>> {
+  /// Creates a new, empty table.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::{ConstParams, StaticPTab};
+  ///
+  /// let table: StaticPTab<u64, ConstParams<64>> = StaticPTab::new();
+  /// assert!(table.is_empty());
+  /// ```
+  #[inline]
+  #[must_use]
+  pub const fn new() -> Self {
+    const {
+      assert!(
+        N == ConstParams::<N>::LENGTH.as_usize(),
+        "`N` must be a power of two within `Capacity::MIN..=Capacity::MAX`",
+      );
+    }
+
+    Self {
+      inner: Table::with_storage(Inline {
+        volatile: CachePadded::new(Volatile::new()),
+        data: CachePadded::new([const { Atomic::null() }; N]),
+        slot: CachePadded::new([const { AtomicUsize::new(0) }; N]),
+      }),
+    }
+  }
+}
+
+impl<T, P> StaticPTab<T, P>
+where
+  P: StaticParams,
+{
+  /// Returns the maximum number of entries the table can hold.
+  ///
+  /// See [`PTab::capacity`].
+  ///
+  /// [`PTab::capacity`]: crate::public::PTab::capacity
+  #[inline]
+  pub const fn capacity(&self) -> usize {
+    self.inner.cap()
+  }
+
+  /// Returns the number of entries currently in the table.
+  ///
+  /// See [`PTab::len`].
+  ///
+  /// [`PTab::len`]: crate::public::PTab::len
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.inner.len() as usize
+  }
+
+  /// Returns `true` if the table contains no entries.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.inner.is_empty()
+  }
+
+  /// Inserts a value into the table and returns its index.
+  ///
+  /// See [`PTab::insert`].
+  ///
+  /// [`PTab::insert`]: crate::public::PTab::insert
+  #[inline]
+  pub fn insert(&self, value: T) -> Option<Detached>
+  where
+    T: 'static,
+  {
+    self.inner.insert(value)
+  }
+
+  /// Inserts a value using an initialization function that receives its
+  /// index.
+  ///
+  /// See [`PTab::write`] for the requirements on `init`.
+  ///
+  /// [`PTab::write`]: crate::public::PTab::write
+  #[inline]
+  pub fn write<F>(&self, init: F) -> Option<Detached>
+  where
+    T: 'static,
+    F: FnOnce(&mut MaybeUninit<T>, Detached),
+  {
+    self.inner.write(init)
+  }
+
+  /// Removes the entry at the given index.
+  ///
+  /// See [`PTab::remove`].
+  ///
+  /// [`PTab::remove`]: crate::public::PTab::remove
+  #[inline]
+  pub fn remove(&self, index: impl Handle<T, P>) -> bool {
+    self.inner.remove(index.detached())
+  }
+
+  /// Returns `true` if an entry exists at the given index.
+  #[inline]
+  pub fn exists(&self, index: impl Handle<T, P>) -> bool {
+    self.inner.exists(index.detached(), &Guard::new())
+  }
+
+  /// Accesses an entry by index, applying a function to it.
+  ///
+  /// See [`PTab::with`].
+  ///
+  /// [`PTab::with`]: crate::public::PTab::with
+  #[inline]
+  pub fn with<F, R>(&self, index: impl Handle<T, P>, f: F) -> Option<R>
+  where
+    F: Fn(&T) -> R,
+  {
+    self.inner.with(index.detached(), &Guard::new(), f)
+  }
+
+  /// Returns a copy of the entry at the given index.
+  #[inline]
+  pub fn read(&self, index: impl Handle<T, P>) -> Option<T>
+  where
+    T: Copy,
+  {
+    self.inner.read(index.detached(), &Guard::new())
+  }
+
+  /// Returns an iterator over the indices of the table's entries.
+  ///
+  /// See [`PTab::weak_keys`] for the consistency model.
+  ///
+  /// [`PTab::weak_keys`]: crate::public::PTab::weak_keys
+  #[inline]
+  pub fn weak_keys(&self) -> WeakKeys<'_, T, P> {
+    self.inner.weak_keys(Guard::new())
+  }
+
+  /// Returns a snapshot of the table's operation counters.
+  #[cfg(feature = "stats")]
+  #[inline]
+  pub fn stats(&self) -> crate::stats::Stats {
+    self.inner.stats()
+  }
+}
+
+impl<T, P> Debug for StaticPTab<T, P>
+where
+  T: Debug,
+  P: StaticParams,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    f.debug_struct("StaticPTab")
+      .field("values", &self.inner)
+      .field("params", &P::debug())
+      .finish()
+  }
+}
+
+impl<T, const N: usize> Default for StaticPTab<T, ConstParams<N>> {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Table arrays stored inline.
+#[repr(C)]
+struct Inline<T, P>
+where
+  P: StaticParams,
+{
+  volatile: CachePadded<Volatile<P>>,
+  data: CachePadded<P::Array<Atomic<Entry<T>>>>,
+  slot: CachePadded<P::Array<AtomicUsize>>,
+}
+
+// SAFETY:
+// - `StaticPTab::new` only creates arrays of `N == P::LENGTH` elements.
+// - The state and both arrays are wrapped in `CachePadded`, which aligns and
+//   pads them to `CACHE_LINE`, the line width of every `ConstParams`.
+// - Both arrays are created from null pointers and zeros, and the state from
+//   `Volatile::new`.
+unsafe impl<T, P> Storage<T, P> for Inline<T, P>
+where
+  P: StaticParams,
+{
+  #[inline]
+  fn volatile(&self) -> &Volatile<P> {
+    &self.volatile
+  }
+
+  #[inline]
+  fn data(&self) -> &[Atomic<Entry<T>>] {
+    (*self.data).as_ref()
+  }
+
+  #[inline]
+  fn data_mut(&mut self) -> &mut [Atomic<Entry<T>>] {
+    (*self.data).as_mut()
+  }
+
+  #[inline]
+  fn slot(&self) -> &[AtomicUsize] {
+    (*self.slot).as_ref()
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cfg(test)]
+mod tests {
+  use std::collections::HashSet;
+  use std::sync::Arc;
+  use std::thread;
+
+  use crate::index::Detached;
+  use crate::inline::StaticPTab;
+  use crate::params::Capacity;
+  use crate::params::ConstParams;
+
+  type MinParams = ConstParams<{ Capacity::MIN.as_usize() }>;
+
+  static TABLE: StaticPTab<usize, ConstParams<1024>> = StaticPTab::new();
+
+  #[test]
+  fn fill() {
+    let table: StaticPTab<usize, MinParams> = StaticPTab::new();
+
+    let keys: HashSet<Detached> = (0..table.capacity())
+      .map(|value| table.insert(value).unwrap())
+      .collect();
+
+    assert_eq!(keys.len(), table.capacity());
+    assert_eq!(table.len(), table.capacity());
+    assert_eq!(table.insert(0), None);
+
+    for key in &keys {
+      assert!(table.remove(*key));
+    }
+
+    assert!(table.is_empty());
+    assert_eq!(table.weak_keys().count(), 0);
+  }
+
+  #[test]
+  fn drop_values() {
+    let table: StaticPTab<Arc<()>, MinParams> = StaticPTab::new();
+    let value: Arc<()> = Arc::new(());
+
+    for _ in 0..4 {
+      table.insert(Arc::clone(&value)).unwrap();
+    }
+
+    drop(table);
+
+    assert_eq!(Arc::strong_count(&value), 1);
+  }
+
+  #[test]
+  fn concurrent() {
+    let threads: Vec<_> = (0..8)
+      .map(|thread| {
+        thread::spawn(move || {
+          for value in 0..1000 {
+            let index: Detached = TABLE.insert(thread * value).unwrap();
+
+            assert_eq!(TABLE.read(index), Some(thread * value));
+            assert!(TABLE.remove(index));
+          }
+        })
+      })
+      .collect();
+
+    for thread in threads {
+      thread.join().unwrap();
+    }
+
+    assert!(TABLE.is_empty());
+  }
+}
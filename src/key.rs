@@ -0,0 +1,314 @@
+use core::cmp::Ordering;
+use core::fmt::Debug;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::hash::Hash;
+use core::hash::Hasher;
+use core::marker::PhantomData;
+
+use crate::index::Detached;
+use crate::params::DefaultParams;
+
+/// An index that can only be used with tables of type `PTab<T, P>`.
+///
+/// Returned by [`PTab::insert_key`] and [`PTab::write_key`]. A `Key` has the
+/// same representation as a [`Detached`] index, but carries the value and
+/// parameter types of its table, so passing it to a table of a different type
+/// fails to compile. Use [`Brand`] to give otherwise identical tables
+/// distinct types.
+///
+/// [`Detached`] remains the untyped escape hatch: every method accepting a
+/// `Key` also accepts a [`Detached`] index, and [`PTab::key`] converts back.
+///
+/// # Examples
+///
+/// ```
+/// use ptab::{Brand, Key, PTab};
+///
+/// struct Users;
+///
+/// let users: PTab<&str, Brand<Users>> = PTab::new();
+/// let alice: Key<&str, Brand<Users>> = users.insert_key("alice").unwrap();
+///
+/// assert_eq!(users.read(alice), Some("alice"));
+/// assert_eq!(users.key(alice.detached()), Some(alice));
+/// ```
+///
+/// [`PTab::insert_key`]: crate::public::PTab::insert_key
+/// [`PTab::write_key`]: crate::public::PTab::write_key
+/// [`PTab::key`]: crate::public::PTab::key
+/// [`Brand`]: crate::params::Brand
+#[repr(transparent)]
+pub struct Key<T, P = DefaultParams>
+where
+  P: ?Sized,
+{
+  index: Detached,
+  marker: PhantomData<fn(&T, &P)>,
+}
+
+impl<T, P> Key<T, P>
+where
+  P: ?Sized,
+{
+  #[inline]
+  pub(crate) const fn new(index: Detached) -> Self {
+    Self {
+      index,
+      marker: PhantomData,
+    }
+  }
+
+  /// Returns the untyped index of this key.
+  #[inline]
+  pub const fn detached(self) -> Detached {
+    self.index
+  }
+}
+
+impl<T, P> Clone for Key<T, P>
+where
+  P: ?Sized,
+{
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T, P> Copy for Key<T, P> where P: ?Sized {}
+
+impl<T, P> PartialEq for Key<T, P>
+where
+  P: ?Sized,
+{
+  #[inline]
+  fn eq(&self, other: &Self) -> bool {
+    self.index == other.index
+  }
+}
+
+impl<T, P> Eq for Key<T, P> where P: ?Sized {}
+
+impl<T, P> PartialOrd for Key<T, P>
+where
+  P: ?Sized,
+{
+  #[inline]
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<T, P> Ord for Key<T, P>
+where
+  P: ?Sized,
+{
+  #[inline]
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.index.cmp(&other.index)
+  }
+}
+
+impl<T, P> Hash for Key<T, P>
+where
+  P: ?Sized,
+{
+  #[inline]
+  fn hash<H>(&self, state: &mut H)
+  where
+    H: Hasher,
+  {
+    self.index.hash(state);
+  }
+}
+
+impl<T, P> Debug for Key<T, P>
+where
+  P: ?Sized,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_tuple("Key").field(&self.index).finish()
+  }
+}
+
+impl<T, P> Display for Key<T, P>
+where
+  P: ?Sized,
+{
+  #[inline]
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    Display::fmt(&self.index, f)
+  }
+}
+
+impl<T, P> From<Key<T, P>> for Detached
+where
+  P: ?Sized,
+{
+  #[inline]
+  fn from(other: Key<T, P>) -> Self {
+    other.index
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Handle
+// -----------------------------------------------------------------------------
+
+/// An index accepted by the methods of `PTab<T, P>`.
+///
+/// Implemented by [`Key<T, P>`] for the matching table type and by
+/// [`Detached`] for every table. This trait is sealed and cannot be
+/// implemented outside of this crate.
+///
+/// [`Key<T, P>`]: crate::key::Key
+pub trait Handle<T, P>: Copy + Sealed
+where
+  P: ?Sized,
+{
+  /// Returns the untyped index.
+  fn detached(self) -> Detached;
+}
+
+mod private {
+  pub trait Sealed {}
+}
+
+use private::Sealed;
+
+impl Sealed for Detached {}
+impl<T, P> Sealed for Key<T, P> where P: ?Sized {}
+
+impl<T, P> Handle<T, P> for Detached
+where
+  P: ?Sized,
+{
+  #[inline]
+  fn detached(self) -> Detached {
+    self
+  }
+}
+
+impl<T, P> Handle<T, P> for Key<T, P>
+where
+  P: ?Sized,
+{
+  #[inline]
+  fn detached(self) -> Detached {
+    self.index
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(not(any(loom, shuttle)))]
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cfg(test)]
+mod tests {
+  use std::collections::HashSet;
+
+  use crate::flags::Flags;
+  use crate::index::Detached;
+  use crate::key::Key;
+  use crate::params::Brand;
+  use crate::params::Capacity;
+  use crate::params::ConstParams;
+  use crate::params::Params;
+  use crate::public::PTab;
+
+  type MinParams = ConstParams<{ Capacity::MIN.as_usize() }>;
+
+  struct Users;
+  struct Groups;
+
+  type UserParams = Brand<Users, MinParams>;
+  type GroupParams = Brand<Groups, MinParams>;
+
+  #[test]
+  fn brand_params() {
+    assert_eq!(UserParams::LENGTH, MinParams::LENGTH);
+    assert_eq!(GroupParams::LENGTH, MinParams::LENGTH);
+  }
+
+  #[test]
+  fn insert_key() {
+    let table: PTab<usize, UserParams> = PTab::new();
+    let key: Key<usize, UserParams> = table.insert_key(1).unwrap();
+
+    assert!(table.exists(key));
+    assert_eq!(table.read(key), Some(1));
+    assert_eq!(table.with(key, |value| value + 1), Some(2));
+    assert_eq!(table.set_flags(key, Flags::FIRST), Some(Flags::EMPTY));
+    assert_eq!(table.flags(key), Some(Flags::FIRST));
+    #[cfg(feature = "std")]
+    assert_eq!(table.import(table.export(key)), Some(key.detached()));
+    assert!(table.remove(key));
+    assert!(!table.exists(key));
+  }
+
+  #[test]
+  fn write_key() {
+    struct Node {
+      this: Key<Node, UserParams>,
+    }
+
+    let table: PTab<Node, UserParams> = PTab::new();
+    let key: Key<Node, UserParams> = table
+      .write_key(|slot, this| {
+        slot.write(Node { this });
+      })
+      .unwrap();
+
+    assert_eq!(table.with(key, |node| node.this), Some(key));
+  }
+
+  #[test]
+  fn detached_escape_hatch() {
+    let users: PTab<usize, UserParams> = PTab::new();
+    let groups: PTab<usize, GroupParams> = PTab::new();
+
+    let user: Key<usize, UserParams> = users.insert_key(1).unwrap();
+    let index: Detached = user.into();
+
+    // Untyped indices are accepted by any table...
+    assert!(!groups.exists(index));
+    assert!(users.exists(index));
+
+    // ...and can be upgraded while the entry exists.
+    assert_eq!(users.key(index), Some(user));
+    assert_eq!(groups.key(index), None);
+
+    assert!(users.remove(index));
+    assert_eq!(users.key(index), None);
+  }
+
+  #[test]
+  fn link_keys() {
+    let table: PTab<usize, UserParams> = PTab::new();
+    let a: Key<usize, UserParams> = table.insert_key(1).unwrap();
+    let b: Detached = table.insert(2).unwrap();
+
+    assert!(table.link(a, b));
+    assert!(table.remove(a));
+    assert!(!table.exists(b));
+  }
+
+  #[test]
+  fn traits() {
+    let table: PTab<usize, UserParams> = PTab::new();
+    let a: Key<usize, UserParams> = table.insert_key(1).unwrap();
+    let b: Key<usize, UserParams> = table.insert_key(2).unwrap();
+
+    let set: HashSet<Key<usize, UserParams>> = [a, b, a].into_iter().collect();
+
+    assert_eq!(set.len(), 2);
+    assert_ne!(a, b);
+    assert_eq!(a.cmp(&b), a.detached().cmp(&b.detached()));
+    assert_eq!(format!("{a:?}"), format!("Key({:?})", a.detached()));
+    assert_eq!(a.to_string(), a.detached().to_string());
+  }
+}
@@ -57,7 +57,15 @@
 //! ```
 //!
 //! Capacity is always rounded up to the nearest power of two and clamped
-//! to the range <code>[Capacity::MIN]..=[Capacity::MAX]</code>.
+//! to the range <code>[Capacity::MIN]..=[Capacity::MAX]</code>. To accept an
+//! exact number of entries instead, use [`ExactParams`]:
+//!
+//! ```
+//! use ptab::{PTab, ExactParams};
+//!
+//! let table: PTab<u64, ExactParams<1000>> = PTab::new();
+//! assert_eq!(table.capacity(), 1000);
+//! ```
 //!
 //! # Concurrency
 //!
@@ -89,24 +97,78 @@
 //! }
 //! ```
 //!
+//! ## Static Tables
+//!
+//! [`StaticPTab`] stores its arrays inline and can be created in `const`
+//! contexts, so it can serve as a global table without heap-allocated arrays
+//! or lazy initialization. Inserted values are still boxed, so it needs a
+//! global allocator all the same:
+//!
+//! ```
+//! use ptab::{ConstParams, StaticPTab};
+//!
+//! static PROCS: StaticPTab<u64, ConstParams<1024>> = StaticPTab::new();
+//!
+//! let pid = PROCS.insert(42).unwrap();
+//! assert_eq!(PROCS.read(pid), Some(42));
+//! ```
+//!
+//! ## Local Tables
+//!
+//! [`LocalPTab`] is a single-threaded table that skips atomics and deferred
+//! reclamation but issues indices exactly like [`PTab`]. Owned tables convert
+//! into each other without invalidating any index:
+//!
+//! ```
+//! use ptab::{LocalPTab, PTab};
+//!
+//! let local: LocalPTab<u64> = LocalPTab::new();
+//! let idx = local.insert(42).unwrap();
+//!
+//! let table: PTab<u64> = PTab::from(local);
+//! assert_eq!(table.read(idx), Some(42));
+//! ```
+//!
 //! ## Memory Reclamation
 //!
 //! Removed entries are reclaimed using epoch-based memory management via
 //! [`sdd`]. This ensures concurrent readers can safely access entries even
 //! while other threads are removing them.
 //!
+//! Without the `std` feature, there is no thread-local epoch tracking, so all
+//! threads share a single global epoch instead. Removed entries are still
+//! reclaimed, but a thread holding a guard delays reclamation for every
+//! other thread.
+//!
 //! # Memory Layout
 //!
 //! The table uses a cache-line-aware memory layout to minimize false sharing
 //! between threads. Consecutive allocations are distributed across different
 //! cache lines, reducing contention when multiple threads operate on
 //! recently-allocated entries. See [`CACHE_LINE_SLOTS`] for the distribution
-//! stride.
+//! stride, and [`Params::CACHE_LINE`] to tune it for a specific line width.
+//!
+//! Workloads that favor scans over concurrent inserts can place consecutive
+//! allocations next to each other instead, see [`SlotLayout`].
 //!
 //! # Capacity Limits
 //!
 //! Capacity is bounded by [`Capacity::MIN`] and [`Capacity::MAX`]. The default
-//! is [`Capacity::DEF`]. When full, [`PTab::insert()`] returns [`None`].
+//! is [`Capacity::DEF`]. [`Params::LIMIT`] caps the number of entries below
+//! the capacity of the arrays. When full, [`PTab::insert()`] returns [`None`].
+//!
+//! # Feature Flags
+//!
+//! - `std` (default): Links the standard library. Without it the crate is
+//!   `no_std` and only requires `alloc`; removed entries are then reclaimed
+//!   through a global epoch, and TTLs, handle tokens and blocking monitor
+//!   waits are unavailable.
+//! - `stats`: Enables `PTab::stats`, exposing operation and contention
+//!   counters.
+//! - `etf`: Enables `ExternalPid`, which encodes indices as Erlang pids in
+//!   the external term format.
+//! - `serde`: Implements `Serialize` and `Deserialize` for [`Detached`] and
+//!   for [`PTab`] snapshots that restore every entry at its original index.
 //!
 //! [Capacity::MAX]: crate::config::Capacity::MAX
 //! [Capacity::MIN]: crate::config::Capacity::MIN
@@ -116,25 +178,57 @@
 //! [`Capacity::MIN`]: crate::config::Capacity::MIN
 //! [`ConstParams`]: crate::config::ConstParams
 //! [`DefaultParams`]: crate::config::DefaultParams
+//! [`ExactParams`]: crate::config::ExactParams
 //! [`Params`]: crate::config::Params
+//! [`Params::CACHE_LINE`]: crate::config::Params::CACHE_LINE
+//! [`Params::LIMIT`]: crate::config::Params::LIMIT
+//! [`SlotLayout`]: crate::config::SlotLayout
 //! [`PTab::insert()`]: crate::public::PTab::insert
 //!
 //! [ABA problem]: https://en.wikipedia.org/wiki/ABA_problem
 //! [`sdd`]: https://docs.rs/sdd
 //!
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
 
+extern crate alloc;
+
+mod allocator;
 mod array;
+mod entry;
+mod error;
+#[cfg(feature = "etf")]
+mod etf;
+#[cfg(feature = "std")]
+mod expiry;
+mod flags;
+mod hooks;
 mod index;
+#[cfg(not(any(loom, shuttle)))]
+mod inline;
+mod key;
+mod local;
+mod monitor;
+mod owned;
 mod padded;
 mod params;
 mod public;
+mod quota;
+mod reason;
 mod reclaim;
+mod registry;
+#[cfg(feature = "std")]
+mod seal;
+mod secondary;
+#[cfg(feature = "serde")]
+mod snapshot;
+mod stats;
 mod table;
 mod utils;
+mod watch;
 
-pub(crate) use crate::utils::alloc;
+pub(crate) use crate::utils::heap;
 pub(crate) use crate::utils::sync;
 
 pub mod implementation {
@@ -145,16 +239,27 @@ pub mod config {
   //! Configuration parameters which can be used to override the default table
   //! settings.
 
+  pub use crate::allocator::Allocator;
+  pub use crate::allocator::Global;
+  pub use crate::error::AllocError;
+  pub use crate::params::Brand;
   pub use crate::params::CACHE_LINE;
   pub use crate::params::CACHE_LINE_SLOTS;
   pub use crate::params::Capacity;
   pub use crate::params::ConstParams;
   pub use crate::params::DebugParams;
   pub use crate::params::DefaultParams;
+  pub use crate::params::ExactParams;
+  pub use crate::params::MAX_CACHE_LINE;
   pub use crate::params::Params;
   pub use crate::params::ParamsExt;
+  pub use crate::params::SlotLayout;
+  pub use crate::params::StaticParams;
 }
 
+#[doc(inline)]
+pub use self::config::Brand;
+
 #[doc(inline)]
 pub use self::config::Capacity;
 
@@ -164,10 +269,43 @@ pub use self::config::ConstParams;
 #[doc(inline)]
 pub use self::config::DefaultParams;
 
+#[doc(inline)]
+pub use self::config::ExactParams;
+
 #[doc(inline)]
 pub use self::config::Params;
 
+pub use self::error::InsertAtError;
+pub use self::error::ParseIndexError;
+#[cfg(feature = "etf")]
+pub use self::error::PidError;
+#[cfg(feature = "etf")]
+pub use self::etf::ExternalPid;
+#[cfg(feature = "std")]
+pub use self::expiry::Sweeper;
+pub use self::flags::Flags;
+pub use self::hooks::Hooks;
 pub use self::index::Detached;
+pub use self::index::Parts;
+#[cfg(not(any(loom, shuttle)))]
+pub use self::inline::StaticPTab;
+pub use self::key::Handle;
+pub use self::key::Key;
+pub use self::local::LocalKeys;
+pub use self::local::LocalPTab;
+pub use self::monitor::Monitor;
+pub use self::owned::Owned;
+pub use self::owned::OwnedArc;
+pub use self::owned::Ref;
+pub use self::quota::Quota;
+pub use self::reason::Reason;
+pub use self::registry::Registry;
+#[cfg(feature = "std")]
+pub use self::seal::Token;
+pub use self::secondary::SecondaryTable;
 
 pub use self::public::PTab;
 pub use self::public::WeakKeys;
+
+#[cfg(feature = "stats")]
+pub use self::stats::Stats;
@@ -0,0 +1,815 @@
+use alloc::vec::Vec;
+use core::cell::Cell;
+use core::fmt::Debug;
+use core::fmt::DebugMap;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::mem::MaybeUninit;
+use core::ptr;
+use core::ptr::NonNull;
+
+use crate::array::Array;
+use crate::entry::Entry;
+use crate::index::Abstract;
+use crate::index::Concrete;
+use crate::index::Detached;
+use crate::key::Handle;
+use crate::key::Key;
+use crate::params::DefaultParams;
+use crate::params::Params;
+use crate::params::ParamsExt;
+use crate::public::PTab;
+use crate::reclaim::Owned;
+use crate::table::RESERVED;
+use crate::table::RawTable;
+use crate::table::initial_id;
+use crate::table::next_generation;
+use crate::table::next_tag;
+
+/// A single-threaded table with the same index format as [`PTab`].
+///
+/// `LocalPTab` keeps its state in plain [`Cell`]s instead of atomics and frees
+/// removed values without deferred reclamation, which makes it cheaper for
+/// tables that never leave one thread. It is [`Send`] but not [`Sync`].
+///
+/// Indices are encoded, allocated and reused exactly like those of a [`PTab`]
+/// with the same [`Params`], so [`Detached`] indices and typed [`Key`]s work
+/// with both kinds of table. A table can be converted into the other kind with
+/// [`From`] whenever it is owned; every index keeps resolving to the same
+/// value and the conversion does not touch the sequence of generations.
+///
+/// Hooks, flags, TTLs, monitors, links and quotas are not carried over when
+/// converting a [`PTab`]; monitors report [`Reason::Shutdown`] as if the table
+/// had been dropped.
+///
+/// # Examples
+///
+/// ```
+/// use ptab::{LocalPTab, PTab};
+///
+/// let local: LocalPTab<&str> = LocalPTab::new();
+/// let index = local.insert("worker").unwrap();
+///
+/// // Share the table across threads once it is set up.
+/// let table: PTab<&str> = PTab::from(local);
+/// assert_eq!(table.read(index), Some("worker"));
+///
+/// // ...and take it back into a single thread.
+/// let local: LocalPTab<&str> = LocalPTab::from(table);
+/// assert!(local.remove(index));
+/// ```
+///
+/// [`PTab`]: crate::public::PTab
+/// [`Detached`]: crate::index::Detached
+/// [`Key`]: crate::key::Key
+/// [`Params`]: crate::params::Params
+/// [`Reason::Shutdown`]: crate::reason::Reason::Shutdown
+pub struct LocalPTab<T, P = DefaultParams>
+where
+  P: Params + ?Sized,
+{
+  data: Array<Cell<Option<Owned<Entry<T>>>>, P>,
+  slot: Array<Cell<usize>, P>,
+  entries: Cell<u32>,
+  next_id: Cell<u32>,
+  free_id: Cell<u32>,
+  // Values removed while a reference into the table is handed out are kept
+  // alive until the outermost access returns.
+  scopes: Cell<usize>,
+  retired: Cell<Vec<Owned<Entry<T>>>>,
+  tag: usize,
+  phantom: PhantomData<fn(P)>,
+}
+
+impl<T, P> LocalPTab<T, P>
+where
+  P: Params + ?Sized,
+{
+  /// Creates a new, empty table.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::LocalPTab;
+  ///
+  /// let table: LocalPTab<String> = LocalPTab::new();
+  /// assert!(table.is_empty());
+  /// ```
+  #[inline]
+  pub fn new() -> Self {
+    Self {
+      data: Self::new_data_array(),
+      slot: Self::new_slot_array(),
+      entries: Cell::new(0),
+      next_id: Cell::new(0),
+      free_id: Cell::new(0),
+      scopes: Cell::new(0),
+      retired: Cell::new(Vec::new()),
+      tag: next_tag::<P>(),
+      phantom: PhantomData,
+    }
+  }
+
+  /// Returns the maximum number of entries the table can hold.
+  ///
+  /// See [`PTab::capacity`].
+  ///
+  /// [`PTab::capacity`]: crate::public::PTab::capacity
+  #[inline]
+  pub const fn capacity(&self) -> usize {
+    P::ENTRIES_LIMIT as usize
+  }
+
+  /// Returns the number of entries currently in the table.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.entries.get() as usize
+  }
+
+  /// Returns `true` if the table contains no entries.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Inserts a value into the table and returns its index.
+  ///
+  /// Returns [`None`] if the table is at capacity.
+  #[inline]
+  pub fn insert(&self, value: T) -> Option<Detached> {
+    self.write(|entry, _| {
+      entry.write(value);
+    })
+  }
+
+  /// Inserts a value into the table and returns a typed [`Key`] to it.
+  ///
+  /// [`Key`]: crate::key::Key
+  #[inline]
+  pub fn insert_key(&self, value: T) -> Option<Key<T, P>> {
+    self.insert(value).map(Key::new)
+  }
+
+  /// Inserts a value using an initialization function that receives its
+  /// index.
+  ///
+  /// See [`PTab::write`] for the requirements on `init`.
+  ///
+  /// [`PTab::write`]: crate::public::PTab::write
+  pub fn write<F>(&self, init: F) -> Option<Detached>
+  where
+    F: FnOnce(&mut MaybeUninit<T>, Detached),
+  {
+    let len: u32 = self.entries.get();
+
+    if len >= P::ENTRIES_LIMIT {
+      return None;
+    }
+
+    self.entries.set(len + 1);
+
+    let next_id: u32 = self.next_id.get();
+    self.next_id.set(next_id.wrapping_add(1));
+
+    let position: Concrete<P> = Concrete::from_abstract(Abstract::new(next_id as usize));
+    let id: usize = self.load_slot(position);
+
+    // Without concurrent releases, the ring is filled up to `next_id` while
+    // the table has room.
+    debug_assert_ne!(id, RESERVED, "free-id ring is exhausted");
+
+    self.store_slot(position, RESERVED);
+
+    let key: Detached = self.detach(Abstract::new(id));
+
+    let entry: Owned<Entry<T>> = Owned::new_with(|uninit| {
+      Entry::init(uninit, key, |value| init(value, key));
+    });
+
+    self.cell(key).set(Some(entry));
+
+    Some(key)
+  }
+
+  /// Inserts a value using an initialization function that receives its
+  /// typed [`Key`].
+  ///
+  /// [`Key`]: crate::key::Key
+  #[inline]
+  pub fn write_key<F>(&self, init: F) -> Option<Key<T, P>>
+  where
+    F: FnOnce(&mut MaybeUninit<T>, Key<T, P>),
+  {
+    self
+      .write(|maybe, index| init(maybe, Key::new(index)))
+      .map(Key::new)
+  }
+
+  /// Returns a typed [`Key`] for the entry at `index`.
+  ///
+  /// [`Key`]: crate::key::Key
+  #[inline]
+  pub fn key(&self, index: Detached) -> Option<Key<T, P>> {
+    self.exists(index).then_some(Key::new(index))
+  }
+
+  /// Removes the entry at the given index.
+  ///
+  /// Returns `true` if an entry was removed.
+  pub fn remove(&self, index: impl Handle<T, P>) -> bool {
+    let key: Detached = index.detached();
+
+    if self.find(key).is_none() {
+      return false;
+    }
+
+    let Some(entry) = self.cell(key).take() else {
+      unreachable!("entry was found above");
+    };
+
+    self.release(key);
+
+    if self.scopes.get() == 0 {
+      drop(entry);
+    } else {
+      let mut retired: Vec<Owned<Entry<T>>> = self.retired.take();
+      retired.push(entry);
+      self.retired.set(retired);
+    }
+
+    true
+  }
+
+  /// Returns `true` if an entry exists at the given index.
+  #[inline]
+  pub fn exists(&self, index: impl Handle<T, P>) -> bool {
+    self.find(index.detached()).is_some()
+  }
+
+  /// Accesses an entry by index, applying a function to it.
+  ///
+  /// `f` may operate on the table; a value removed while it is borrowed is
+  /// dropped once `f` returns.
+  #[inline]
+  pub fn with<F, R>(&self, index: impl Handle<T, P>, f: F) -> Option<R>
+  where
+    F: Fn(&T) -> R,
+  {
+    let entry: NonNull<Entry<T>> = self.find(index.detached())?;
+    let _scope: Scope<'_, T, P> = Scope::new(self);
+
+    // SAFETY: Removed values are retired instead of dropped while a `Scope`
+    //         is alive.
+    Some(f(unsafe { entry.as_ref() }.get()))
+  }
+
+  /// Returns a copy of the entry at the given index.
+  #[inline]
+  pub fn read(&self, index: impl Handle<T, P>) -> Option<T>
+  where
+    T: Copy,
+  {
+    self.with(index, |value| *value)
+  }
+
+  /// Returns an iterator over the indices of the table's entries.
+  ///
+  /// Entries inserted or removed during iteration may or may not be observed.
+  #[inline]
+  pub fn weak_keys(&self) -> LocalKeys<'_, T, P> {
+    LocalKeys {
+      table: self,
+      index: 0,
+    }
+  }
+
+  #[inline]
+  fn cell(&self, key: Detached) -> &Cell<Option<Owned<Entry<T>>>> {
+    self.data.get(Concrete::from_detached(key))
+  }
+
+  /// Returns a pointer to the entry linked at `index`.
+  #[inline]
+  fn load(&self, index: Concrete<P>) -> Option<NonNull<Entry<T>>> {
+    // SAFETY: The borrow of the cell ends before the cell can be written
+    //         again; only a pointer to the entry itself is kept.
+    unsafe { &*self.data.get(index).as_ptr() }
+      .as_deref()
+      .map(NonNull::from)
+  }
+
+  #[inline]
+  fn find(&self, key: Detached) -> Option<NonNull<Entry<T>>> {
+    let entry: NonNull<Entry<T>> = self.load(Concrete::from_detached(key))?;
+
+    // SAFETY: Linked values are only freed after they are unlinked.
+    // Reject stale indices whose slot has since been reused.
+    (unsafe { entry.as_ref() }.key().matches::<P>(key)).then_some(entry)
+  }
+
+  /// Converts an allocated id into the index handed out by this table.
+  #[inline]
+  const fn detach(&self, index: Abstract<P>) -> Detached {
+    Detached::from_bits((Detached::from_abstract(index).into_bits() & P::ID_MASK_LOCAL) | self.tag)
+  }
+
+  #[inline]
+  fn release(&self, key: Detached) {
+    let index: Abstract<P> =
+      Abstract::from_detached(Detached::from_bits(key.into_bits() & P::ID_MASK_LOCAL));
+
+    let free_id: u32 = self.free_id.get();
+    self.free_id.set(free_id.wrapping_add(1));
+
+    let position: Concrete<P> = Concrete::from_abstract(Abstract::new(free_id as usize));
+
+    debug_assert_eq!(self.load_slot(position), RESERVED);
+
+    self.store_slot(position, next_generation(index));
+    self.entries.set(self.entries.get() - 1);
+  }
+
+  #[inline]
+  fn new_data_array() -> Array<Cell<Option<Owned<Entry<T>>>>, P> {
+    // SAFETY: `None` is represented by the null pointer niche of `Owned`,
+    //         which is the all-zero bit pattern.
+    unsafe { Array::new_zeroed() }
+  }
+
+  /// Creates the free-id ring, see [`initial_id`].
+  #[inline]
+  fn new_slot_array() -> Array<Cell<usize>, P> {
+    // SAFETY: `Cell<usize>` has the same in-memory representation as `usize`.
+    unsafe { Array::new_zeroed() }
+  }
+
+  /// Loads the id stored in the slot at `index`, see [`initial_id`].
+  #[inline]
+  fn load_slot(&self, index: Concrete<P>) -> usize {
+    self.slot.get(index).get().wrapping_add(initial_id(index))
+  }
+
+  /// Stores `id` into the slot at `index`.
+  #[inline]
+  fn store_slot(&self, index: Concrete<P>, id: usize) {
+    self.slot.get(index).set(id.wrapping_sub(initial_id(index)));
+  }
+}
+
+impl<T, P> Debug for LocalPTab<T, P>
+where
+  T: Debug,
+  P: Params + ?Sized,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_struct("LocalPTab")
+      .field("values", &DebugValues(self))
+      .field("params", &P::debug())
+      .finish()
+  }
+}
+
+impl<T, P> Default for LocalPTab<T, P>
+where
+  P: Params + ?Sized,
+{
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T, P> Drop for LocalPTab<T, P>
+where
+  P: Params + ?Sized,
+{
+  fn drop(&mut self) {
+    for entry in self.data.as_mut_slice() {
+      drop(entry.get_mut().take());
+    }
+  }
+}
+
+// SAFETY: The table owns its values, and `Cell` keeps it from being shared.
+unsafe impl<T, P> Send for LocalPTab<T, P>
+where
+  T: Send,
+  P: Params + ?Sized,
+{
+}
+
+impl<T, P> From<PTab<T, P>> for LocalPTab<T, P>
+where
+  P: Params + ?Sized,
+{
+  /// Moves the entries of a [`PTab`] into a single-threaded table.
+  ///
+  /// Reuses the arrays and entry allocations of `other`, and keeps its table
+  /// id.
+  ///
+  /// [`PTab`]: crate::public::PTab
+  fn from(other: PTab<T, P>) -> Self {
+    let RawTable {
+      tag,
+      len,
+      next_id,
+      free_id,
+      slot,
+      data,
+    } = other.into_raw();
+
+    Self {
+      data: data.map(Cell::new),
+      slot: slot.map(Cell::new),
+      entries: Cell::new(len),
+      next_id: Cell::new(next_id),
+      free_id: Cell::new(free_id),
+      scopes: Cell::new(0),
+      retired: Cell::new(Vec::new()),
+      tag,
+      phantom: PhantomData,
+    }
+  }
+}
+
+impl<T, P> From<LocalPTab<T, P>> for PTab<T, P>
+where
+  T: 'static,
+  P: Params + ?Sized,
+{
+  /// Moves the entries of a [`LocalPTab`] into a concurrent table.
+  ///
+  /// Reuses the arrays and entry allocations of `other`, and keeps its table
+  /// id.
+  ///
+  /// [`LocalPTab`]: crate::local::LocalPTab
+  fn from(other: LocalPTab<T, P>) -> Self {
+    let this: ManuallyDrop<LocalPTab<T, P>> = ManuallyDrop::new(other);
+
+    // Owning the table means no `Scope` is alive, so nothing is retired, but
+    // the buffer may still hold capacity.
+    drop(this.retired.take());
+
+    // SAFETY: `this` is never dropped, so both arrays are moved out exactly
+    //         once, and `LocalPTab::drop` does not run for them.
+    let (data, slot) = unsafe {
+      (
+        ptr::read(&raw const this.data),
+        ptr::read(&raw const this.slot),
+      )
+    };
+
+    PTab::from_raw(RawTable {
+      tag: this.tag,
+      len: this.entries.get(),
+      next_id: this.next_id.get(),
+      free_id: this.free_id.get(),
+      slot: slot.map(Cell::into_inner),
+      data: data.map(Cell::into_inner),
+    })
+  }
+}
+
+/// Delays dropping removed values while references into the table exist.
+struct Scope<'table, T, P>
+where
+  P: Params + ?Sized,
+{
+  table: &'table LocalPTab<T, P>,
+}
+
+impl<'table, T, P> Scope<'table, T, P>
+where
+  P: Params + ?Sized,
+{
+  #[inline]
+  fn new(table: &'table LocalPTab<T, P>) -> Self {
+    table.scopes.set(table.scopes.get() + 1);
+    Self { table }
+  }
+}
+
+impl<T, P> Drop for Scope<'_, T, P>
+where
+  P: Params + ?Sized,
+{
+  fn drop(&mut self) {
+    let scopes: usize = self.table.scopes.get() - 1;
+
+    self.table.scopes.set(scopes);
+
+    if scopes == 0 {
+      drop(self.table.retired.take());
+    }
+  }
+}
+
+struct DebugValues<'a, T, P>(&'a LocalPTab<T, P>)
+where
+  P: Params + ?Sized;
+
+impl<T, P> Debug for DebugValues<'_, T, P>
+where
+  T: Debug,
+  P: Params + ?Sized,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    let _scope: Scope<'_, T, P> = Scope::new(self.0);
+    let mut debug: DebugMap<'_, '_> = f.debug_map();
+
+    for index in 0..P::LENGTH.as_usize() {
+      let position: Concrete<P> = Concrete::from_abstract(Abstract::new(index));
+
+      if let Some(entry) = self.0.load(position) {
+        // SAFETY: See `LocalPTab::with`.
+        let entry: &Entry<T> = unsafe { entry.as_ref() };
+        debug.entry(&entry.key(), entry.get());
+      }
+    }
+
+    debug.finish()
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Local Keys
+// -----------------------------------------------------------------------------
+
+/// An iterator over the indices of a [`LocalPTab`].
+///
+/// Created by [`LocalPTab::weak_keys`].
+///
+/// [`LocalPTab`]: crate::local::LocalPTab
+/// [`LocalPTab::weak_keys`]: crate::local::LocalPTab::weak_keys
+pub struct LocalKeys<'table, T, P>
+where
+  P: Params + ?Sized,
+{
+  table: &'table LocalPTab<T, P>,
+  index: usize,
+}
+
+impl<T, P> Debug for LocalKeys<'_, T, P>
+where
+  P: Params + ?Sized,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.write_str("LocalKeys(..)")
+  }
+}
+
+impl<T, P> Iterator for LocalKeys<'_, T, P>
+where
+  P: Params + ?Sized,
+{
+  type Item = Detached;
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    while self.index < P::LENGTH.as_usize() {
+      let position: Concrete<P> = Concrete::from_abstract(Abstract::new(self.index));
+
+      self.index += 1;
+
+      if let Some(entry) = self.table.load(position) {
+        // SAFETY: Linked values are only freed after they are unlinked.
+        return Some(unsafe { entry.as_ref() }.key());
+      }
+    }
+
+    None
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(not(any(loom, shuttle)))]
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cfg(test)]
+mod tests {
+  use core::ptr;
+  use std::collections::HashSet;
+  use std::rc::Rc;
+  use std::sync::Arc;
+  use std::thread;
+
+  use crate::index::Concrete;
+  use crate::index::Detached;
+  use crate::local::LocalPTab;
+  use crate::params::Capacity;
+  use crate::params::ConstParams;
+  use crate::params::ExactParams;
+  use crate::params::Params;
+  use crate::public::PTab;
+
+  type MinParams = ConstParams<{ Capacity::MIN.as_usize() }>;
+
+  struct TaggedParams;
+
+  impl Params for TaggedParams {
+    const LENGTH: Capacity = Capacity::MIN;
+    const TABLE_ID_BITS: u32 = 8;
+    const CHECKED: bool = true;
+  }
+
+  #[test]
+  fn fill() {
+    let table: LocalPTab<usize, MinParams> = LocalPTab::new();
+
+    let keys: HashSet<Detached> = (0..table.capacity())
+      .map(|value| table.insert(value).unwrap())
+      .collect();
+
+    assert_eq!(keys.len(), table.capacity());
+    assert_eq!(table.len(), table.capacity());
+    assert_eq!(table.insert(0), None);
+    assert_eq!(table.weak_keys().collect::<HashSet<_>>(), keys);
+
+    for key in &keys {
+      assert!(table.remove(*key));
+      assert!(!table.remove(*key));
+    }
+
+    assert!(table.is_empty());
+  }
+
+  #[test]
+  fn exact_limit() {
+    let table: LocalPTab<usize, ExactParams<20>> = LocalPTab::new();
+
+    let keys: Vec<Detached> = (0..20).map(|value| table.insert(value).unwrap()).collect();
+
+    assert_eq!(table.insert(20), None);
+
+    for key in keys {
+      assert!(table.remove(key));
+      assert!(table.insert(0).is_some());
+    }
+
+    assert_eq!(table.weak_keys().count(), 20);
+  }
+
+  #[test]
+  fn same_sequence() {
+    let local: LocalPTab<usize, MinParams> = LocalPTab::new();
+    let table: PTab<usize, MinParams> = PTab::new();
+
+    for round in 0..4 * Capacity::MIN.as_usize() {
+      let a: Detached = local.insert(round).unwrap();
+      let b: Detached = table.insert(round).unwrap();
+
+      assert_eq!(a, b);
+
+      if round % 3 != 0 {
+        assert!(local.remove(a));
+        assert!(table.remove(b));
+      }
+
+      if local.len() == local.capacity() {
+        for key in local.weak_keys() {
+          assert!(local.remove(key));
+          assert!(table.remove(key));
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn stale_index() {
+    let table: LocalPTab<usize, MinParams> = LocalPTab::new();
+
+    let old: Detached = table.insert(1).unwrap();
+    let slot: usize = Concrete::<MinParams>::from_detached(old).get();
+
+    assert!(table.remove(old));
+
+    let new: Detached = (0..table.capacity())
+      .map(|_| table.insert(2).unwrap())
+      .find(|index| Concrete::<MinParams>::from_detached(*index).get() == slot)
+      .unwrap();
+
+    assert_ne!(old, new);
+    assert_eq!(table.read(old), None);
+    assert!(!table.remove(old));
+    assert_eq!(table.read(new), Some(2));
+  }
+
+  #[test]
+  fn with_remove() {
+    let table: LocalPTab<Rc<usize>, MinParams> = LocalPTab::new();
+    let value: Rc<usize> = Rc::new(7);
+    let index: Detached = table.insert(Rc::clone(&value)).unwrap();
+
+    // The value outlives its removal until the callback returns.
+    let seen: usize = table
+      .with(index, |inner| {
+        assert!(table.remove(index));
+        assert_eq!(Rc::strong_count(&value), 2);
+        **inner
+      })
+      .unwrap();
+
+    assert_eq!(seen, 7);
+    assert_eq!(Rc::strong_count(&value), 1);
+  }
+
+  #[test]
+  fn drop_values() {
+    let table: LocalPTab<Arc<()>, MinParams> = LocalPTab::new();
+    let value: Arc<()> = Arc::new(());
+
+    for _ in 0..4 {
+      table.insert(Arc::clone(&value)).unwrap();
+    }
+
+    drop(table);
+
+    assert_eq!(Arc::strong_count(&value), 1);
+  }
+
+  #[test]
+  fn convert() {
+    let local: LocalPTab<String, TaggedParams> = LocalPTab::new();
+
+    let keys: Vec<Detached> = (0..8)
+      .map(|value| local.insert(value.to_string()).unwrap())
+      .collect();
+
+    assert!(local.remove(keys[0]));
+
+    let table: PTab<String, TaggedParams> = PTab::from(local);
+
+    assert_eq!(table.len(), 7);
+    assert!(!table.exists(keys[0]));
+
+    for (value, key) in keys.iter().enumerate().skip(1) {
+      assert_eq!(table.with(*key, String::clone), Some(value.to_string()));
+    }
+
+    let next: Detached = table.insert("next".to_string()).unwrap();
+    let local: LocalPTab<String, TaggedParams> = LocalPTab::from(table);
+
+    assert_eq!(local.len(), 8);
+    assert_eq!(local.with(next, String::clone), Some("next".to_string()));
+
+    // The table keeps its tag, so indices of other tables stay rejected.
+    let table: PTab<String, TaggedParams> = PTab::new();
+    let other: Detached = table.insert(String::new()).unwrap();
+
+    assert!(!local.exists(other));
+  }
+
+  #[test]
+  fn convert_drops_nothing() {
+    let value: Arc<()> = Arc::new(());
+    let table: PTab<Arc<()>, MinParams> = PTab::new();
+    let index: Detached = table.insert(Arc::clone(&value)).unwrap();
+
+    let local: LocalPTab<Arc<()>, MinParams> = LocalPTab::from(table);
+
+    assert_eq!(Arc::strong_count(&value), 2);
+    assert!(local.remove(index));
+    assert_eq!(Arc::strong_count(&value), 1);
+  }
+
+  #[test]
+  fn convert_in_place() {
+    let local: LocalPTab<u64, MinParams> = LocalPTab::new();
+    let index: Detached = local.insert(1).unwrap();
+    let address: usize = local
+      .with(index, |value| ptr::from_ref(value).addr())
+      .unwrap();
+
+    // Entries move across without being copied.
+    let table: PTab<u64, MinParams> = PTab::from(local);
+    assert_eq!(
+      table.with(index, |value| ptr::from_ref(value).addr()),
+      Some(address)
+    );
+
+    let local: LocalPTab<u64, MinParams> = LocalPTab::from(table);
+    assert_eq!(
+      local.with(index, |value| ptr::from_ref(value).addr()),
+      Some(address)
+    );
+  }
+
+  #[test]
+  fn send() {
+    let table: LocalPTab<usize> = LocalPTab::new();
+    let index: Detached = table.insert(1).unwrap();
+
+    let table: LocalPTab<usize> = thread::spawn(move || {
+      assert_eq!(table.read(index), Some(1));
+      table
+    })
+    .join()
+    .unwrap();
+
+    assert!(table.remove(index));
+  }
+}
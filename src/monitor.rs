@@ -0,0 +1,600 @@
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::Context;
+use core::task::Poll;
+use core::task::Waker;
+#[cfg(feature = "std")]
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::sync::Condvar;
+#[cfg(feature = "std")]
+use std::sync::PoisonError;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use crate::index::Detached;
+use crate::reason::Reason;
+use crate::utils::Mutex;
+use crate::utils::MutexGuard;
+
+type Callback = Box<dyn FnOnce(Reason) + Send>;
+
+/// A handle that is notified when a table entry is removed.
+///
+/// Created by [`PTab::monitor`] or [`PTab::monitor_with`]. Modeled on
+/// `erlang:monitor/2`: a monitor fires exactly once, when the monitored entry
+/// leaves the table, and reports the [`Reason`] it left. Monitoring an entry
+/// that does not exist, including through a stale index, fires immediately
+/// with [`Reason::NoEntry`].
+///
+/// The notification can be observed in several ways:
+///
+/// - Polling with [`is_down`] or [`reason`].
+/// - Blocking with [`wait`] or [`wait_timeout`] (requires `std`).
+/// - Awaiting the monitor, which implements [`Future`].
+/// - A callback registered with [`PTab::monitor_with`].
+///
+/// Dropping a monitor does not cancel it; use [`demonitor`] instead.
+///
+/// # Examples
+///
+/// ```
+/// use ptab::{PTab, Reason};
+///
+/// let table: PTab<&str> = PTab::new();
+/// let index = table.insert("worker").unwrap();
+///
+/// let monitor = table.monitor(index);
+/// assert!(!monitor.is_down());
+///
+/// table.remove(index);
+///
+/// assert!(monitor.is_down());
+/// assert_eq!(monitor.reason(), Some(Reason::Normal));
+/// ```
+///
+/// [`PTab::monitor`]: crate::public::PTab::monitor
+/// [`PTab::monitor_with`]: crate::public::PTab::monitor_with
+/// [`is_down`]: Self::is_down
+/// [`reason`]: Self::reason
+/// [`wait`]: Self::wait
+/// [`wait_timeout`]: Self::wait_timeout
+/// [`demonitor`]: Self::demonitor
+pub struct Monitor {
+  state: Arc<State>,
+}
+
+impl Monitor {
+  #[inline]
+  pub(crate) fn new(index: Detached, callback: Option<Callback>) -> Self {
+    Self {
+      state: Arc::new(State::new(index, callback)),
+    }
+  }
+
+  #[inline]
+  pub(crate) fn state(&self) -> &Arc<State> {
+    &self.state
+  }
+
+  /// Returns the index of the monitored entry.
+  #[inline]
+  pub fn index(&self) -> Detached {
+    self.state.index
+  }
+
+  /// Returns `true` if the monitored entry has been removed.
+  #[inline]
+  pub fn is_down(&self) -> bool {
+    self.reason().is_some()
+  }
+
+  /// Returns the reason the entry was removed, or [`None`] if it is still
+  /// present.
+  #[inline]
+  pub fn reason(&self) -> Option<Reason> {
+    self.state.lock().reason
+  }
+
+  /// Blocks the current thread until the monitored entry is removed.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::sync::Arc;
+  /// use std::thread;
+  ///
+  /// use ptab::{PTab, Reason};
+  ///
+  /// let table: Arc<PTab<u64>> = Arc::new(PTab::new());
+  /// let index = table.insert(1).unwrap();
+  /// let monitor = table.monitor(index);
+  ///
+  /// let remover = {
+  ///   let table = Arc::clone(&table);
+  ///   thread::spawn(move || table.remove(index))
+  /// };
+  ///
+  /// assert_eq!(monitor.wait(), Reason::Normal);
+  /// assert!(remover.join().unwrap());
+  /// ```
+  #[cfg(feature = "std")]
+  pub fn wait(&self) -> Reason {
+    let mut inner: MutexGuard<'_, Inner> = self.state.lock();
+
+    loop {
+      if let Some(reason) = inner.reason {
+        return reason;
+      }
+
+      inner = self
+        .state
+        .signal
+        .wait(inner)
+        .unwrap_or_else(PoisonError::into_inner);
+    }
+  }
+
+  /// Blocks the current thread until the monitored entry is removed or the
+  /// `timeout` elapses.
+  ///
+  /// Returns [`None`] if the timeout elapsed first.
+  #[cfg(feature = "std")]
+  pub fn wait_timeout(&self, timeout: Duration) -> Option<Reason> {
+    let deadline: Option<Instant> = Instant::now().checked_add(timeout);
+    let mut inner: MutexGuard<'_, Inner> = self.state.lock();
+
+    loop {
+      if let Some(reason) = inner.reason {
+        return Some(reason);
+      }
+
+      let remaining: Duration = match deadline {
+        Some(deadline) => deadline.checked_duration_since(Instant::now())?,
+        None => Duration::MAX,
+      };
+
+      inner = self
+        .state
+        .signal
+        .wait_timeout(inner, remaining)
+        .unwrap_or_else(PoisonError::into_inner)
+        .0;
+    }
+  }
+
+  /// Cancels the monitor.
+  ///
+  /// Returns `true` if the monitor was cancelled before it fired; a pending
+  /// callback will then never run. Returns `false` if it had already fired.
+  pub fn demonitor(self) -> bool {
+    let mut inner: MutexGuard<'_, Inner> = self.state.lock();
+
+    if inner.reason.is_some() {
+      return false;
+    }
+
+    inner.cancelled = true;
+
+    let callback: Option<Callback> = inner.callback.take();
+
+    drop(inner);
+    drop(callback);
+
+    true
+  }
+}
+
+impl Debug for Monitor {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_struct("Monitor")
+      .field("index", &self.index())
+      .field("reason", &self.reason())
+      .finish()
+  }
+}
+
+impl Future for Monitor {
+  type Output = Reason;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    let mut inner: MutexGuard<'_, Inner> = self.state.lock();
+
+    if let Some(reason) = inner.reason {
+      return Poll::Ready(reason);
+    }
+
+    match inner.waker {
+      Some(ref mut waker) => waker.clone_from(cx.waker()),
+      None => inner.waker = Some(cx.waker().clone()),
+    }
+
+    Poll::Pending
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Monitor State
+// -----------------------------------------------------------------------------
+
+struct Inner {
+  reason: Option<Reason>,
+  cancelled: bool,
+  waker: Option<Waker>,
+  callback: Option<Callback>,
+}
+
+/// State shared between a [`Monitor`] and the entry it watches.
+pub(crate) struct State {
+  index: Detached,
+  inner: Mutex<Inner>,
+  #[cfg(feature = "std")]
+  signal: Condvar,
+}
+
+impl State {
+  #[inline]
+  fn new(index: Detached, callback: Option<Callback>) -> Self {
+    Self {
+      index,
+      inner: Mutex::new(Inner {
+        reason: None,
+        cancelled: false,
+        waker: None,
+        callback,
+      }),
+      #[cfg(feature = "std")]
+      signal: Condvar::new(),
+    }
+  }
+
+  #[inline]
+  fn lock(&self) -> MutexGuard<'_, Inner> {
+    self.inner.lock()
+  }
+
+  /// Returns `true` if the monitor was cancelled with [`Monitor::demonitor`].
+  #[inline]
+  pub(crate) fn is_cancelled(&self) -> bool {
+    self.lock().cancelled
+  }
+
+  /// Marks the monitor as down and notifies every observer.
+  ///
+  /// Has no effect if the monitor already fired or was cancelled.
+  pub(crate) fn fire(&self, reason: Reason) {
+    let mut inner: MutexGuard<'_, Inner> = self.lock();
+
+    if inner.reason.is_some() || inner.cancelled {
+      return;
+    }
+
+    inner.reason = Some(reason);
+
+    let waker: Option<Waker> = inner.waker.take();
+    let callback: Option<Callback> = inner.callback.take();
+
+    drop(inner);
+
+    #[cfg(feature = "std")]
+    self.signal.notify_all();
+
+    if let Some(waker) = waker {
+      waker.wake();
+    }
+
+    if let Some(callback) = callback {
+      callback(reason);
+    }
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(not(any(loom, shuttle)))]
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cfg(test)]
+mod tests {
+  use core::future::Future;
+  use core::pin::Pin;
+  use core::task::Context;
+  use core::task::Poll;
+  use core::task::Waker;
+  #[cfg(feature = "std")]
+  use core::time::Duration;
+  use std::sync::Arc;
+  use std::sync::Barrier;
+  use std::sync::Mutex;
+  use std::sync::atomic::AtomicUsize;
+  use std::sync::atomic::Ordering;
+  use std::task::Wake;
+  use std::thread;
+  use std::thread::JoinHandle;
+
+  use crate::index::Detached;
+  use crate::local::LocalPTab;
+  use crate::monitor::Monitor;
+  use crate::params::Capacity;
+  use crate::params::ConstParams;
+  use crate::public::PTab;
+  use crate::reason::Reason;
+  use crate::watch::MIN_COMPACT;
+
+  type MinParams = ConstParams<{ Capacity::MIN.as_usize() }>;
+
+  const THREADS: usize = 8;
+
+  struct Flag(AtomicUsize);
+
+  impl Wake for Flag {
+    fn wake(self: Arc<Self>) {
+      self.0.fetch_add(1, Ordering::Relaxed);
+    }
+  }
+
+  #[test]
+  fn fires_on_remove() {
+    let table: PTab<usize> = PTab::new();
+    let index: Detached = table.insert(1).unwrap();
+    let monitor: Monitor = table.monitor(index);
+
+    assert_eq!(monitor.index(), index);
+    assert_eq!(monitor.reason(), None);
+
+    assert!(table.remove(index));
+
+    assert_eq!(monitor.reason(), Some(Reason::Normal));
+    #[cfg(feature = "std")]
+    assert_eq!(monitor.wait(), Reason::Normal);
+  }
+
+  #[test]
+  fn fires_on_linked_remove() {
+    let table: PTab<usize> = PTab::new();
+    let a: Detached = table.insert(1).unwrap();
+    let b: Detached = table.insert(2).unwrap();
+    let monitor: Monitor = table.monitor(b);
+
+    assert!(table.link(a, b));
+    assert!(table.remove(a));
+
+    assert_eq!(monitor.reason(), Some(Reason::Linked(a)));
+  }
+
+  #[test]
+  fn fires_once_per_monitor() {
+    let table: PTab<usize> = PTab::new();
+    let index: Detached = table.insert(1).unwrap();
+    let count: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+
+    for _ in 0..4 {
+      let count: Arc<AtomicUsize> = Arc::clone(&count);
+
+      let _monitor: Monitor = table.monitor_with(index, move |reason| {
+        assert_eq!(reason, Reason::Normal);
+        count.fetch_add(1, Ordering::Relaxed);
+      });
+    }
+
+    assert!(table.remove(index));
+    assert!(!table.remove(index));
+
+    assert_eq!(count.load(Ordering::Relaxed), 4);
+  }
+
+  #[test]
+  fn many_monitors() {
+    let table: PTab<usize> = PTab::new();
+    let index: Detached = table.insert(1).unwrap();
+
+    // Enough live monitors to trigger several compactions that keep them all.
+    let monitors: Vec<Monitor> = (0..MIN_COMPACT * 8)
+      .map(|_| {
+        assert!(table.monitor(index).demonitor());
+        table.monitor(index)
+      })
+      .collect();
+
+    assert!(table.remove(index));
+
+    for monitor in monitors {
+      assert_eq!(monitor.reason(), Some(Reason::Normal));
+    }
+  }
+
+  #[test]
+  fn missing_entry() {
+    let table: PTab<usize> = PTab::new();
+    let monitor: Monitor = table.monitor(Detached::from_bits(0));
+
+    assert_eq!(monitor.reason(), Some(Reason::NoEntry));
+  }
+
+  #[test]
+  fn stale_index() {
+    let table: PTab<usize, MinParams> = PTab::new();
+    let stale: Detached = table.insert(1).unwrap();
+
+    assert!(table.remove(stale));
+
+    // Cycle through every slot so the stale slot is reused.
+    let fresh: Vec<Detached> = (0..table.capacity())
+      .map(|value| table.insert(value).unwrap())
+      .collect();
+
+    assert!(!fresh.contains(&stale));
+
+    let monitor: Monitor = table.monitor(stale);
+
+    assert_eq!(monitor.reason(), Some(Reason::NoEntry));
+  }
+
+  #[test]
+  fn callback_immediate_on_missing_entry() {
+    let table: PTab<usize> = PTab::new();
+    let seen: Arc<Mutex<Option<Reason>>> = Arc::default();
+
+    let _monitor: Monitor = {
+      let seen: Arc<Mutex<Option<Reason>>> = Arc::clone(&seen);
+      table.monitor_with(Detached::from_bits(0), move |reason| {
+        *seen.lock().unwrap() = Some(reason);
+      })
+    };
+
+    assert_eq!(*seen.lock().unwrap(), Some(Reason::NoEntry));
+  }
+
+  #[test]
+  fn demonitor() {
+    let table: PTab<usize> = PTab::new();
+    let index: Detached = table.insert(1).unwrap();
+    let count: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+
+    let monitor: Monitor = {
+      let count: Arc<AtomicUsize> = Arc::clone(&count);
+      table.monitor_with(index, move |_| {
+        count.fetch_add(1, Ordering::Relaxed);
+      })
+    };
+
+    assert!(monitor.demonitor());
+    assert!(table.remove(index));
+    assert_eq!(count.load(Ordering::Relaxed), 0);
+  }
+
+  #[test]
+  fn demonitor_cycles() {
+    let table: PTab<usize> = PTab::new();
+    let index: Detached = table.insert(1).unwrap();
+    let monitor: Monitor = table.monitor(index);
+
+    for _ in 0..1000 {
+      assert!(table.monitor(index).demonitor());
+    }
+
+    let mut watchers: usize = 0;
+
+    table.watched(index, |_| {
+      watchers += 1;
+      false
+    });
+
+    // Cancelled monitors are unlinked once the list doubles in length.
+    assert!(watchers <= MIN_COMPACT);
+
+    assert!(table.remove(index));
+    assert_eq!(monitor.reason(), Some(Reason::Normal));
+  }
+
+  #[test]
+  fn demonitor_after_fire() {
+    let table: PTab<usize> = PTab::new();
+    let index: Detached = table.insert(1).unwrap();
+    let monitor: Monitor = table.monitor(index);
+
+    assert!(table.remove(index));
+    assert!(!monitor.demonitor());
+  }
+
+  #[test]
+  fn table_drop() {
+    let table: PTab<usize> = PTab::new();
+    let index: Detached = table.insert(1).unwrap();
+    let monitor: Monitor = table.monitor(index);
+
+    drop(table);
+
+    assert_eq!(monitor.reason(), Some(Reason::Shutdown));
+  }
+
+  #[test]
+  fn table_convert() {
+    let table: PTab<usize> = PTab::new();
+    let index: Detached = table.insert(1).unwrap();
+    let monitor: Monitor = table.monitor(index);
+
+    let local: LocalPTab<usize> = LocalPTab::from(table);
+
+    assert_eq!(monitor.reason(), Some(Reason::Shutdown));
+    assert_eq!(local.read(index), Some(1));
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn wait_timeout() {
+    let table: PTab<usize> = PTab::new();
+    let index: Detached = table.insert(1).unwrap();
+    let monitor: Monitor = table.monitor(index);
+
+    assert_eq!(monitor.wait_timeout(Duration::from_millis(1)), None);
+    assert!(table.remove(index));
+    assert_eq!(monitor.wait_timeout(Duration::ZERO), Some(Reason::Normal));
+  }
+
+  #[test]
+  fn future() {
+    let table: PTab<usize> = PTab::new();
+    let index: Detached = table.insert(1).unwrap();
+    let flag: Arc<Flag> = Arc::new(Flag(AtomicUsize::new(0)));
+    let waker: Waker = Waker::from(Arc::clone(&flag));
+    let mut context: Context<'_> = Context::from_waker(&waker);
+    let mut monitor: Monitor = table.monitor(index);
+
+    assert_eq!(Pin::new(&mut monitor).poll(&mut context), Poll::Pending);
+    assert_eq!(flag.0.load(Ordering::Relaxed), 0);
+
+    assert!(table.remove(index));
+
+    assert_eq!(flag.0.load(Ordering::Relaxed), 1);
+    assert_eq!(
+      Pin::new(&mut monitor).poll(&mut context),
+      Poll::Ready(Reason::Normal),
+    );
+  }
+
+  // Scenario: Monitors are created while the entry is concurrently removed.
+  // Expected: Every monitor fires exactly once.
+  #[test]
+  fn monitor_remove_race() {
+    let table: Arc<PTab<usize>> = Arc::new(PTab::new());
+    let index: Detached = table.insert(1).unwrap();
+    let barrier: Arc<Barrier> = Arc::new(Barrier::new(THREADS + 1));
+    let count: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+
+    let threads: Vec<JoinHandle<()>> = (0..THREADS)
+      .map(|_| {
+        let table: Arc<PTab<usize>> = Arc::clone(&table);
+        let barrier: Arc<Barrier> = Arc::clone(&barrier);
+        let count: Arc<AtomicUsize> = Arc::clone(&count);
+
+        thread::spawn(move || {
+          barrier.wait();
+
+          for _ in 0..64 {
+            let count: Arc<AtomicUsize> = Arc::clone(&count);
+            let _monitor: Monitor = table.monitor_with(index, move |_| {
+              count.fetch_add(1, Ordering::Relaxed);
+            });
+          }
+        })
+      })
+      .collect();
+
+    barrier.wait();
+
+    assert!(table.remove(index));
+
+    for thread in threads {
+      thread.join().unwrap();
+    }
+
+    assert_eq!(count.load(Ordering::Relaxed), THREADS * 64);
+  }
+}
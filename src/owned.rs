@@ -0,0 +1,406 @@
+use alloc::sync::Arc;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ops::Deref;
+use core::ptr;
+use core::ptr::NonNull;
+
+use crate::index::Detached;
+use crate::params::DefaultParams;
+use crate::params::Params;
+use crate::public::PTab;
+use crate::reclaim::Guard;
+
+macro_rules! owned_methods {
+  () => {
+    /// Returns the index of the owned entry.
+    #[inline]
+    pub fn key(&self) -> Detached {
+      self.key
+    }
+
+    /// Returns a scoped reference to the value.
+    ///
+    /// Returns [`None`] if the entry was removed through another path, such
+    /// as [`PTab::remove`] or a [linked] removal. The returned [`Ref`] pins
+    /// the current thread, delaying memory reclamation until it is dropped,
+    /// so it should be short-lived.
+    ///
+    /// [`PTab::remove`]: crate::public::PTab::remove
+    /// [linked]: crate::public::PTab::link
+    /// [`Ref`]: crate::owned::Ref
+    #[inline]
+    pub fn get(&self) -> Option<Ref<'_, T>> {
+      let guard: Guard = Guard::new();
+      let value: NonNull<T> = NonNull::from(self.table.get(self.key, &guard)?);
+
+      Some(Ref {
+        guard,
+        value,
+        marker: PhantomData,
+      })
+    }
+
+    /// Accesses the value, applying a function to it.
+    ///
+    /// Returns [`None`] if the entry was removed through another path.
+    #[inline]
+    pub fn with<F, R>(&self, f: F) -> Option<R>
+    where
+      F: Fn(&T) -> R,
+    {
+      self.table.with(self.key, f)
+    }
+
+    /// Returns a copy of the value.
+    ///
+    /// Returns [`None`] if the entry was removed through another path.
+    #[inline]
+    pub fn read(&self) -> Option<T>
+    where
+      T: Copy,
+    {
+      self.table.read(self.key)
+    }
+  };
+}
+
+/// An owning handle to a table entry that removes the entry when dropped.
+///
+/// Created by [`PTab::insert_owned`]. Ties the lifetime of a slot to normal
+/// Rust scoping, so an early return or panic cannot leak it. See [`OwnedArc`]
+/// for a variant that does not borrow the table.
+///
+/// The index of an owned entry can still be shared, so the entry may also be
+/// removed through other paths. For that reason, `Owned` does not implement
+/// [`Deref`] itself; access goes through a scoped [`Ref`] or a closure.
+///
+/// # Examples
+///
+/// ```
+/// use ptab::PTab;
+///
+/// let table: PTab<&str> = PTab::new();
+///
+/// {
+///   let owned = table.insert_owned("scoped").unwrap();
+///
+///   assert_eq!(owned.read(), Some("scoped"));
+///   assert_eq!(table.len(), 1);
+/// }
+///
+/// assert!(table.is_empty());
+/// ```
+///
+/// [`PTab::insert_owned`]: crate::public::PTab::insert_owned
+/// [`OwnedArc`]: crate::owned::OwnedArc
+/// [`Ref`]: crate::owned::Ref
+/// [`Deref`]: core::ops::Deref
+pub struct Owned<'table, T, P = DefaultParams>
+where
+  P: Params + ?Sized,
+{
+  table: &'table PTab<T, P>,
+  key: Detached,
+}
+
+impl<'table, T, P> Owned<'table, T, P>
+where
+  P: Params + ?Sized,
+{
+  #[inline]
+  pub(crate) const fn new(table: &'table PTab<T, P>, key: Detached) -> Self {
+    Self { table, key }
+  }
+
+  /// Returns the table the entry belongs to.
+  #[inline]
+  pub const fn table(&self) -> &'table PTab<T, P> {
+    self.table
+  }
+
+  /// Releases ownership without removing the entry, returning its index.
+  ///
+  /// The entry then has to be removed manually with [`PTab::remove`].
+  ///
+  /// [`PTab::remove`]: crate::public::PTab::remove
+  #[inline]
+  pub fn leak(self) -> Detached {
+    ManuallyDrop::new(self).key
+  }
+
+  owned_methods!();
+}
+
+impl<T, P> Debug for Owned<'_, T, P>
+where
+  P: Params + ?Sized,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_tuple("Owned").field(&self.key).finish()
+  }
+}
+
+impl<T, P> Drop for Owned<'_, T, P>
+where
+  P: Params + ?Sized,
+{
+  #[inline]
+  fn drop(&mut self) {
+    self.table.remove(self.key);
+  }
+}
+
+/// An owning handle to an entry of a shared table.
+///
+/// Created by [`PTab::insert_owned_arc`]. Behaves like [`Owned`], but keeps
+/// the table alive through an [`Arc`] instead of borrowing it, so the handle
+/// is `'static` and can be moved into threads or stored alongside the table.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// use ptab::PTab;
+///
+/// let table: Arc<PTab<u64>> = Arc::new(PTab::new());
+/// let owned = PTab::insert_owned_arc(&table, 42).unwrap();
+///
+/// thread::spawn(move || {
+///   assert_eq!(owned.read(), Some(42));
+/// })
+/// .join()
+/// .unwrap();
+///
+/// assert!(table.is_empty());
+/// ```
+///
+/// [`PTab::insert_owned_arc`]: crate::public::PTab::insert_owned_arc
+/// [`Owned`]: crate::owned::Owned
+pub struct OwnedArc<T, P = DefaultParams>
+where
+  P: Params + ?Sized,
+{
+  table: Arc<PTab<T, P>>,
+  key: Detached,
+}
+
+impl<T, P> OwnedArc<T, P>
+where
+  P: Params + ?Sized,
+{
+  #[inline]
+  pub(crate) const fn new(table: Arc<PTab<T, P>>, key: Detached) -> Self {
+    Self { table, key }
+  }
+
+  /// Returns the table the entry belongs to.
+  #[inline]
+  pub const fn table(&self) -> &Arc<PTab<T, P>> {
+    &self.table
+  }
+
+  /// Releases ownership without removing the entry, returning its index.
+  ///
+  /// The reference to the table is released, so the entry then has to be
+  /// removed manually with [`PTab::remove`] through another handle.
+  ///
+  /// [`PTab::remove`]: crate::public::PTab::remove
+  #[inline]
+  pub fn leak(self) -> Detached {
+    let this: ManuallyDrop<Self> = ManuallyDrop::new(self);
+
+    // SAFETY: `this` is never dropped or used again, so `table` is moved out
+    //         exactly once.
+    drop(unsafe { ptr::read(&raw const this.table) });
+
+    this.key
+  }
+
+  owned_methods!();
+}
+
+impl<T, P> Debug for OwnedArc<T, P>
+where
+  P: Params + ?Sized,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_tuple("OwnedArc").field(&self.key).finish()
+  }
+}
+
+impl<T, P> Drop for OwnedArc<T, P>
+where
+  P: Params + ?Sized,
+{
+  #[inline]
+  fn drop(&mut self) {
+    self.table.remove(self.key);
+  }
+}
+
+/// A scoped reference to the value of an [`Owned`] or [`OwnedArc`] entry.
+///
+/// Created by [`Owned::get`]. The value stays valid while the `Ref` exists,
+/// even if the entry is removed concurrently.
+///
+/// [`Owned`]: crate::owned::Owned
+/// [`OwnedArc`]: crate::owned::OwnedArc
+/// [`Owned::get`]: crate::owned::Owned::get
+pub struct Ref<'a, T> {
+  // Keeps `value` from being reclaimed.
+  #[allow(dead_code, reason = "held for its drop")]
+  guard: Guard,
+  value: NonNull<T>,
+  marker: PhantomData<&'a T>,
+}
+
+impl<T> Deref for Ref<'_, T> {
+  type Target = T;
+
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    // SAFETY: `value` was loaded under `guard`, which is alive as long as
+    //         `self` is, so the value has not been reclaimed.
+    unsafe { self.value.as_ref() }
+  }
+}
+
+impl<T> Debug for Ref<'_, T>
+where
+  T: Debug,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    Debug::fmt(&**self, f)
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(not(any(loom, shuttle)))]
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+  use std::thread;
+
+  use crate::index::Detached;
+  use crate::owned::Owned;
+  use crate::owned::OwnedArc;
+  use crate::params::Capacity;
+  use crate::params::ConstParams;
+  use crate::public::PTab;
+
+  type DefParams = ConstParams<{ Capacity::DEF.as_usize() }>;
+  type MinParams = ConstParams<{ Capacity::MIN.as_usize() }>;
+
+  #[test]
+  fn drop_removes() {
+    let table: PTab<usize, DefParams> = PTab::new();
+    let owned: Owned<'_, usize, DefParams> = table.insert_owned(1).unwrap();
+    let key: Detached = owned.key();
+
+    assert!(table.exists(key));
+    drop(owned);
+    assert!(!table.exists(key));
+  }
+
+  #[test]
+  fn leak() {
+    let table: PTab<usize, DefParams> = PTab::new();
+    let key: Detached = table.insert_owned(1).unwrap().leak();
+
+    assert!(table.exists(key));
+    assert!(table.remove(key));
+  }
+
+  #[test]
+  fn access() {
+    let table: PTab<String, DefParams> = PTab::new();
+    let owned: Owned<'_, String, DefParams> = table.insert_owned("hello".to_string()).unwrap();
+    assert_eq!(owned.get().as_deref().map(String::as_str), Some("hello"));
+    assert_eq!(owned.get().unwrap().len(), 5);
+    assert_eq!(owned.with(String::len), Some(5));
+    assert!(core::ptr::eq(owned.table(), &raw const table));
+  }
+
+  #[test]
+  fn removed_elsewhere() {
+    let table: PTab<usize, MinParams> = PTab::new();
+    let owned: Owned<'_, usize, MinParams> = table.insert_owned(1).unwrap();
+
+    assert!(table.remove(owned.key()));
+    assert_eq!(owned.read(), None);
+    assert!(owned.get().is_none());
+
+    // Reuse the slot: dropping the handle must not remove the new entry.
+    let keys: Vec<Detached> = (0..table.capacity())
+      .map(|value| table.insert(value).unwrap())
+      .collect();
+
+    drop(owned);
+
+    assert_eq!(table.len(), keys.len());
+  }
+
+  #[test]
+  fn full_table() {
+    let table: PTab<usize, MinParams> = PTab::new();
+
+    for _ in 0..table.capacity() * 4 {
+      let owned: Owned<'_, usize, MinParams> = table.insert_owned(1).unwrap();
+      assert_eq!(owned.read(), Some(1));
+    }
+
+    assert!(table.is_empty());
+  }
+
+  #[test]
+  fn arc() {
+    let table: Arc<PTab<usize, DefParams>> = Arc::new(PTab::new());
+    let owned: OwnedArc<usize, DefParams> = PTab::insert_owned_arc(&table, 1).unwrap();
+    let key: Detached = owned.key();
+
+    assert!(Arc::ptr_eq(owned.table(), &table));
+
+    thread::spawn(move || {
+      assert_eq!(owned.read(), Some(1));
+    })
+    .join()
+    .unwrap();
+
+    assert!(!table.exists(key));
+  }
+
+  #[test]
+  fn arc_keeps_table_alive() {
+    let table: Arc<PTab<usize, DefParams>> = Arc::new(PTab::new());
+    let owned: OwnedArc<usize, DefParams> = PTab::insert_owned_arc(&table, 1).unwrap();
+
+    drop(table);
+
+    assert_eq!(owned.read(), Some(1));
+    assert_eq!(Arc::strong_count(owned.table()), 1);
+  }
+
+  #[test]
+  fn arc_leak() {
+    let table: Arc<PTab<usize, DefParams>> = Arc::new(PTab::new());
+    let owned: OwnedArc<usize, DefParams> = PTab::insert_owned_arc(&table, 1).unwrap();
+
+    assert_eq!(Arc::strong_count(&table), 2);
+
+    let key: Detached = owned.leak();
+
+    assert_eq!(Arc::strong_count(&table), 1);
+    assert!(table.exists(key));
+    assert!(table.remove(key));
+  }
+}
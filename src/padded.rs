@@ -1,5 +1,12 @@
+use core::alloc::Layout;
+use core::marker::PhantomData;
 use core::ops::Deref;
 use core::ops::DerefMut;
+use core::ptr::NonNull;
+
+use crate::heap::handle_alloc_error;
+use crate::params::Params;
+use crate::params::ParamsExt;
 
 /// Pads and aligns a value to the length of a cache line.
 ///
@@ -61,6 +68,7 @@ unsafe impl<T: Send> Send for CachePadded<T> {}
 unsafe impl<T: Sync> Sync for CachePadded<T> {}
 
 impl<T> CachePadded<T> {
+  #[allow(dead_code, reason = "not used by loom/shuttle tests")]
   #[inline]
   pub(crate) const fn new(value: T) -> Self {
     Self { value }
@@ -83,6 +91,94 @@ impl<T> DerefMut for CachePadded<T> {
   }
 }
 
+/// A value in its own heap allocation, padded and aligned to the cache line
+/// width of `P`.
+///
+/// Used for table state that must not share a cache line with its neighbors.
+/// The width selected through [`Params::CACHE_LINE`] can't be expressed as
+/// the `repr(align)` of a generic type, so the allocation is laid out from
+/// [`ParamsExt::LINE_WIDTH`] instead.
+///
+/// [`Params::CACHE_LINE`]: crate::params::Params::CACHE_LINE
+/// [`ParamsExt::LINE_WIDTH`]: crate::params::ParamsExt::LINE_WIDTH
+pub(crate) struct Line<T, P>
+where
+  P: Params + ?Sized,
+{
+  nonnull: NonNull<T>,
+  phantom: PhantomData<(T, fn(P))>,
+}
+
+// SAFETY: Owns its value like a `Box`; inherits `Send` from `T`.
+unsafe impl<T: Send, P: Params + ?Sized> Send for Line<T, P> {}
+
+// SAFETY: Owns its value like a `Box`; inherits `Sync` from `T`.
+unsafe impl<T: Sync, P: Params + ?Sized> Sync for Line<T, P> {}
+
+impl<T, P> Line<T, P>
+where
+  P: Params + ?Sized,
+{
+  /// The layout of the allocation: whole lines of `P`, aligned to a line.
+  pub(crate) const LAYOUT: Layout = match Layout::from_size_align(
+    size_of::<T>().next_multiple_of(P::LINE_WIDTH),
+    if align_of::<T>() > P::LINE_WIDTH {
+      align_of::<T>()
+    } else {
+      P::LINE_WIDTH
+    },
+  ) {
+    Ok(layout) => layout,
+    Err(_) => panic!("invalid params: `T` does not fit a cache line allocation"),
+  };
+
+  #[inline]
+  pub(crate) fn new(value: T) -> Self {
+    let nonnull: NonNull<T> = match P::ALLOCATOR.allocate(Self::LAYOUT) {
+      Ok(ptr) => ptr.cast(),
+      Err(_) => handle_alloc_error(Self::LAYOUT),
+    };
+
+    // SAFETY: The allocation is valid for writes and aligned for `T`.
+    unsafe {
+      nonnull.write(value);
+    }
+
+    Self {
+      nonnull,
+      phantom: PhantomData,
+    }
+  }
+}
+
+impl<T, P> Deref for Line<T, P>
+where
+  P: Params + ?Sized,
+{
+  type Target = T;
+
+  #[inline]
+  fn deref(&self) -> &T {
+    // SAFETY: The value was initialized in `new` and lives until `drop`.
+    unsafe { self.nonnull.as_ref() }
+  }
+}
+
+impl<T, P> Drop for Line<T, P>
+where
+  P: Params + ?Sized,
+{
+  fn drop(&mut self) {
+    // SAFETY:
+    // - The value was initialized in `new` and is dropped exactly once.
+    // - The allocation was created by `P::ALLOCATOR` with `Self::LAYOUT`.
+    unsafe {
+      self.nonnull.drop_in_place();
+      P::ALLOCATOR.deallocate(self.nonnull.cast(), Self::LAYOUT);
+    }
+  }
+}
+
 // -----------------------------------------------------------------------------
 // Tests
 // -----------------------------------------------------------------------------
@@ -90,7 +186,13 @@ impl<T> DerefMut for CachePadded<T> {
 #[cfg_attr(coverage_nightly, coverage(off))]
 #[cfg(test)]
 mod tests {
+  use core::sync::atomic::AtomicU32;
+  use core::sync::atomic::Ordering::Relaxed;
+
   use crate::padded::CachePadded;
+  use crate::padded::Line;
+  use crate::params::Capacity;
+  use crate::params::Params;
 
   #[test]
   fn works() {
@@ -99,4 +201,26 @@ mod tests {
     assert_eq!(&*value, &123);
     assert_eq!(&mut *value, &123);
   }
+
+  #[test]
+  fn line() {
+    struct Line64;
+
+    impl Params for Line64 {
+      const LENGTH: Capacity = Capacity::MIN;
+      const CACHE_LINE: usize = 64;
+    }
+
+    let value: Line<AtomicU32, Line64> = Line::new(AtomicU32::new(123));
+
+    assert_eq!(value.load(Relaxed), 123);
+    assert_eq!(Line::<AtomicU32, Line64>::LAYOUT.size(), 64);
+    assert_eq!(Line::<AtomicU32, Line64>::LAYOUT.align(), 64);
+    assert_eq!(Line::<[u64; 9], Line64>::LAYOUT.size(), 128);
+    assert!((&raw const *value).addr().is_multiple_of(64));
+
+    // The line is not part of the owning value.
+    assert_eq!(size_of::<Line<AtomicU32, Line64>>(), size_of::<usize>());
+    assert_eq!(align_of::<Line<AtomicU32, Line64>>(), align_of::<usize>());
+  }
 }
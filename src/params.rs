@@ -6,7 +6,9 @@ use core::marker::PhantomData;
 use core::mem;
 use core::num::NonZeroUsize;
 
-use crate::alloc::Layout;
+use crate::allocator::Allocator;
+use crate::allocator::Global;
+use crate::heap::Layout;
 use crate::padded::CachePadded;
 use crate::sync::atomic::AtomicUsize;
 
@@ -20,6 +22,9 @@ pub const CACHE_LINE: usize = size_of::<CachePadded<u8>>();
 /// The number of table slots that fit in a single cache line.
 pub const CACHE_LINE_SLOTS: usize = CACHE_LINE / size_of::<AtomicUsize>();
 
+/// The largest cache line width supported by [`Params::CACHE_LINE`].
+pub const MAX_CACHE_LINE: usize = 256;
+
 const _: () = assert!(
   CACHE_LINE.is_multiple_of(size_of::<AtomicUsize>()),
   "invalid params: `CACHE_LINE` must be a multiple of pointer width",
@@ -35,6 +40,11 @@ const _: () = assert!(
   "invalid params: `CACHE_LINE_SLOTS` must be a power of two",
 );
 
+const _: () = assert!(
+  CACHE_LINE <= MAX_CACHE_LINE,
+  "invalid params: `CACHE_LINE` must not exceed `MAX_CACHE_LINE`",
+);
+
 // -----------------------------------------------------------------------------
 // Configurable Params
 // -----------------------------------------------------------------------------
@@ -56,6 +66,121 @@ pub trait Params {
   ///
   /// See [`Capacity`] for more info.
   const LENGTH: Capacity = DefaultParams::LENGTH;
+
+  /// The maximum number of entries the table accepts at once.
+  ///
+  /// Unlike [`LENGTH`], which sizes the table arrays and is always a power of
+  /// two, the limit may be any count: inserts beyond it fail as if the table
+  /// were full, while the remaining slots stay unused. Values above the
+  /// capacity of the arrays have no effect. Defaults to `usize::MAX`.
+  ///
+  /// Must not be `0`; this is checked at compile time.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::PTab;
+  /// use ptab::config::{Capacity, Params};
+  ///
+  /// struct Ten;
+  ///
+  /// impl Params for Ten {
+  ///   const LENGTH: Capacity = Capacity::MIN;
+  ///   const LIMIT: usize = 10;
+  /// }
+  ///
+  /// let table: PTab<usize, Ten> = PTab::new();
+  ///
+  /// for value in 0..10 {
+  ///   assert!(table.insert(value).is_some());
+  /// }
+  ///
+  /// assert_eq!(table.capacity(), 10);
+  /// assert_eq!(table.insert(10), None);
+  /// ```
+  ///
+  /// [`LENGTH`]: Self::LENGTH
+  const LIMIT: usize = usize::MAX;
+
+  /// The number of high bits of each [`Detached`] index that identify the
+  /// table which issued it.
+  ///
+  /// Every table is assigned an id from a global counter, so indices passed
+  /// to the wrong table are rejected like stale ones instead of addressing an
+  /// unrelated entry. Ids are reused once `2^TABLE_ID_BITS` tables have been
+  /// created, and the reserved bits are taken from the generation.
+  ///
+  /// The bits are reserved in every build, but indices are only checked
+  /// against the table id in debug builds unless [`CHECKED`] is set. Defaults
+  /// to `0`, which disables table ids.
+  ///
+  /// [`Detached`]: crate::index::Detached
+  /// [`CHECKED`]: Self::CHECKED
+  const TABLE_ID_BITS: u32 = 0;
+
+  /// Whether to check table ids in release builds as well.
+  ///
+  /// See [`TABLE_ID_BITS`].
+  ///
+  /// [`TABLE_ID_BITS`]: Self::TABLE_ID_BITS
+  const CHECKED: bool = false;
+
+  /// The allocator backing the arrays of each table.
+  ///
+  /// Defaults to [`Global`]. See [`Allocator`] for more info.
+  ///
+  /// [`Allocator`]: crate::allocator::Allocator
+  /// [`Global`]: crate::allocator::Global
+  const ALLOCATOR: &'static (dyn Allocator + Sync) = &Global;
+
+  /// How consecutive allocations are placed in the table arrays.
+  ///
+  /// Defaults to [`SlotLayout::Striped`]. See [`SlotLayout`] for more info.
+  const SLOT_LAYOUT: SlotLayout = SlotLayout::Striped;
+
+  /// The cache line width in bytes that the table layout is tuned for.
+  ///
+  /// Determines how many slots share a cache line, and thereby the stride of
+  /// [`SlotLayout::Striped`], the alignment of the table arrays and the size
+  /// of a block. Defaults to [`CACHE_LINE`], which is chosen per target
+  /// architecture and may be larger than the actual line width.
+  ///
+  /// Must be a power of two between the size of a pointer and
+  /// [`MAX_CACHE_LINE`]; this is checked at compile time.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::PTab;
+  /// use ptab::config::{Capacity, Params, ParamsExt};
+  ///
+  /// struct Narrow;
+  ///
+  /// impl Params for Narrow {
+  ///   const LENGTH: Capacity = Capacity::MIN;
+  ///   const CACHE_LINE: usize = 64;
+  /// }
+  ///
+  /// assert_eq!(Narrow::LINE_SLOTS, 64 / size_of::<usize>());
+  ///
+  /// let table: PTab<u64, Narrow> = PTab::new();
+  /// assert!(table.insert(1).is_some());
+  /// ```
+  ///
+  /// ```compile_fail
+  /// use ptab::PTab;
+  /// use ptab::config::{Params, ParamsExt};
+  ///
+  /// struct Odd;
+  ///
+  /// impl Params for Odd {
+  ///   // error: not a power of two
+  ///   const CACHE_LINE: usize = 96;
+  /// }
+  ///
+  /// let table: PTab<u64, Odd> = PTab::new();
+  /// ```
+  const CACHE_LINE: usize = CACHE_LINE;
 }
 
 // -----------------------------------------------------------------------------
@@ -75,16 +200,26 @@ pub trait Params {
 /// println!("{:#?}", <ConstParams<1024> as ParamsExt>::debug());
 /// ```
 pub trait ParamsExt: Params + Sealed {
+  const LINE_WIDTH: usize = derive_line_width::<Self>();
+  const LINE_SLOTS: usize = Self::LINE_WIDTH / size_of::<AtomicUsize>();
+
   const BLOCKS: NonZeroUsize = derive_blocks::<Self>();
   const LAYOUT: Layout = derive_layout::<Self>();
-  const MEMORY: usize = Self::BLOCKS.get().strict_mul(CACHE_LINE);
+  const MEMORY: usize = Self::BLOCKS.get().strict_mul(Self::LINE_WIDTH);
+
+  const ENTRIES_LIMIT: u32 = derive_entries_limit::<Self>();
+  const ENTRIES_BASE: u32 = Self::LENGTH.as_u32().strict_sub(Self::ENTRIES_LIMIT);
 
   const ID_MASK_BITS: u32 = Self::LENGTH.log2();
   const ID_MASK_ENTRY: usize = 1_usize.strict_shl(Self::ID_MASK_BITS).strict_sub(1);
   const ID_MASK_BLOCK: usize = Self::BLOCKS.get().strict_sub(1);
-  const ID_MASK_INDEX: usize = CACHE_LINE_SLOTS.strict_sub(1);
+  const ID_MASK_INDEX: usize = Self::LINE_SLOTS.strict_sub(1);
   const ID_SHIFT_BLOCK: u32 = Self::ID_MASK_INDEX.trailing_ones();
   const ID_SHIFT_INDEX: u32 = Self::ID_MASK_BLOCK.trailing_ones();
+  const ID_TABLE_BITS: u32 = derive_table_bits::<Self>();
+  const ID_MASK_LOCAL: usize = usize::MAX >> Self::ID_TABLE_BITS;
+  const ID_MASK_CHECK: usize = derive_check_mask::<Self>();
+  const ID_LAYOUT: SlotLayout = derive_slot_layout::<Self>();
 
   #[inline]
   fn debug() -> DebugParams<Self> {
@@ -96,9 +231,11 @@ pub trait ParamsExt: Params + Sealed {
 
 mod private {
   pub trait Sealed {}
+  pub trait StaticSealed {}
 }
 
 use private::Sealed;
+use private::StaticSealed;
 
 impl<P> Sealed for P where P: Params + ?Sized {}
 impl<P> ParamsExt for P where P: Params + ?Sized {}
@@ -126,15 +263,22 @@ where
   fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
     f.debug_struct(any::type_name::<P>())
       .field("LENGTH", &P::LENGTH)
+      .field("LINE_WIDTH", &P::LINE_WIDTH)
+      .field("LINE_SLOTS", &P::LINE_SLOTS)
       .field("BLOCKS", &P::BLOCKS)
       .field("LAYOUT", &P::LAYOUT)
       .field("MEMORY", &P::MEMORY)
+      .field("ENTRIES_LIMIT", &P::ENTRIES_LIMIT)
+      .field("ENTRIES_BASE", &P::ENTRIES_BASE)
       .field("ID_MASK_BITS", &P::ID_MASK_BITS)
       .field("ID_MASK_ENTRY", &format_args!("{:0>32b}", P::ID_MASK_ENTRY))
       .field("ID_MASK_BLOCK", &format_args!("{:0>32b}", P::ID_MASK_BLOCK))
       .field("ID_MASK_INDEX", &format_args!("{:0>32b}", P::ID_MASK_INDEX))
       .field("ID_SHIFT_BLOCK", &P::ID_SHIFT_BLOCK)
       .field("ID_SHIFT_INDEX", &P::ID_SHIFT_INDEX)
+      .field("ID_TABLE_BITS", &P::ID_TABLE_BITS)
+      .field("ID_MASK_CHECK", &format_args!("{:0>32b}", P::ID_MASK_CHECK))
+      .field("ID_LAYOUT", &P::ID_LAYOUT)
       .finish()
   }
 }
@@ -198,6 +342,182 @@ impl<const N: usize> Params for ConstParams<N> {
   const LENGTH: Capacity = Capacity::new(N);
 }
 
+/// A [`Params`] implementation that holds exactly `N` entries.
+///
+/// The table arrays are sized like [`ConstParams`], but [`Params::LIMIT`] is
+/// set to `N`, so the table rejects inserts beyond `N` entries instead of
+/// accepting the rounded-up capacity. `N` must be in the range
+/// <code>1..[Capacity::MAX]</code>; other values fail to compile instead of
+/// being clamped.
+///
+/// # Examples
+///
+/// ```
+/// use ptab::{PTab, ExactParams};
+///
+/// let table: PTab<String, ExactParams<1000>> = PTab::new();
+/// assert_eq!(table.capacity(), 1000);
+/// ```
+///
+/// ```compile_fail
+/// use ptab::{PTab, ExactParams};
+///
+/// // error: no table can hold zero entries
+/// let table: PTab<String, ExactParams<0>> = PTab::new();
+/// ```
+///
+/// [Capacity::MAX]: Capacity::MAX
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ExactParams<const N: usize>;
+
+impl<const N: usize> Params for ExactParams<N> {
+  const LENGTH: Capacity = derive_exact_capacity(N);
+  const LIMIT: usize = N;
+}
+
+/// [`Params`] whose table arrays can be stored inline, without allocating them
+/// on the heap.
+///
+/// Required by [`StaticPTab`]. This trait is sealed and implemented for
+/// [`ConstParams`].
+///
+/// [`StaticPTab`]: crate::inline::StaticPTab
+pub trait StaticParams: Params + StaticSealed {
+  /// An array of `N` elements of type `E`.
+  #[doc(hidden)]
+  type Array<E>: AsRef<[E]> + AsMut<[E]>;
+}
+
+impl<const N: usize> StaticSealed for ConstParams<N> {}
+
+impl<const N: usize> StaticParams for ConstParams<N> {
+  type Array<E> = [E; N];
+}
+
+// -----------------------------------------------------------------------------
+// Branded Params
+// -----------------------------------------------------------------------------
+
+/// A [`Params`] implementation that gives a table a distinct type.
+///
+/// Behaves exactly like `P`, but tables using different brands `B` have
+/// different types, so their [`Key`]s cannot be mixed up even if they store the
+/// same values with the same capacity. `B` is typically an empty marker type.
+///
+/// # Examples
+///
+/// ```compile_fail
+/// use ptab::{Brand, PTab};
+///
+/// struct Users;
+/// struct Groups;
+///
+/// let users: PTab<String, Brand<Users>> = PTab::new();
+/// let groups: PTab<String, Brand<Groups>> = PTab::new();
+///
+/// let user = users.insert_key("alice".to_string()).unwrap();
+///
+/// groups.remove(user); // error: expected a key for `groups`
+/// ```
+///
+/// [`Key`]: crate::key::Key
+#[non_exhaustive]
+pub struct Brand<B, P = DefaultParams>
+where
+  B: ?Sized,
+  P: ?Sized,
+{
+  marker: PhantomData<fn(&B, &P)>,
+}
+
+impl<B, P> Params for Brand<B, P>
+where
+  B: ?Sized,
+  P: Params + ?Sized,
+{
+  const LENGTH: Capacity = P::LENGTH;
+  const LIMIT: usize = P::LIMIT;
+  const TABLE_ID_BITS: u32 = P::TABLE_ID_BITS;
+  const CHECKED: bool = P::CHECKED;
+  const ALLOCATOR: &'static (dyn Allocator + Sync) = P::ALLOCATOR;
+  const SLOT_LAYOUT: SlotLayout = P::SLOT_LAYOUT;
+  const CACHE_LINE: usize = P::CACHE_LINE;
+}
+
+// -----------------------------------------------------------------------------
+// Slot Layout
+// -----------------------------------------------------------------------------
+
+/// The mapping from allocation order to slots, selected by
+/// [`Params::SLOT_LAYOUT`].
+///
+/// Tables hand out ids sequentially and map each id to the slot its entry is
+/// stored in. The mapping is a bijection on the low [`ID_MASK_BITS`] bits of
+/// the id, which are also the low bits of every [`Detached`] index, so indices
+/// always address their slot directly.
+///
+/// # Examples
+///
+/// ```
+/// use ptab::PTab;
+/// use ptab::config::{Capacity, Params, SlotLayout};
+///
+/// struct Scan;
+///
+/// impl Params for Scan {
+///   const LENGTH: Capacity = Capacity::MIN;
+///   const SLOT_LAYOUT: SlotLayout = SlotLayout::Sequential;
+/// }
+///
+/// let table: PTab<u64, Scan> = PTab::new();
+///
+/// let a = table.insert(1).unwrap();
+/// let b = table.insert(2).unwrap();
+///
+/// assert_eq!(b.into_bits() - a.into_bits(), 1);
+/// ```
+///
+/// Custom layouts are checked at compile time:
+///
+/// ```compile_fail
+/// use ptab::PTab;
+/// use ptab::config::{Capacity, Params, SlotLayout};
+///
+/// struct Broken;
+///
+/// impl Params for Broken {
+///   const LENGTH: Capacity = Capacity::MIN;
+///   // error: bit 0 is used twice
+///   const SLOT_LAYOUT: SlotLayout = SlotLayout::Custom(&[0, 0, 1, 2]);
+/// }
+///
+/// let table: PTab<u64, Broken> = PTab::new();
+/// table.insert(1);
+/// ```
+///
+/// [`ID_MASK_BITS`]: crate::params::ParamsExt::ID_MASK_BITS
+/// [`Detached`]: crate::index::Detached
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SlotLayout {
+  /// Spreads consecutive allocations across cache lines.
+  ///
+  /// Each of the first [`CACHE_LINE_SLOTS`] ids lands in a different cache
+  /// line, so threads inserting concurrently rarely write to the same line.
+  Striped,
+  /// Places consecutive allocations in consecutive slots.
+  ///
+  /// Keeps entries allocated together close in memory, which favors scans
+  /// over concurrent inserts.
+  Sequential,
+  /// Permutes the bits of each id.
+  ///
+  /// Bit `i` of an id becomes bit `map[i]` of its slot. The map must be a
+  /// permutation of `0..ID_MASK_BITS`, which is checked at compile time.
+  Custom(&'static [u32]),
+}
+
 // -----------------------------------------------------------------------------
 // Capacity
 // -----------------------------------------------------------------------------
@@ -429,6 +749,55 @@ impl CapacityEnum {
 // Misc. Utilities
 // -----------------------------------------------------------------------------
 
+#[inline]
+const fn derive_line_width<P>() -> usize
+where
+  P: Params + ?Sized,
+{
+  if !P::CACHE_LINE.is_power_of_two()
+    || P::CACHE_LINE < size_of::<AtomicUsize>()
+    || P::CACHE_LINE > MAX_CACHE_LINE
+  {
+    panic_for_line_width();
+  }
+
+  P::CACHE_LINE
+}
+
+#[inline]
+const fn derive_entries_limit<P>() -> u32
+where
+  P: Params + ?Sized,
+{
+  if P::LIMIT == 0 {
+    panic_for_entries_limit();
+  }
+
+  // At `Capacity::MAX`, one slot is permanently reserved because we can't
+  // produce enough unique identifiers.
+  let length: usize = if P::LENGTH.as_usize() == Capacity::MAX.as_usize() {
+    P::LENGTH.as_usize() - 1
+  } else {
+    P::LENGTH.as_usize()
+  };
+
+  if P::LIMIT < length {
+    P::LIMIT as u32
+  } else {
+    length as u32
+  }
+}
+
+#[inline]
+const fn derive_exact_capacity(value: usize) -> Capacity {
+  // See `derive_entries_limit`
+  if value == 0 || value >= Capacity::MAX.as_usize() {
+    panic_for_exact_capacity();
+  }
+
+  Capacity::new(value)
+}
+
 #[inline]
 const fn derive_blocks<P>() -> NonZeroUsize
 where
@@ -440,7 +809,7 @@ where
   };
 
   // Round up so we have an even number of slots.
-  let Some(mem_align) = mem_bytes.checked_next_multiple_of(CACHE_LINE) else {
+  let Some(mem_align) = mem_bytes.checked_next_multiple_of(P::LINE_WIDTH) else {
     panic_for_blocks();
   };
 
@@ -450,7 +819,7 @@ where
   }
 
   // Finally, compute the block count.
-  let Some(blocks) = NonZeroUsize::new(mem_align / CACHE_LINE) else {
+  let Some(blocks) = NonZeroUsize::new(mem_align / P::LINE_WIDTH) else {
     panic_for_blocks();
   };
 
@@ -469,9 +838,109 @@ where
 
   // SAFETY:
   // - `P::MEMORY != 0` (asserted above).
-  // - `CACHE_LINE` is a power of two, so it is a valid alignment.
+  // - `P::LINE_WIDTH` is a power of two, so it is a valid alignment.
   // - `P::MEMORY <= isize::MAX`, guaranteed by `derive_blocks`.
-  unsafe { Layout::from_size_align_unchecked(P::MEMORY, CACHE_LINE) }
+  unsafe { Layout::from_size_align_unchecked(P::MEMORY, P::LINE_WIDTH) }
+}
+
+#[inline]
+const fn derive_table_bits<P>() -> u32
+where
+  P: Params + ?Sized,
+{
+  // Leave at least one bit for the generation.
+  if P::TABLE_ID_BITS >= usize::BITS - P::LENGTH.log2() {
+    panic_for_table_bits();
+  }
+
+  P::TABLE_ID_BITS
+}
+
+/// The table id bits are reserved in every build, so the layout of an index
+/// does not depend on the build profile, but only compared in debug builds
+/// unless `CHECKED` is set.
+#[inline]
+const fn derive_check_mask<P>() -> usize
+where
+  P: Params + ?Sized,
+{
+  if P::CHECKED || cfg!(debug_assertions) {
+    usize::MAX
+  } else {
+    P::ID_MASK_LOCAL
+  }
+}
+
+#[inline]
+const fn derive_slot_layout<P>() -> SlotLayout
+where
+  P: Params + ?Sized,
+{
+  let SlotLayout::Custom(map) = P::SLOT_LAYOUT else {
+    return P::SLOT_LAYOUT;
+  };
+
+  if map.len() != P::LENGTH.log2() as usize {
+    panic_for_slot_layout();
+  }
+
+  let mut seen: usize = 0;
+  let mut index: usize = 0;
+
+  while index < map.len() {
+    let bit: u32 = map[index];
+
+    if bit >= P::LENGTH.log2() || seen & (1 << bit) != 0 {
+      panic_for_slot_layout();
+    }
+
+    seen |= 1 << bit;
+    index += 1;
+  }
+
+  P::SLOT_LAYOUT
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cold]
+#[cfg_attr(panic = "abort", inline)]
+#[cfg_attr(not(panic = "abort"), inline(never))]
+const fn panic_for_slot_layout() -> ! {
+  panic!("invalid params: `SLOT_LAYOUT` must permute the id bits");
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cold]
+#[cfg_attr(panic = "abort", inline)]
+#[cfg_attr(not(panic = "abort"), inline(never))]
+const fn panic_for_table_bits() -> ! {
+  panic!("invalid params: `TABLE_ID_BITS` must leave room for a generation");
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cold]
+#[cfg_attr(panic = "abort", inline)]
+#[cfg_attr(not(panic = "abort"), inline(never))]
+const fn panic_for_line_width() -> ! {
+  panic!(
+    "invalid params: `CACHE_LINE` must be a power of two within `size_of::<usize>()..=MAX_CACHE_LINE`"
+  );
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cold]
+#[cfg_attr(panic = "abort", inline)]
+#[cfg_attr(not(panic = "abort"), inline(never))]
+const fn panic_for_entries_limit() -> ! {
+  panic!("invalid params: `LIMIT` must not be zero");
+}
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cold]
+#[cfg_attr(panic = "abort", inline)]
+#[cfg_attr(not(panic = "abort"), inline(never))]
+const fn panic_for_exact_capacity() -> ! {
+  panic!("invalid params: `N` must be within `1..Capacity::MAX`");
 }
 
 #[cfg_attr(coverage_nightly, coverage(off))]
@@ -491,9 +960,13 @@ const fn panic_for_blocks() -> ! {
 mod tests {
   use core::num::NonZeroUsize;
 
+  use crate::params::CACHE_LINE;
   use crate::params::CACHE_LINE_SLOTS;
   use crate::params::Capacity;
+  use crate::params::ConstParams;
   use crate::params::DefaultParams;
+  use crate::params::ExactParams;
+  use crate::params::MAX_CACHE_LINE;
   use crate::params::Params;
   use crate::params::ParamsExt;
   use crate::params::derive_blocks;
@@ -673,4 +1146,70 @@ mod tests {
       assert_eq!(P::ID_SHIFT_INDEX, P::ID_MASK_BLOCK.count_ones());
     });
   }
+
+  #[test]
+  fn line_width_default() {
+    assert_eq!(DefaultParams::LINE_WIDTH, CACHE_LINE);
+    assert_eq!(DefaultParams::LINE_SLOTS, CACHE_LINE_SLOTS);
+  }
+
+  #[test]
+  fn line_width_custom() {
+    struct Narrow;
+
+    impl Params for Narrow {
+      const CACHE_LINE: usize = 32;
+    }
+
+    struct Wide;
+
+    impl Params for Wide {
+      const CACHE_LINE: usize = MAX_CACHE_LINE;
+    }
+
+    fn check<P: Params>(width: usize) {
+      assert_eq!(P::LINE_WIDTH, width);
+      assert_eq!(P::LINE_SLOTS, width / size_of::<usize>());
+      assert_eq!(P::BLOCKS.get() * P::LINE_SLOTS, P::LENGTH.as_usize());
+      assert_eq!(P::LAYOUT.align(), width);
+      assert_eq!(P::ID_MASK_INDEX, P::LINE_SLOTS - 1);
+      assert_eq!(P::ID_MASK_BITS, P::ID_SHIFT_BLOCK + P::ID_SHIFT_INDEX);
+    }
+
+    check::<Narrow>(32);
+    check::<Wide>(MAX_CACHE_LINE);
+  }
+
+  #[test]
+  fn entries_limit() {
+    struct Limited;
+
+    impl Params for Limited {
+      const LENGTH: Capacity = Capacity::MIN;
+      const LIMIT: usize = 3;
+    }
+
+    type MaxParams = ConstParams<{ Capacity::MAX.as_usize() }>;
+
+    assert_eq!(DefaultParams::ENTRIES_LIMIT, Capacity::DEF.as_u32());
+    assert_eq!(DefaultParams::ENTRIES_BASE, 0);
+    assert_eq!(MaxParams::ENTRIES_LIMIT, Capacity::MAX.as_u32() - 1);
+    assert_eq!(MaxParams::ENTRIES_BASE, 1);
+    assert_eq!(Limited::ENTRIES_LIMIT, 3);
+    assert_eq!(Limited::ENTRIES_BASE, Capacity::MIN.as_u32() - 3);
+  }
+
+  #[test]
+  fn exact_params() {
+    type Largest = ExactParams<{ Capacity::MAX.as_usize() - 1 }>;
+
+    assert_eq!(ExactParams::<1>::LENGTH, Capacity::MIN);
+    assert_eq!(ExactParams::<1>::ENTRIES_LIMIT, 1);
+    assert_eq!(ExactParams::<1000>::LENGTH.as_usize(), 1024);
+    assert_eq!(ExactParams::<1000>::ENTRIES_LIMIT, 1000);
+    assert_eq!(ExactParams::<1024>::ENTRIES_BASE, 0);
+
+    assert_eq!(Largest::LENGTH, Capacity::MAX);
+    assert_eq!(Largest::ENTRIES_LIMIT, Capacity::MAX.as_u32() - 1);
+  }
 }
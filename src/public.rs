@@ -1,15 +1,37 @@
+use alloc::boxed::Box;
+use alloc::sync::Arc;
 use core::fmt::Debug;
 use core::fmt::Formatter;
 use core::fmt::Result;
 use core::mem::MaybeUninit;
+#[cfg(feature = "std")]
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
 
+use crate::error::InsertAtError;
+#[cfg(feature = "std")]
+use crate::expiry::Sweeper;
+use crate::flags::Flags;
+use crate::hooks::Hooks;
 use crate::index::Detached;
+use crate::key::Handle;
+use crate::key::Key;
+use crate::monitor::Monitor;
+use crate::owned::Owned;
+use crate::owned::OwnedArc;
 use crate::params::DefaultParams;
 use crate::params::Params;
 use crate::params::ParamsExt;
+use crate::quota::Quota;
+use crate::reason::Reason;
+#[cfg(feature = "std")]
+use crate::seal::Token;
+use crate::table::RawTable;
 use crate::table::Table;
+use crate::watch::Watch;
 
-pub use crate::reclaim::sdd::Guard;
+pub use crate::reclaim::Guard;
 pub use crate::table::WeakKeys;
 
 /// A lock-free concurrent table.
@@ -81,9 +103,46 @@ where
     }
   }
 
+  /// Creates a new, empty table that reports entry lifecycle events to
+  /// `hooks`.
+  ///
+  /// See [`Hooks`] for the events and their ordering guarantees.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::{Detached, Hooks, PTab, Reason};
+  ///
+  /// struct Audit;
+  ///
+  /// impl Hooks<u64> for Audit {
+  ///   fn on_remove(&self, index: Detached, value: &u64, reason: Reason) {
+  ///     println!("removed {value} at {index}: {reason:?}");
+  ///   }
+  /// }
+  ///
+  /// let table: PTab<u64> = PTab::with_hooks(Audit);
+  /// let index = table.insert(42).unwrap();
+  ///
+  /// assert!(table.remove(index));
+  /// ```
+  ///
+  /// [`Hooks`]: crate::hooks::Hooks
+  #[inline]
+  pub fn with_hooks<H>(hooks: H) -> Self
+  where
+    T: 'static,
+    H: Hooks<T> + 'static,
+  {
+    Self {
+      inner: Table::with_hooks(hooks),
+    }
+  }
+
   /// Returns the maximum number of entries the table can hold.
   ///
-  /// Determined by [`Params::LENGTH`] and fixed for the lifetime of the table.
+  /// Determined by [`Params::LENGTH`] and [`Params::LIMIT`], and fixed for the
+  /// lifetime of the table.
   ///
   /// # Examples
   ///
@@ -95,6 +154,7 @@ where
   /// ```
   ///
   /// [`Params::LENGTH`]: crate::params::Params::LENGTH
+  /// [`Params::LIMIT`]: crate::params::Params::LIMIT
   #[inline]
   pub const fn capacity(&self) -> usize {
     self.inner.cap()
@@ -164,6 +224,79 @@ where
     self.inner.insert(value)
   }
 
+  /// Inserts a value into the table and returns a typed [`Key`] to it.
+  ///
+  /// Like [`insert()`], but the key can only be used with tables of this
+  /// exact type. Returns [`None`] if the table is at capacity.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::{Key, PTab};
+  ///
+  /// let table: PTab<&str> = PTab::new();
+  /// let key: Key<&str> = table.insert_key("hello").unwrap();
+  ///
+  /// assert_eq!(table.read(key), Some("hello"));
+  /// ```
+  ///
+  /// [`Key`]: crate::key::Key
+  /// [`insert()`]: Self::insert
+  #[inline]
+  pub fn insert_key(&self, value: T) -> Option<Key<T, P>>
+  where
+    T: 'static,
+  {
+    self.inner.insert(value).map(Key::new)
+  }
+
+  /// Inserts a value into the table and returns an owning handle to it.
+  ///
+  /// The entry is removed when the returned [`Owned`] is dropped, unless it is
+  /// [leaked]. Returns [`None`] if the table is at capacity.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::PTab;
+  ///
+  /// let table: PTab<&str> = PTab::new();
+  /// let owned = table.insert_owned("hello").unwrap();
+  ///
+  /// assert!(table.exists(owned.key()));
+  ///
+  /// drop(owned);
+  /// assert!(table.is_empty());
+  /// ```
+  ///
+  /// [`Owned`]: crate::owned::Owned
+  /// [leaked]: crate::owned::Owned::leak
+  #[inline]
+  pub fn insert_owned(&self, value: T) -> Option<Owned<'_, T, P>>
+  where
+    T: 'static,
+  {
+    self.inner.insert(value).map(|key| Owned::new(self, key))
+  }
+
+  /// Inserts a value into a shared table and returns an owning handle to it.
+  ///
+  /// Like [`insert_owned()`], but the returned [`OwnedArc`] keeps the table
+  /// alive instead of borrowing it.
+  ///
+  /// [`insert_owned()`]: Self::insert_owned
+  /// [`OwnedArc`]: crate::owned::OwnedArc
+  #[inline]
+  pub fn insert_owned_arc(this: &Arc<Self>, value: T) -> Option<OwnedArc<T, P>>
+  where
+    T: 'static,
+  {
+    this
+      .inner
+      .insert(value)
+      .map(|key| OwnedArc::new(Arc::clone(this), key))
+  }
+
   /// Inserts a value using an initialization function that receives the index.
   ///
   /// Enables self-referential structures where the stored value contains its
@@ -207,12 +340,218 @@ where
     self.inner.write(init)
   }
 
+  /// Inserts a value using an initialization function that receives its
+  /// typed [`Key`].
+  ///
+  /// Like [`write()`], but hands out a [`Key`] instead of a [`Detached`]
+  /// index. Returns [`None`] if the table is at capacity.
+  ///
+  /// [`Key`]: crate::key::Key
+  /// [`Detached`]: crate::index::Detached
+  /// [`write()`]: Self::write
+  #[inline]
+  pub fn write_key<F>(&self, init: F) -> Option<Key<T, P>>
+  where
+    T: 'static,
+    F: FnOnce(&mut MaybeUninit<T>, Key<T, P>),
+  {
+    self
+      .inner
+      .write(|maybe, index| init(maybe, Key::new(index)))
+      .map(Key::new)
+  }
+
+  /// Returns a typed [`Key`] for the entry at `index`.
+  ///
+  /// Returns [`None`] if no entry exists, so untyped indices can only be
+  /// upgraded while they refer to an entry of this table.
+  ///
+  /// [`Key`]: crate::key::Key
+  #[inline]
+  pub fn key(&self, index: Detached) -> Option<Key<T, P>> {
+    self.exists(index).then_some(Key::new(index))
+  }
+
+  /// Inserts a value at a specific index.
+  ///
+  /// Claims exactly the slot and generation of `index`, so an entry can be
+  /// restored under the index it originally had, e.g. when replaying a log or
+  /// mirroring a peer. Later insertions allocate around the claimed slot.
+  ///
+  /// Requires exclusive access and takes time linear in the capacity.
+  ///
+  /// With [table ids] enabled, only indices carrying the id of this table can
+  /// be claimed. Indices issued by another table are rejected rather than
+  /// re-tagged, since the id of a table cannot be overridden.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`InsertAtError::Occupied`] if the slot of `index` already holds
+  /// an entry, of any generation, [`InsertAtError::Stale`] if `index` is of
+  /// an older generation than the table would issue next for its slot,
+  /// [`InsertAtError::Foreign`] if it carries the id of another table, and
+  /// [`InsertAtError::Full`] if the table is at capacity.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::PTab;
+  ///
+  /// let source: PTab<&str> = PTab::new();
+  /// let index = source.insert("replayed").unwrap();
+  ///
+  /// let mut table: PTab<&str> = PTab::new();
+  /// table.insert_at(index, "replayed").unwrap();
+  ///
+  /// assert_eq!(table.read(index), Some("replayed"));
+  /// ```
+  ///
+  /// [table ids]: crate::config::Params::TABLE_ID_BITS
+  /// [`InsertAtError::Occupied`]: crate::error::InsertAtError::Occupied
+  /// [`InsertAtError::Stale`]: crate::error::InsertAtError::Stale
+  /// [`InsertAtError::Foreign`]: crate::error::InsertAtError::Foreign
+  /// [`InsertAtError::Full`]: crate::error::InsertAtError::Full
+  #[inline]
+  pub fn insert_at(&mut self, index: Detached, value: T) -> core::result::Result<(), InsertAtError>
+  where
+    T: 'static,
+  {
+    self.inner.insert_at(index, value)
+  }
+
+  /// Inserts a value that expires after `ttl`.
+  ///
+  /// Expired entries are not removed automatically; they are removed by
+  /// [`sweep()`], either called periodically or from a background
+  /// [`sweeper()`], through the same path as [`remove()`] with
+  /// [`Reason::Expired`]. Until then, they remain accessible.
+  ///
+  /// Returns [`None`] if the table is at capacity.
+  ///
+  /// The first call allocates lease storage for the whole table, one pointer
+  /// per slot of [`capacity()`]. It is allocated zeroed, so pages that are
+  /// never touched are typically never committed; each lease itself is
+  /// allocated along with its entry.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::time::{Duration, Instant};
+  ///
+  /// use ptab::PTab;
+  ///
+  /// let table: PTab<&str> = PTab::new();
+  /// let idx = table.insert_with_ttl("session", Duration::from_secs(30)).unwrap();
+  ///
+  /// table.sweep(Instant::now());
+  /// assert!(table.exists(idx));
+  ///
+  /// table.sweep(Instant::now() + Duration::from_secs(60));
+  /// assert!(!table.exists(idx));
+  /// ```
+  ///
+  /// [`capacity()`]: Self::capacity
+  /// [`sweep()`]: Self::sweep
+  /// [`sweeper()`]: Self::sweeper
+  /// [`remove()`]: Self::remove
+  /// [`Reason::Expired`]: crate::reason::Reason::Expired
+  #[cfg(feature = "std")]
+  #[inline]
+  pub fn insert_with_ttl(&self, value: T, ttl: Duration) -> Option<Detached>
+  where
+    T: 'static,
+  {
+    self.inner.insert_with_ttl(value, ttl)
+  }
+
+  /// Extends the lifetime of the entry at the given index.
+  ///
+  /// The entry now expires after its original TTL, counted from now. Returns
+  /// `false` if no entry exists or it was inserted without a TTL.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::time::Duration;
+  ///
+  /// use ptab::PTab;
+  ///
+  /// let table: PTab<&str> = PTab::new();
+  /// let idx = table.insert_with_ttl("session", Duration::from_secs(30)).unwrap();
+  ///
+  /// assert!(table.touch(idx));
+  /// ```
+  #[cfg(feature = "std")]
+  #[inline]
+  pub fn touch(&self, index: impl Handle<T, P>) -> bool {
+    self.inner.touch(index.detached())
+  }
+
+  /// Returns the remaining lifetime of the entry at the given index.
+  ///
+  /// Returns [`None`] if no entry exists or it was inserted without a TTL.
+  /// Deadlines are tracked with a resolution of 10 milliseconds.
+  #[cfg(feature = "std")]
+  #[inline]
+  pub fn ttl(&self, index: impl Handle<T, P>) -> Option<Duration> {
+    self.inner.ttl(index.detached())
+  }
+
+  /// Removes every entry whose TTL elapsed at or before `now`.
+  ///
+  /// Returns the number of entries removed. Expired entries are found with a
+  /// timer wheel, so the cost is proportional to the number of scheduled
+  /// entries due since the last sweep rather than to the size of the table.
+  #[cfg(feature = "std")]
+  #[inline]
+  pub fn sweep(&self, now: Instant) -> usize {
+    self.inner.sweep(now)
+  }
+
+  /// Spawns a background thread that calls [`sweep()`] every `interval`.
+  ///
+  /// The thread stops when the returned [`Sweeper`] is dropped or once the
+  /// table itself is dropped.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::sync::Arc;
+  /// use std::time::Duration;
+  ///
+  /// use ptab::{PTab, Reason};
+  ///
+  /// let table: Arc<PTab<&str>> = Arc::new(PTab::new());
+  /// let sweeper = PTab::sweeper(&table, Duration::from_millis(10));
+  ///
+  /// let idx = table.insert_with_ttl("session", Duration::from_millis(10)).unwrap();
+  ///
+  /// assert_eq!(table.monitor(idx).wait(), Reason::Expired);
+  ///
+  /// sweeper.stop();
+  /// ```
+  ///
+  /// [`sweep()`]: Self::sweep
+  /// [`Sweeper`]: crate::expiry::Sweeper
+  #[cfg(feature = "std")]
+  #[inline]
+  pub fn sweeper(this: &Arc<Self>, interval: Duration) -> Sweeper
+  where
+    T: Send + 'static,
+    P: 'static,
+  {
+    Sweeper::spawn(Arc::downgrade(this), interval)
+  }
+
   /// Removes the entry at the given index.
   ///
   /// Returns `true` if an entry was removed, `false` if already absent. The
   /// slot becomes available for reuse immediately; memory is reclaimed via
   /// epoch-based reclamation once no readers hold references.
   ///
+  /// Entries [linked] to the removed entry are removed as well, transitively,
+  /// with [`Reason::Linked`].
+  ///
   /// # Examples
   ///
   /// ```
@@ -224,9 +563,116 @@ where
   /// assert!(table.remove(idx));  // Entry removed
   /// assert!(!table.remove(idx)); // Already gone
   /// ```
+  ///
+  /// [linked]: Self::link
+  /// [`Reason::Linked`]: crate::reason::Reason::Linked
   #[inline]
-  pub fn remove(&self, index: Detached) -> bool {
-    self.inner.remove(index)
+  pub fn remove(&self, index: impl Handle<T, P>) -> bool {
+    self.inner.remove(index.detached())
+  }
+
+  /// Links the entries at `a` and `b`.
+  ///
+  /// Links are bidirectional: removing either entry also removes the other,
+  /// reporting [`Reason::Linked`] with the index of the entry that caused it.
+  /// Removal cascades through chains of links without recursion.
+  ///
+  /// Returns `true` if the entries are linked, or `false` if either entry does
+  /// not exist. Linking an entry to itself, or linking already linked entries,
+  /// has no effect.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::{PTab, Reason};
+  ///
+  /// let table: PTab<&str> = PTab::new();
+  /// let parent = table.insert("parent").unwrap();
+  /// let child = table.insert("child").unwrap();
+  ///
+  /// assert!(table.link(parent, child));
+  ///
+  /// let monitor = table.monitor(child);
+  /// table.remove(parent);
+  ///
+  /// assert!(!table.exists(child));
+  /// assert_eq!(monitor.reason(), Some(Reason::Linked(parent)));
+  /// ```
+  ///
+  /// [`Reason::Linked`]: crate::reason::Reason::Linked
+  #[inline]
+  pub fn link(&self, a: impl Handle<T, P>, b: impl Handle<T, P>) -> bool {
+    self.inner.link(a.detached(), b.detached())
+  }
+
+  /// Removes the link between the entries at `a` and `b`.
+  ///
+  /// Returns `true` if the entries were linked.
+  ///
+  /// Unlinking releases the link immediately, but a small amount of
+  /// bookkeeping remains attached to both entries until they are removed.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::PTab;
+  ///
+  /// let table: PTab<&str> = PTab::new();
+  /// let a = table.insert("a").unwrap();
+  /// let b = table.insert("b").unwrap();
+  ///
+  /// table.link(a, b);
+  ///
+  /// assert!(table.unlink(b, a));
+  /// assert!(!table.unlink(a, b));
+  ///
+  /// table.remove(a);
+  /// assert!(table.exists(b));
+  /// ```
+  #[inline]
+  pub fn unlink(&self, a: impl Handle<T, P>, b: impl Handle<T, P>) -> bool {
+    self.inner.unlink(a.detached(), b.detached())
+  }
+
+  /// Encodes an index as an opaque [`Token`] to hand out to clients.
+  ///
+  /// The token is encrypted under a random key chosen by this table on first
+  /// use, so it reveals nothing about the entry, and tokens not exported by
+  /// this table are rejected on import; see [`Token`] for the exact
+  /// guarantees. Use [`import()`] to recover the index.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::PTab;
+  ///
+  /// let table: PTab<&str> = PTab::new();
+  /// let index = table.insert("session").unwrap();
+  ///
+  /// let token = table.export(index);
+  /// assert_eq!(table.import(token), Some(index));
+  /// ```
+  ///
+  /// [`Token`]: crate::seal::Token
+  /// [`import()`]: Self::import
+  #[cfg(feature = "std")]
+  #[inline]
+  pub fn export(&self, index: impl Handle<T, P>) -> Token {
+    self.inner.export(index.detached())
+  }
+
+  /// Decodes a [`Token`] created by [`export()`] on this table.
+  ///
+  /// Returns [`None`] if the token fails the redundancy check, as corrupted
+  /// tokens and tokens exported by another table almost always do. A genuine token for an entry that has since
+  /// been removed still decodes; the returned index then refers to no entry.
+  ///
+  /// [`Token`]: crate::seal::Token
+  /// [`export()`]: Self::export
+  #[cfg(feature = "std")]
+  #[inline]
+  pub fn import(&self, token: Token) -> Option<Detached> {
+    self.inner.import(token)
   }
 
   /// Returns `true` if an entry exists at the given index.
@@ -246,8 +692,8 @@ where
   /// assert!(!table.exists(idx));
   /// ```
   #[inline]
-  pub fn exists(&self, index: Detached) -> bool {
-    self.inner.exists(index, &Guard::new())
+  pub fn exists(&self, index: impl Handle<T, P>) -> bool {
+    self.inner.exists(index.detached(), &Guard::new())
   }
 
   /// Accesses an entry by index, applying a function to it.
@@ -268,11 +714,113 @@ where
   /// assert_eq!(len, Some(5));
   /// ```
   #[inline]
-  pub fn with<F, R>(&self, index: Detached, f: F) -> Option<R>
+  pub fn with<F, R>(&self, index: impl Handle<T, P>, f: F) -> Option<R>
   where
     F: Fn(&T) -> R,
   {
-    self.inner.with(index, &Guard::new(), f)
+    self.inner.with(index.detached(), &Guard::new(), f)
+  }
+
+  #[inline]
+  pub(crate) fn get<'guard>(&self, index: Detached, guard: &'guard Guard) -> Option<&'guard T> {
+    self.inner.get(index, guard)
+  }
+
+  /// Accesses an entry and its [`Flags`], applying a function to them.
+  ///
+  /// The flags are read atomically with the entry. Returns [`None`] if no
+  /// entry exists.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::{Flags, PTab};
+  ///
+  /// let table: PTab<u64> = PTab::new();
+  /// let idx = table.insert(42).unwrap();
+  ///
+  /// table.set_flags(idx, Flags::SECOND);
+  ///
+  /// assert_eq!(table.with_flags(idx, |&v, flags| (v, flags)), Some((42, Flags::SECOND)));
+  /// ```
+  ///
+  /// [`Flags`]: crate::flags::Flags
+  #[inline]
+  pub fn with_flags<F, R>(&self, index: impl Handle<T, P>, f: F) -> Option<R>
+  where
+    F: Fn(&T, Flags) -> R,
+  {
+    self.inner.with_flags(index.detached(), &Guard::new(), f)
+  }
+
+  /// Returns the [`Flags`] of the entry at the given index.
+  ///
+  /// Returns [`None`] if no entry exists.
+  ///
+  /// [`Flags`]: crate::flags::Flags
+  #[inline]
+  pub fn flags(&self, index: impl Handle<T, P>) -> Option<Flags> {
+    self.with_flags(index, |_, flags| flags)
+  }
+
+  /// Sets `flags` on the entry at the given index, returning the previous
+  /// flags.
+  ///
+  /// Returns [`None`] if no entry exists.
+  #[inline]
+  pub fn set_flags(&self, index: impl Handle<T, P>, flags: Flags) -> Option<Flags> {
+    self
+      .inner
+      .update_flags(index.detached(), |current| Some(current | flags))
+      .ok()
+  }
+
+  /// Clears `flags` on the entry at the given index, returning the previous
+  /// flags.
+  ///
+  /// Returns [`None`] if no entry exists.
+  #[inline]
+  pub fn clear_flags(&self, index: impl Handle<T, P>, flags: Flags) -> Option<Flags> {
+    self
+      .inner
+      .update_flags(index.detached(), |current| Some(current - flags))
+      .ok()
+  }
+
+  /// Replaces the flags of the entry at the given index with `new` if they
+  /// equal `current`.
+  ///
+  /// Returns the previous flags on success.
+  ///
+  /// # Errors
+  ///
+  /// Returns the actual flags if they differ from `current`, or [`None`] if
+  /// no entry exists.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::{Flags, PTab};
+  ///
+  /// const EXITING: Flags = Flags::FIRST;
+  ///
+  /// let table: PTab<u64> = PTab::new();
+  /// let idx = table.insert(1).unwrap();
+  ///
+  /// // Only one caller wins the transition into the exiting state
+  /// assert_eq!(table.compare_exchange_flags(idx, Flags::EMPTY, EXITING), Ok(Flags::EMPTY));
+  /// assert_eq!(table.compare_exchange_flags(idx, Flags::EMPTY, EXITING), Err(Some(EXITING)));
+  /// ```
+  #[inline]
+  pub fn compare_exchange_flags(
+    &self,
+    index: impl Handle<T, P>,
+    current: Flags,
+    new: Flags,
+  ) -> core::result::Result<Flags, Option<Flags>> {
+    self
+      .inner
+      .update_flags(index.detached(), |flags| (flags == current).then_some(new))
   }
 
   /// Returns a copy of the entry at the given index.
@@ -291,11 +839,141 @@ where
   /// assert_eq!(table.read(idx), Some(42));
   /// ```
   #[inline]
-  pub fn read(&self, index: Detached) -> Option<T>
+  pub fn read(&self, index: impl Handle<T, P>) -> Option<T>
   where
     T: Copy,
   {
-    self.inner.read(index, &Guard::new())
+    self.inner.read(index.detached(), &Guard::new())
+  }
+
+  /// Monitors the entry at the given index.
+  ///
+  /// The returned [`Monitor`] fires exactly once, when the entry is removed.
+  /// If no entry exists at `index` (including when `index` is stale), the
+  /// monitor fires immediately with [`Reason::NoEntry`].
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::{PTab, Reason};
+  ///
+  /// let table: PTab<i32> = PTab::new();
+  /// let idx = table.insert(42).unwrap();
+  ///
+  /// let monitor = table.monitor(idx);
+  /// assert!(!monitor.is_down());
+  ///
+  /// table.remove(idx);
+  /// assert_eq!(monitor.reason(), Some(Reason::Normal));
+  ///
+  /// // Monitoring a removed entry fires immediately
+  /// let monitor = table.monitor(idx);
+  /// assert_eq!(monitor.reason(), Some(Reason::NoEntry));
+  /// ```
+  ///
+  /// [`Monitor`]: crate::monitor::Monitor
+  /// [`Reason::NoEntry`]: crate::reason::Reason::NoEntry
+  #[must_use]
+  #[inline]
+  pub fn monitor(&self, index: impl Handle<T, P>) -> Monitor {
+    self.attach(Monitor::new(index.detached(), None))
+  }
+
+  /// Monitors the entry at the given index, invoking `f` when it is removed.
+  ///
+  /// Behaves like [`monitor()`], additionally running `f` exactly once with
+  /// the removal [`Reason`] unless the monitor is cancelled first. The callback
+  /// runs on the thread that removes the entry, or immediately on the current
+  /// thread if the entry does not exist.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::sync::mpsc;
+  ///
+  /// use ptab::{PTab, Reason};
+  ///
+  /// let table: PTab<i32> = PTab::new();
+  /// let idx = table.insert(42).unwrap();
+  /// let (send, recv) = mpsc::channel();
+  ///
+  /// table.monitor_with(idx, move |reason| send.send(reason).unwrap());
+  /// table.remove(idx);
+  ///
+  /// assert_eq!(recv.recv(), Ok(Reason::Normal));
+  /// ```
+  ///
+  /// [`monitor()`]: Self::monitor
+  /// [`Reason`]: crate::reason::Reason
+  #[inline]
+  pub fn monitor_with<F>(&self, index: impl Handle<T, P>, f: F) -> Monitor
+  where
+    F: FnOnce(Reason) + Send + 'static,
+  {
+    self.attach(Monitor::new(index.detached(), Some(Box::new(f))))
+  }
+
+  /// Creates a [`Quota`] that admits at most `limit` entries into the table.
+  ///
+  /// Each quota has its own counter, so tenants sharing one table can be
+  /// limited independently of each other.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::PTab;
+  ///
+  /// let table: PTab<u64> = PTab::new();
+  /// let quota = table.quota(1);
+  ///
+  /// assert!(quota.insert(1).is_some());
+  /// assert!(quota.insert(2).is_none());
+  /// assert!(table.insert(3).is_some());
+  /// ```
+  ///
+  /// [`Quota`]: crate::quota::Quota
+  #[inline]
+  pub fn quota(&self, limit: usize) -> Quota<'_, T, P> {
+    Quota::new(self, limit)
+  }
+
+  #[inline]
+  pub(crate) fn into_raw(self) -> RawTable<T, P> {
+    self.inner.into_raw()
+  }
+
+  #[inline]
+  pub(crate) fn from_raw(raw: RawTable<T, P>) -> Self
+  where
+    T: 'static,
+  {
+    Self {
+      inner: Table::from_raw(raw),
+    }
+  }
+
+  #[inline]
+  pub(crate) fn watch(&self, index: Detached, watch: Watch) -> core::result::Result<(), Watch> {
+    self.inner.watch(index, watch)
+  }
+
+  #[inline]
+  pub(crate) fn watched<F>(&self, index: Detached, f: F) -> bool
+  where
+    F: FnMut(&Watch) -> bool,
+  {
+    self.inner.watched(index, f)
+  }
+
+  #[inline]
+  fn attach(&self, monitor: Monitor) -> Monitor {
+    let watch: Watch = Watch::Monitor(Arc::clone(monitor.state()));
+
+    if let Err(watch) = self.inner.watch(monitor.index(), watch) {
+      watch.fire(Reason::NoEntry);
+    }
+
+    monitor
   }
 
   /// Returns a weakly consistent iterator over all currently allocated indices.
@@ -329,6 +1007,33 @@ where
   pub fn weak_keys(&self) -> WeakKeys<'_, T, P> {
     self.inner.weak_keys(Guard::new())
   }
+
+  /// Returns a snapshot of the table's operation counters.
+  ///
+  /// Only inserts and removals update the counters; lookups never write to
+  /// shared memory. See [`Stats`] for the available counters.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::{PTab, ConstParams};
+  ///
+  /// let table: PTab<i32, ConstParams<16>> = PTab::new();
+  ///
+  /// for value in 0..17 {
+  ///   table.insert(value);
+  /// }
+  ///
+  /// assert_eq!(table.stats().inserts(), 16);
+  /// assert_eq!(table.stats().failed_inserts(), 1);
+  /// ```
+  ///
+  /// [`Stats`]: crate::stats::Stats
+  #[cfg(feature = "stats")]
+  #[inline]
+  pub fn stats(&self) -> crate::stats::Stats {
+    self.inner.stats()
+  }
 }
 
 impl<T, P> Debug for PTab<T, P>
@@ -344,6 +1049,76 @@ where
   }
 }
 
+/// Serializes a snapshot of the table.
+///
+/// The snapshot holds every occupied entry together with its exact index and
+/// the allocator state, so [deserializing] it yields a table where each index
+/// issued by this table resolves to the same value, including its table id.
+/// Flags, monitors, links, TTLs, and hooks are not part of the snapshot.
+///
+/// The snapshot is only consistent if the table is not modified concurrently
+/// while it is serialized; deserialization rejects inconsistent snapshots.
+///
+/// [deserializing]: serde::Deserialize
+#[cfg(feature = "serde")]
+impl<T, P> serde::Serialize for PTab<T, P>
+where
+  T: serde::Serialize,
+  P: Params + ?Sized,
+{
+  fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    self.inner.snapshot(&Guard::new()).serialize(serializer)
+  }
+}
+
+/// Restores a table from a serialized snapshot.
+///
+/// Fails if the snapshot was taken from a table with a different capacity or
+/// is otherwise inconsistent.
+///
+/// The restored table takes over the [table id] of the snapshotted table, so
+/// every index it issued resolves to the same value in the restored one. If
+/// the snapshotted table is still alive, the two tables accept each other's
+/// indices, so snapshots are meant for replacing a table, e.g. across a
+/// restart.
+///
+/// # Examples
+///
+/// ```
+/// use ptab::PTab;
+///
+/// let table: PTab<String> = PTab::new();
+/// let index = table.insert("hello".to_string()).unwrap();
+///
+/// let json = serde_json::to_string(&table).unwrap();
+/// let restored: PTab<String> = serde_json::from_str(&json).unwrap();
+///
+/// assert_eq!(restored.with(index, String::clone), Some("hello".to_string()));
+/// ```
+///
+/// [table id]: crate::params::Params::TABLE_ID_BITS
+#[cfg(feature = "serde")]
+impl<'de, T, P> serde::Deserialize<'de> for PTab<T, P>
+where
+  T: serde::Deserialize<'de> + 'static,
+  P: Params + ?Sized,
+{
+  fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+  where
+    D: serde::Deserializer<'de>,
+  {
+    let snapshot: crate::snapshot::Snapshot<T> = serde::Deserialize::deserialize(deserializer)?;
+
+    match Table::restore(snapshot) {
+      Ok(inner) => Ok(Self { inner }),
+      Err(message) => Err(<D::Error as serde::de::Error>::custom(message)),
+    }
+  }
+}
+
 impl<T, P> Default for PTab<T, P>
 where
   P: Params + ?Sized,
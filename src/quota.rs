@@ -0,0 +1,351 @@
+use alloc::sync::Arc;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::mem::MaybeUninit;
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering::Acquire;
+use core::sync::atomic::Ordering::Relaxed;
+use core::sync::atomic::Ordering::Release;
+
+use crate::index::Detached;
+use crate::key::Handle;
+use crate::params::DefaultParams;
+use crate::params::Params;
+use crate::public::PTab;
+use crate::reason::Reason;
+use crate::watch::Watch;
+
+/// A share of a table's capacity with its own entry limit.
+///
+/// Created by [`PTab::quota`]. Entries inserted through a quota live in the
+/// shared table like any other entry, but each quota admits at most
+/// [`limit`] of them at a time, so one tenant of a shared table cannot starve
+/// the others. Inserts still fail if the table itself is full.
+///
+/// An entry is charged to the quota that inserted it until it leaves the
+/// table, whether through [`Quota::remove`], [`PTab::remove`], a [linked]
+/// removal or expiry. Dropping the quota leaves its entries in the table.
+///
+/// # Examples
+///
+/// ```
+/// use ptab::PTab;
+///
+/// let table: PTab<&str> = PTab::new();
+/// let quota = table.quota(2);
+///
+/// let a = quota.insert("a").unwrap();
+/// let b = quota.insert("b").unwrap();
+///
+/// assert_eq!(quota.insert("c"), None);
+/// assert_eq!(quota.len(), 2);
+///
+/// table.remove(a);
+///
+/// assert_eq!(quota.len(), 1);
+/// assert!(quota.insert("c").is_some());
+/// assert!(quota.remove(b));
+/// ```
+///
+/// [`PTab::quota`]: crate::public::PTab::quota
+/// [`PTab::remove`]: crate::public::PTab::remove
+/// [`limit`]: Self::limit
+/// [linked]: crate::public::PTab::link
+pub struct Quota<'table, T, P = DefaultParams>
+where
+  P: Params + ?Sized,
+{
+  table: &'table PTab<T, P>,
+  usage: Arc<Usage>,
+}
+
+impl<'table, T, P> Quota<'table, T, P>
+where
+  P: Params + ?Sized,
+{
+  #[inline]
+  pub(crate) fn new(table: &'table PTab<T, P>, limit: usize) -> Self {
+    Self {
+      table,
+      usage: Arc::new(Usage::new(limit)),
+    }
+  }
+
+  /// Returns the table the quota draws from.
+  #[inline]
+  pub const fn table(&self) -> &'table PTab<T, P> {
+    self.table
+  }
+
+  /// Returns the maximum number of entries charged to the quota at once.
+  #[inline]
+  pub fn limit(&self) -> usize {
+    self.usage.limit
+  }
+
+  /// Returns the number of entries currently charged to the quota.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.usage.count.load(Relaxed)
+  }
+
+  /// Returns `true` if no entries are charged to the quota.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Inserts a value into the table, charging it to the quota.
+  ///
+  /// Returns [`None`] if the quota is exhausted or the table is at capacity.
+  #[inline]
+  pub fn insert(&self, value: T) -> Option<Detached>
+  where
+    T: 'static,
+  {
+    self.write(|entry, _| {
+      entry.write(value);
+    })
+  }
+
+  /// Inserts a value using an initialization function that receives its
+  /// index, charging it to the quota.
+  ///
+  /// See [`PTab::write`] for the requirements on `init`. Returns [`None`] if
+  /// the quota is exhausted or the table is at capacity.
+  ///
+  /// [`PTab::write`]: crate::public::PTab::write
+  pub fn write<F>(&self, init: F) -> Option<Detached>
+  where
+    T: 'static,
+    F: FnOnce(&mut MaybeUninit<T>, Detached),
+  {
+    if !self.usage.acquire() {
+      return None;
+    }
+
+    let Some(key) = self.table.write(init) else {
+      self.usage.release();
+      return None;
+    };
+
+    let watch: Watch = Watch::Quota(Arc::clone(&self.usage));
+
+    // The entry was removed before it could be charged; settle it here.
+    if let Err(watch) = self.table.watch(key, watch) {
+      watch.fire(Reason::NoEntry);
+    }
+
+    Some(key)
+  }
+
+  /// Removes the entry at the given index if it is charged to this quota.
+  ///
+  /// Returns `true` if the entry was removed. Entries inserted directly or
+  /// through another quota are left untouched.
+  #[inline]
+  pub fn remove(&self, index: impl Handle<T, P>) -> bool {
+    let index: Detached = index.detached();
+    self.contains(index) && self.table.remove(index)
+  }
+
+  /// Returns `true` if the entry at the given index is charged to this quota.
+  #[inline]
+  pub fn contains(&self, index: impl Handle<T, P>) -> bool {
+    self.table.watched(index.detached(), |watch| match watch {
+      Watch::Quota(usage) => Arc::ptr_eq(usage, &self.usage),
+      Watch::Monitor(_) | Watch::Link(_) => false,
+    })
+  }
+}
+
+impl<T, P> Debug for Quota<'_, T, P>
+where
+  P: Params + ?Sized,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_struct("Quota")
+      .field("limit", &self.limit())
+      .field("len", &self.len())
+      .finish_non_exhaustive()
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Usage
+// -----------------------------------------------------------------------------
+
+/// The entry counter shared by a quota and the entries charged to it.
+pub(crate) struct Usage {
+  limit: usize,
+  count: AtomicUsize,
+}
+
+impl Usage {
+  #[inline]
+  const fn new(limit: usize) -> Self {
+    Self {
+      limit,
+      count: AtomicUsize::new(0),
+    }
+  }
+
+  /// Charges one entry, returning `false` if the limit is reached.
+  #[inline]
+  fn acquire(&self) -> bool {
+    self
+      .count
+      .fetch_update(Acquire, Relaxed, |count| {
+        (count < self.limit).then_some(count + 1)
+      })
+      .is_ok()
+  }
+
+  /// Releases one entry.
+  #[inline]
+  pub(crate) fn release(&self) {
+    self.count.fetch_sub(1, Release);
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(not(any(loom, shuttle)))]
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+  use std::thread;
+
+  use crate::index::Detached;
+  use crate::params::Capacity;
+  use crate::params::ConstParams;
+  use crate::public::PTab;
+  use crate::quota::Quota;
+
+  type MinParams = ConstParams<{ Capacity::MIN.as_usize() }>;
+
+  const THREADS: usize = 8;
+
+  #[test]
+  fn limit() {
+    let table: PTab<usize> = PTab::new();
+    let quota: Quota<'_, usize> = table.quota(4);
+
+    let keys: Vec<Detached> = (0..4).map(|value| quota.insert(value).unwrap()).collect();
+
+    assert_eq!(quota.len(), 4);
+    assert_eq!(quota.insert(4), None);
+    assert_eq!(table.len(), 4);
+
+    for key in keys {
+      assert!(quota.remove(key));
+      assert!(!quota.remove(key));
+    }
+
+    assert!(quota.is_empty());
+    assert!(table.is_empty());
+  }
+
+  #[test]
+  fn table_full() {
+    let table: PTab<usize, MinParams> = PTab::new();
+    let quota: Quota<'_, usize, MinParams> = table.quota(usize::MAX);
+
+    for value in 0..table.capacity() {
+      assert!(quota.insert(value).is_some());
+    }
+
+    assert_eq!(quota.insert(0), None);
+    assert_eq!(quota.len(), table.capacity());
+  }
+
+  #[test]
+  fn independent() {
+    let table: PTab<usize> = PTab::new();
+    let a: Quota<'_, usize> = table.quota(1);
+    let b: Quota<'_, usize> = table.quota(1);
+
+    let key_a: Detached = a.insert(1).unwrap();
+    let key_b: Detached = b.insert(2).unwrap();
+    let key_c: Detached = table.insert(3).unwrap();
+
+    assert_eq!(a.insert(4), None);
+    assert_eq!(b.insert(4), None);
+
+    assert!(a.contains(key_a));
+    assert!(!a.contains(key_b));
+    assert!(!a.contains(key_c));
+
+    assert!(!a.remove(key_b));
+    assert!(!a.remove(key_c));
+    assert_eq!(table.len(), 3);
+
+    assert!(b.remove(key_b));
+    assert_eq!(a.len(), 1);
+    assert!(b.is_empty());
+  }
+
+  #[test]
+  fn removed_elsewhere() {
+    let table: PTab<usize> = PTab::new();
+    let quota: Quota<'_, usize> = table.quota(2);
+
+    let a: Detached = quota.insert(1).unwrap();
+    let b: Detached = quota.insert(2).unwrap();
+
+    assert!(table.link(a, b));
+    assert!(table.remove(a));
+
+    // The linked entry is released as well.
+    assert!(quota.is_empty());
+    assert!(!quota.contains(b));
+  }
+
+  #[test]
+  fn outlives_quota() {
+    let table: PTab<usize> = PTab::new();
+    let key: Detached = table.quota(1).insert(1).unwrap();
+
+    assert!(table.remove(key));
+    assert!(table.is_empty());
+  }
+
+  #[test]
+  fn concurrent() {
+    let table: Arc<PTab<usize>> = Arc::new(PTab::new());
+
+    let threads: Vec<_> = (0..THREADS)
+      .map(|thread| {
+        let table: Arc<PTab<usize>> = Arc::clone(&table);
+
+        thread::spawn(move || {
+          let quota: Quota<'_, usize> = table.quota(16);
+
+          for value in 0..1000 {
+            let keys: Vec<Detached> = (0..16)
+              .map(|_| quota.insert(thread * value).unwrap())
+              .collect();
+
+            assert_eq!(quota.insert(0), None);
+
+            for key in keys {
+              assert!(quota.remove(key));
+            }
+          }
+
+          assert!(quota.is_empty());
+        })
+      })
+      .collect();
+
+    for thread in threads {
+      thread.join().unwrap();
+    }
+
+    assert!(table.is_empty());
+  }
+}
@@ -0,0 +1,42 @@
+use crate::index::Detached;
+
+/// The reason an entry left a table, as reported to monitors and hooks.
+///
+/// # Examples
+///
+/// ```
+/// use ptab::{PTab, Reason};
+///
+/// let table: PTab<u64> = PTab::new();
+/// let index = table.insert(1).unwrap();
+/// let monitor = table.monitor(index);
+///
+/// table.remove(index);
+///
+/// assert_eq!(monitor.reason(), Some(Reason::Normal));
+/// ```
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Reason {
+  /// The entry was removed with [`PTab::remove`].
+  ///
+  /// [`PTab::remove`]: crate::public::PTab::remove
+  Normal,
+  /// The entry did not exist, or its index was stale, when it was monitored.
+  NoEntry,
+  /// The table was dropped while the entry was still present.
+  Shutdown,
+  /// The entry's time-to-live elapsed and it was removed by a sweep.
+  ///
+  /// See [`PTab::insert_with_ttl`].
+  ///
+  /// [`PTab::insert_with_ttl`]: crate::public::PTab::insert_with_ttl
+  Expired,
+  /// The entry was removed because the entry it was linked to, at the given
+  /// index, was removed.
+  ///
+  /// See [`PTab::link`].
+  ///
+  /// [`PTab::link`]: crate::public::PTab::link
+  Linked(Detached),
+}
@@ -0,0 +1,617 @@
+#![allow(dead_code)]
+
+use alloc::boxed::Box;
+use core::marker::PhantomData;
+use core::mem;
+use core::mem::ManuallyDrop;
+use core::mem::MaybeUninit;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::ptr;
+use core::ptr::NonNull;
+use core::sync::atomic::AtomicBool;
+use core::sync::atomic::AtomicPtr;
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering;
+use core::sync::atomic::fence;
+
+#[allow(dead_code, reason = "not used by loom/shuttle tests")]
+#[cfg(test)]
+pub(crate) fn try_reclaim() {
+  // Garbage is reclaimed two epochs after it is retired; guards of other
+  // threads may hold the epoch back for a moment.
+  let mut advanced: usize = 0;
+
+  for _ in 0..1_000_000 {
+    if advanced == 2 {
+      return;
+    }
+
+    if try_advance() {
+      advanced += 1;
+    } else {
+      std::thread::yield_now();
+    }
+  }
+}
+
+/// The low pointer bits available for tags.
+const TAG_MASK: usize = 0b11;
+
+// -----------------------------------------------------------------------------
+// Global Epoch
+// -----------------------------------------------------------------------------
+
+// Without thread-locals, guards register in one of two global counters picked
+// by the parity of the epoch they were created in. The epoch advances once no
+// guard of the previous epoch remains, so every live guard was created in the
+// current or the previous epoch, and garbage retired in epoch `e` can be
+// reclaimed when the epoch advances from `e + 1` to `e + 2`.
+//
+// Epochs count modulo `EPOCHS`, a multiple of both the number of counters and
+// the number of garbage bags.
+
+const EPOCHS: usize = 6;
+
+/// The current epoch.
+static EPOCH: AtomicUsize = AtomicUsize::new(0);
+
+/// The number of live guards, by the parity of the epoch they were created in.
+static GUARDS: [AtomicUsize; 2] = [const { AtomicUsize::new(0) }; 2];
+
+/// Retired garbage, by the epoch it was retired in modulo 3.
+static BAGS: [AtomicPtr<Node>; 3] = [const { AtomicPtr::new(ptr::null_mut()) }; 3];
+
+/// Held by the thread advancing the epoch.
+static ADVANCING: AtomicBool = AtomicBool::new(false);
+
+/// A deferred function in a garbage bag.
+struct Node {
+  next: *mut Node,
+  call: unsafe fn(*mut Node),
+}
+
+#[repr(C)]
+struct Deferred<F> {
+  node: Node,
+  f: F,
+}
+
+/// Runs `f` once every guard alive at the time of the call is dropped.
+fn defer<F>(f: F)
+where
+  F: FnOnce(),
+{
+  unsafe fn call<F>(node: *mut Node)
+  where
+    F: FnOnce(),
+  {
+    // SAFETY: `node` is the first field of a `Deferred<F>` from `Box::into_raw`.
+    let deferred: Box<Deferred<F>> = unsafe { Box::from_raw(node.cast::<Deferred<F>>()) };
+    (deferred.f)();
+  }
+
+  let node: *mut Node = Box::into_raw(Box::new(Deferred {
+    node: Node {
+      next: ptr::null_mut(),
+      call: call::<F>,
+    },
+    f,
+  }))
+  .cast::<Node>();
+
+  // Pairs with the fences in `Guard::new` and `try_advance`: a guard that
+  // still observes the garbage was counted before this epoch was read.
+  fence(Ordering::SeqCst);
+
+  let bag: &AtomicPtr<Node> = &BAGS[EPOCH.load(Ordering::SeqCst) % 3];
+  let mut head: *mut Node = bag.load(Ordering::Relaxed);
+
+  loop {
+    // SAFETY: `node` was never published, so we have exclusive access.
+    unsafe {
+      (*node).next = head;
+    }
+
+    match bag.compare_exchange_weak(head, node, Ordering::Release, Ordering::Relaxed) {
+      Ok(_) => break,
+      Err(next) => head = next,
+    }
+  }
+
+  try_advance();
+}
+
+/// Drops the boxed value at `pointer` once no guard can observe it anymore.
+///
+/// # Safety
+///
+/// `pointer` must come from `Box::into_raw`, must no longer be reachable from
+/// shared memory, and dropping the value later must not access data that may
+/// no longer be alive, as for values inserted with `T: 'static`.
+unsafe fn retire<T>(pointer: *mut T) {
+  unsafe fn drop_box<T>(pointer: *mut ()) {
+    // SAFETY: Guaranteed by the caller of `retire`.
+    drop(unsafe { Box::from_raw(pointer.cast::<T>()) });
+  }
+
+  // Erases `T`, so the deferred function does not borrow its lifetime.
+  let pointer: *mut () = pointer.cast::<()>();
+  let drop_box: unsafe fn(*mut ()) = drop_box::<T>;
+
+  // SAFETY: Guaranteed by the caller.
+  defer(move || unsafe { drop_box(pointer) });
+}
+
+/// Advances the epoch if no guard of the previous epoch is alive, reclaiming
+/// the garbage that can no longer be observed.
+///
+/// Returns `false` if the epoch could not be advanced.
+fn try_advance() -> bool {
+  if ADVANCING.swap(true, Ordering::Acquire) {
+    return false;
+  }
+
+  fence(Ordering::SeqCst);
+
+  let epoch: usize = EPOCH.load(Ordering::Relaxed);
+  let mut garbage: *mut Node = ptr::null_mut();
+  let advanced: bool = GUARDS[(epoch + 1) % 2].load(Ordering::SeqCst) == 0;
+
+  if advanced {
+    EPOCH.store((epoch + 1) % EPOCHS, Ordering::SeqCst);
+
+    // Every live guard was created in the new epoch or the one before, after
+    // the garbage retired two epochs ago was unlinked.
+    garbage = BAGS[(epoch + 2) % 3].swap(ptr::null_mut(), Ordering::Acquire);
+  }
+
+  ADVANCING.store(false, Ordering::Release);
+
+  while !garbage.is_null() {
+    // SAFETY: The bag was taken, so each node is run exactly once.
+    unsafe {
+      let next: *mut Node = (*garbage).next;
+      ((*garbage).call)(garbage);
+      garbage = next;
+    }
+  }
+
+  advanced
+}
+
+/// Returns `true` if any garbage waits to be reclaimed.
+#[inline]
+fn has_garbage() -> bool {
+  BAGS
+    .iter()
+    .any(|bag| !bag.load(Ordering::Relaxed).is_null())
+}
+
+// -----------------------------------------------------------------------------
+// Atomic Ptr
+// -----------------------------------------------------------------------------
+
+/// An atomic pointer that can be safely shared between threads.
+#[repr(transparent)]
+pub(crate) struct Atomic<T> {
+  inner: AtomicPtr<T>,
+}
+
+impl<T> Atomic<T> {
+  /// Creates a null atomic pointer.
+  #[inline]
+  pub(crate) const fn null() -> Self {
+    Self {
+      inner: AtomicPtr::new(ptr::null_mut()),
+    }
+  }
+
+  /// Loads a value from the pointer.
+  #[inline]
+  pub(crate) fn load<'guard>(&self, order: Ordering, _guard: &'guard Guard) -> Shared<'guard, T> {
+    Shared {
+      pointer: self.inner.load(order),
+      phantom: PhantomData,
+    }
+  }
+
+  /// Initializes and stores a value into the pointer.
+  #[inline]
+  pub(crate) fn write<F>(&self, order: Ordering, init: F)
+  where
+    F: FnOnce(&mut MaybeUninit<T>),
+    T: 'static,
+  {
+    let mut uninit: Box<MaybeUninit<T>> = Box::new_uninit();
+
+    init(&mut uninit);
+
+    // SAFETY:
+    // - The `init` closure is required to fully initialize `uninit`.
+    // - After `init` returns, the value is assumed to be initialized.
+    self
+      .inner
+      .store(Box::into_raw(unsafe { uninit.assume_init() }), order);
+  }
+
+  /// Evicts the value if it satisfies `predicate`, returning ownership of it.
+  #[inline]
+  pub(crate) fn evict_if<P>(&self, order: Ordering, predicate: P) -> Option<Evicted<T>>
+  where
+    P: Fn(&T) -> bool,
+  {
+    let _guard: Guard = Guard::new();
+    let mut current: *mut T = self.inner.load(Ordering::Acquire);
+
+    loop {
+      // SAFETY: Evicted values are not reclaimed while `_guard` is alive.
+      match unsafe { current.map_addr(|addr| addr & !TAG_MASK).as_ref() } {
+        Some(value) if predicate(value) => {}
+        Some(_) | None => return None,
+      }
+
+      match self
+        .inner
+        .compare_exchange(current, ptr::null_mut(), order, Ordering::Acquire)
+      {
+        Ok(_) => {
+          // SAFETY: `current` is non-null, as checked above.
+          let pointer: NonNull<T> =
+            unsafe { NonNull::new_unchecked(current.map_addr(|addr| addr & !TAG_MASK)) };
+
+          return Some(Evicted { pointer });
+        }
+        Err(actual) => current = actual,
+      }
+    }
+  }
+
+  /// Stores `value` into the pointer if the current value satisfies
+  /// `predicate`, retiring the previous value.
+  ///
+  /// Otherwise `value` is passed to `reject` and dropped, and the result of
+  /// `reject` is returned.
+  #[inline]
+  pub(crate) fn replace_if<'guard, P, F, R>(
+    &self,
+    value: T,
+    order: Ordering,
+    _guard: &'guard Guard,
+    predicate: P,
+    reject: F,
+  ) -> Result<Shared<'guard, T>, R>
+  where
+    T: 'static,
+    P: Fn(Option<&T>) -> bool,
+    F: FnOnce(&mut T) -> R,
+  {
+    let value: *mut T = Box::into_raw(Box::new(value));
+    let mut current: *mut T = self.inner.load(Ordering::Acquire);
+
+    loop {
+      let shared: Shared<'guard, T> = Shared {
+        pointer: current,
+        phantom: PhantomData,
+      };
+
+      if !predicate(shared.as_ref()) {
+        // SAFETY: `value` came from `Box::into_raw` and was never published.
+        let mut value: Box<T> = unsafe { Box::from_raw(value) };
+        return Err(reject(&mut value));
+      }
+
+      match self
+        .inner
+        .compare_exchange(current, value, order, Ordering::Acquire)
+      {
+        Ok(_) => {
+          let old: *mut T = current.map_addr(|addr| addr & !TAG_MASK);
+
+          if !old.is_null() {
+            // SAFETY: `old` was unlinked above and is not reclaimed before
+            //         `_guard` is dropped, so `shared` stays valid.
+            unsafe {
+              retire(old);
+            }
+          }
+
+          return Ok(shared);
+        }
+        Err(actual) => current = actual,
+      }
+    }
+  }
+
+  /// Sets the tag bits of the pointer, provided it still equals `current`.
+  #[inline]
+  pub(crate) fn update_tag(&self, current: &Shared<'_, T>, tag: u8, order: Ordering) -> bool {
+    let pointer: *mut T = current
+      .pointer
+      .map_addr(|addr| (addr & !TAG_MASK) | (usize::from(tag) & TAG_MASK));
+
+    self
+      .inner
+      .compare_exchange(current.pointer, pointer, order, Ordering::Acquire)
+      .is_ok()
+  }
+
+  /// Removes the value, passing it to `f` before dropping it in place.
+  ///
+  /// # Safety
+  ///
+  /// No [`Shared`] pointer to the value may be alive.
+  #[inline]
+  pub(crate) unsafe fn take_with<F, R>(&mut self, f: F) -> Option<R>
+  where
+    F: FnOnce(&mut T) -> R,
+  {
+    let pointer: *mut T = mem::replace(self.inner.get_mut(), ptr::null_mut());
+    let pointer: NonNull<T> = NonNull::new(pointer.map_addr(|addr| addr & !TAG_MASK))?;
+
+    // SAFETY:
+    // - `pointer` was previously created by `Box::into_raw`.
+    // - The caller guarantees that no other pointer to the value is alive,
+    //   and the slot was cleared above, so ownership is transferred once.
+    let mut value: Box<T> = unsafe { Box::from_raw(pointer.as_ptr()) };
+
+    Some(f(&mut value))
+  }
+
+  #[inline]
+  pub(crate) unsafe fn drop_in_place(&mut self) -> bool {
+    if let Some(ptr) = NonNull::new(self.inner.get_mut().map_addr(|addr| addr & !TAG_MASK)) {
+      // SAFETY:
+      // - `ptr` was previously created by `Box::into_raw`, so it originated
+      //   from a valid `Box<T>` allocation.
+      // - We have exclusive access via `&mut self`, so no concurrent access to
+      //   the pointer can occur.
+      // - Reconstructing the `Box<T>` transfers ownership back and will drop
+      //   the value and free the allocation exactly once.
+      drop(unsafe { Box::from_raw(ptr.as_ptr()) });
+      true
+    } else {
+      false
+    }
+  }
+
+  /// Creates a pointer that links `value`, or a null pointer.
+  #[inline]
+  pub(crate) fn from_owned(value: Option<Owned<T>>) -> Self
+  where
+    T: 'static,
+  {
+    match value {
+      Some(value) => Self {
+        inner: AtomicPtr::new(Box::into_raw(value.inner)),
+      },
+      None => Self::null(),
+    }
+  }
+
+  /// Takes back ownership of the value, discarding the tag bits.
+  #[inline]
+  pub(crate) fn into_owned(self) -> Option<Owned<T>> {
+    let pointer: NonNull<T> =
+      NonNull::new(self.inner.into_inner().map_addr(|addr| addr & !TAG_MASK))?;
+
+    // SAFETY: `pointer` was created by `Box::into_raw`, and `self` is consumed,
+    //         so ownership is transferred once.
+    Some(Owned {
+      inner: unsafe { Box::from_raw(pointer.as_ptr()) },
+    })
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Evicted Ptr
+// -----------------------------------------------------------------------------
+
+/// An owned value evicted from an [`Atomic`], which readers may still observe.
+///
+/// Dropping it retires the value, so it is dropped once no reader can observe
+/// it anymore.
+pub(crate) struct Evicted<T> {
+  pointer: NonNull<T>,
+}
+
+impl<T> Evicted<T> {
+  /// Retires the value, passing it to `f` right before it is dropped.
+  #[inline]
+  pub(crate) fn defer<F>(self, f: F)
+  where
+    T: 'static,
+    F: FnOnce(&mut T) + 'static,
+  {
+    let pointer: NonNull<T> = ManuallyDrop::new(self).pointer;
+
+    defer(move || {
+      // SAFETY:
+      // - The closure runs once every guard that could observe the value is
+      //   dropped, so no other pointer to it is alive.
+      // - The value is dropped exactly once, after `f` returns.
+      let mut value: Box<T> = unsafe { Box::from_raw(pointer.as_ptr()) };
+      f(&mut value);
+    });
+  }
+}
+
+impl<T> Deref for Evicted<T> {
+  type Target = T;
+
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    // SAFETY: The value is retired only once `self` is dropped.
+    unsafe { self.pointer.as_ref() }
+  }
+}
+
+impl<T> Drop for Evicted<T> {
+  #[inline]
+  fn drop(&mut self) {
+    // SAFETY: The value was unlinked by `Atomic::evict_if`, and values are
+    //         only published by `Atomic::write` and `Atomic::from_owned`,
+    //         which require `T: 'static`.
+    unsafe {
+      retire(self.pointer.as_ptr());
+    }
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Owned Ptr
+// -----------------------------------------------------------------------------
+
+/// A uniquely owned value, allocated like the values linked by an [`Atomic`].
+///
+/// Unlike an [`Evicted`] value, it was never observable by other threads, so
+/// it is dropped right away.
+#[repr(transparent)]
+pub(crate) struct Owned<T> {
+  inner: Box<T>,
+}
+
+impl<T> Owned<T> {
+  /// Allocates a value, initialized in place by `init`.
+  #[inline]
+  pub(crate) fn new_with<F>(init: F) -> Self
+  where
+    F: FnOnce(&mut MaybeUninit<T>),
+  {
+    let mut uninit: Box<MaybeUninit<T>> = Box::new_uninit();
+
+    init(&mut uninit);
+
+    // SAFETY:
+    // - The `init` closure is required to fully initialize `uninit`.
+    // - After `init` returns, the value is assumed to be initialized.
+    Self {
+      inner: unsafe { uninit.assume_init() },
+    }
+  }
+}
+
+impl<T> Deref for Owned<T> {
+  type Target = T;
+
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    &self.inner
+  }
+}
+
+impl<T> DerefMut for Owned<T> {
+  #[inline]
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.inner
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Shared Ptr
+// -----------------------------------------------------------------------------
+
+/// A pointer to an unprotected object.
+#[repr(transparent)]
+pub(crate) struct Shared<'guard, T> {
+  pointer: *mut T,
+  phantom: PhantomData<&'guard T>,
+}
+
+impl<'guard, T> Shared<'guard, T> {
+  /// Creates a null pointer.
+  #[inline]
+  pub(crate) const fn null() -> Self {
+    Self {
+      pointer: ptr::null_mut(),
+      phantom: PhantomData,
+    }
+  }
+
+  /// Returns `true` if the pointer is null.
+  #[inline]
+  pub(crate) const fn is_null(&self) -> bool {
+    self.pointer.is_null()
+  }
+
+  /// Returns the tag bits of the pointer.
+  #[inline]
+  pub(crate) fn tag(&self) -> u8 {
+    (self.pointer.addr() & TAG_MASK) as u8
+  }
+
+  /// Returns a shared reference to the value.
+  #[inline]
+  pub(crate) fn as_ref(&self) -> Option<&'guard T> {
+    // SAFETY:
+    // - `self.pointer` is either null or points to a fully initialized `T`
+    //   written via `Atomic::write`.
+    // - The pointer originates from `Box::into_raw`, so it is valid and
+    //   properly aligned for `T`.
+    // - Only shared references to `T` are created, so aliasing rules are not
+    //   violated.
+    // - The tag bits are stripped before dereferencing.
+    unsafe { self.pointer.map_addr(|addr| addr & !TAG_MASK).as_ref() }
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Guard
+// -----------------------------------------------------------------------------
+
+/// A guard that keeps the values it may observe from being reclaimed.
+///
+/// Registers with the global epoch, so removed values are dropped once every
+/// guard that could still observe them is dropped.
+pub struct Guard {
+  parity: usize,
+}
+
+impl Guard {
+  /// Creates a new [`Guard`].
+  #[inline]
+  pub fn new() -> Self {
+    loop {
+      let epoch: usize = EPOCH.load(Ordering::SeqCst);
+      let parity: usize = epoch % 2;
+
+      GUARDS[parity].fetch_add(1, Ordering::SeqCst);
+      fence(Ordering::SeqCst);
+
+      // The epoch may have advanced past a stale read, in which case the
+      // counter no longer holds it back.
+      if EPOCH.load(Ordering::SeqCst) == epoch {
+        return Self { parity };
+      }
+
+      GUARDS[parity].fetch_sub(1, Ordering::Release);
+    }
+  }
+
+  /// Runs `f` once every guard alive at the time of the call is dropped.
+  #[inline]
+  pub(crate) fn defer<F>(&self, f: F)
+  where
+    F: FnOnce() + 'static,
+  {
+    defer(f);
+  }
+}
+
+impl Default for Guard {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Drop for Guard {
+  #[inline]
+  fn drop(&mut self) {
+    GUARDS[self.parity].fetch_sub(1, Ordering::Release);
+
+    if has_garbage() {
+      try_advance();
+    }
+  }
+}
@@ -1,12 +1,44 @@
-pub(crate) mod leak;
+pub(crate) mod epoch;
+#[cfg(feature = "std")]
 pub(crate) mod sdd;
 
+// Without `std`, there are no thread-locals to track epochs per thread, so a
+// single global epoch is shared by all threads instead.
+#[cfg(not(feature = "std"))]
+pub(crate) use self::epoch::Atomic;
+#[cfg(not(feature = "std"))]
+pub(crate) use self::epoch::Evicted;
+#[cfg(not(feature = "std"))]
+pub use self::epoch::Guard;
+#[cfg(not(feature = "std"))]
+pub(crate) use self::epoch::Owned;
+#[cfg(not(feature = "std"))]
+pub(crate) use self::epoch::Shared;
+#[allow(unused_imports, reason = "not used by loom/shuttle tests")]
+#[cfg(all(test, not(feature = "std")))]
+pub(crate) use self::epoch::try_reclaim;
+#[cfg(feature = "std")]
+pub(crate) use self::sdd::Atomic;
+#[cfg(feature = "std")]
+pub(crate) use self::sdd::Evicted;
+#[cfg(feature = "std")]
+pub use self::sdd::Guard;
+#[cfg(feature = "std")]
+pub(crate) use self::sdd::Owned;
+#[cfg(feature = "std")]
+pub(crate) use self::sdd::Shared;
+#[allow(unused_imports, reason = "not used by loom/shuttle tests")]
+#[cfg(all(test, feature = "std"))]
+pub(crate) use self::sdd::try_reclaim;
+
 // -----------------------------------------------------------------------------
 // Sanity Check
 // -----------------------------------------------------------------------------
 
-const _: () = assert!(align_of::<leak::Atomic<()>>() == align_of::<usize>());
-const _: () = assert!(size_of::<leak::Atomic<()>>() == size_of::<usize>());
+const _: () = assert!(align_of::<epoch::Atomic<()>>() == align_of::<usize>());
+const _: () = assert!(size_of::<epoch::Atomic<()>>() == size_of::<usize>());
 
+#[cfg(feature = "std")]
 const _: () = assert!(align_of::<sdd::Atomic<()>>() == align_of::<usize>());
+#[cfg(feature = "std")]
 const _: () = assert!(size_of::<sdd::Atomic<()>>() == size_of::<usize>());
@@ -1,6 +1,9 @@
 use core::hint;
 use core::mem;
+use core::mem::ManuallyDrop;
 use core::mem::MaybeUninit;
+use core::ops::Deref;
+use core::ops::DerefMut;
 use core::sync::atomic::Ordering;
 
 #[allow(dead_code, reason = "not used by loom/shuttle tests")]
@@ -75,9 +78,129 @@ impl<T> Atomic<T> {
     }
   }
 
+  /// Evicts the value if it satisfies `predicate`, returning ownership of it.
   #[inline]
-  pub(crate) fn evict(&self, order: Ordering) -> bool {
-    self.inner.swap((None, sdd::Tag::None), order).0.is_some()
+  pub(crate) fn evict_if<P>(&self, order: Ordering, predicate: P) -> Option<Evicted<T>>
+  where
+    P: Fn(&T) -> bool,
+  {
+    let guard: sdd::Guard = sdd::Guard::new();
+    let mut current: sdd::Ptr<'_, T> = self.inner.load(Ordering::Acquire, &guard);
+
+    loop {
+      match current.as_ref() {
+        Some(value) if predicate(value) => {}
+        Some(_) | None => return None,
+      }
+
+      match self.inner.compare_exchange(
+        current,
+        (None, sdd::Tag::None),
+        order,
+        Ordering::Acquire,
+        &guard,
+      ) {
+        Ok((Some(inner), _)) => return Some(Evicted { inner }),
+        Ok((None, _)) => return None,
+        Err((_, actual)) => current = actual,
+      }
+    }
+  }
+
+  /// Stores `value` into the pointer if the current value satisfies
+  /// `predicate`, retiring the previous value.
+  ///
+  /// Otherwise `value` is passed to `reject` and dropped, and the result of
+  /// `reject` is returned.
+  #[inline]
+  pub(crate) fn replace_if<'guard, P, F, R>(
+    &self,
+    value: T,
+    order: Ordering,
+    guard: &'guard Guard,
+    predicate: P,
+    reject: F,
+  ) -> Result<Shared<'guard, T>, R>
+  where
+    T: 'static,
+    P: Fn(Option<&T>) -> bool,
+    F: FnOnce(&mut T) -> R,
+  {
+    let mut value: sdd::Owned<T> = sdd::Owned::new(value);
+    let mut current: sdd::Ptr<'_, T> = self.inner.load(Ordering::Acquire, &guard.inner);
+
+    loop {
+      if !predicate(current.as_ref()) {
+        // SAFETY: `value` was never published, so no other pointer to it is
+        //         alive, and it is dropped exactly once.
+        unsafe {
+          let output: R = reject(value.get_mut());
+          value.drop_in_place();
+          return Err(output);
+        }
+      }
+
+      match self.inner.compare_exchange(
+        current,
+        (Some(value), sdd::Tag::None),
+        order,
+        Ordering::Acquire,
+        &guard.inner,
+      ) {
+        // Dropping `old` retires it, so it is not reclaimed before `guard` is.
+        Ok((old, _)) => {
+          return Ok(Shared {
+            inner: old.map_or_else(sdd::Ptr::null, |old| old.get_guarded_ptr(&guard.inner)),
+          });
+        }
+        Err((Some(next), actual)) => {
+          value = next;
+          current = actual;
+        }
+        Err((None, _)) => unreachable!("a failed exchange returns the new value"),
+      }
+    }
+  }
+
+  /// Sets the tag bits of the pointer, provided it still equals `current`.
+  ///
+  /// Returns `false` if the pointer or its tag changed since `current` was
+  /// loaded.
+  #[inline]
+  pub(crate) fn update_tag(&self, current: &Shared<'_, T>, tag: u8, order: Ordering) -> bool {
+    let expected: sdd::Tag = current.inner.tag();
+    let pointer: *const T = current.inner.as_ptr();
+
+    self.inner.update_tag_if(
+      into_tag(tag),
+      |actual| actual.tag() == expected && actual.as_ptr() == pointer,
+      order,
+      Ordering::Acquire,
+    )
+  }
+
+  /// Removes the value, passing it to `f` before dropping it in place.
+  ///
+  /// # Safety
+  ///
+  /// No [`Shared`] pointer to the value may be alive.
+  #[inline]
+  pub(crate) unsafe fn take_with<F, R>(&mut self, f: F) -> Option<R>
+  where
+    F: FnOnce(&mut T) -> R,
+  {
+    let entry: sdd::AtomicOwned<T> = mem::take(&mut self.inner);
+    let mut value: sdd::Owned<T> = entry.into_owned(Ordering::Relaxed)?;
+
+    // SAFETY:
+    // - `entry.into_owned` transfers exclusive ownership of the value.
+    // - The caller guarantees that no other pointer to the value is alive.
+    // - The value is dropped exactly once, after `f` returns.
+    unsafe {
+      let output: R = f(value.get_mut());
+      value.drop_in_place();
+      Some(output)
+    }
   }
 
   #[inline]
@@ -99,6 +222,151 @@ impl<T> Atomic<T> {
       false
     }
   }
+
+  /// Creates a pointer that links `value`, or a null pointer.
+  #[inline]
+  pub(crate) fn from_owned(value: Option<Owned<T>>) -> Self
+  where
+    T: 'static,
+  {
+    match value {
+      Some(value) => Self {
+        inner: sdd::AtomicOwned::from(value.into_inner()),
+      },
+      None => Self::null(),
+    }
+  }
+
+  /// Takes back ownership of the value, discarding the tag bits.
+  #[inline]
+  pub(crate) fn into_owned(self) -> Option<Owned<T>> {
+    self.inner.into_owned(Ordering::Relaxed).map(|inner| Owned {
+      inner: ManuallyDrop::new(inner),
+    })
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Evicted Ptr
+// -----------------------------------------------------------------------------
+
+/// An owned value evicted from an [`Atomic`], which readers may still observe.
+///
+/// Dropping it retires the value, so it is dropped once no reader can observe
+/// it anymore.
+pub(crate) struct Evicted<T> {
+  inner: sdd::Owned<T>,
+}
+
+impl<T> Evicted<T> {
+  /// Retires the value, passing it to `f` right before it is dropped.
+  #[inline]
+  pub(crate) fn defer<F>(self, f: F)
+  where
+    T: 'static,
+    F: FnOnce(&mut T) + 'static,
+  {
+    let mut inner: sdd::Owned<T> = self.inner;
+
+    Guard::new().defer(move || {
+      // SAFETY:
+      // - The closure runs once every guard that could observe the value is
+      //   dropped, so no other pointer to it is alive.
+      // - The value is dropped exactly once, after `f` returns.
+      unsafe {
+        f(inner.get_mut());
+        inner.drop_in_place();
+      }
+    });
+  }
+}
+
+impl<T> Deref for Evicted<T> {
+  type Target = T;
+
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    &self.inner
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Owned Ptr
+// -----------------------------------------------------------------------------
+
+/// A uniquely owned value, allocated like the values linked by an [`Atomic`].
+///
+/// Unlike an [`Evicted`] value, it was never observable by other threads, so
+/// it is dropped right away instead of being passed to the collector.
+pub(crate) struct Owned<T> {
+  inner: ManuallyDrop<sdd::Owned<T>>,
+}
+
+impl<T> Owned<T> {
+  /// Allocates a value, initialized in place by `init`.
+  #[inline]
+  pub(crate) fn new_with<F>(init: F) -> Self
+  where
+    F: FnOnce(&mut MaybeUninit<T>),
+  {
+    // SAFETY: `Owned` drops the value in place rather than passing it to the
+    //         collector, so `T` does not have to outlive any guard. Values
+    //         are only linked by `Atomic::from_owned`, which requires
+    //         `T: 'static`.
+    let inner: sdd::Owned<T> = unsafe {
+      sdd::Owned::new_with_unchecked(|| {
+        let mut uninit: MaybeUninit<T> = MaybeUninit::uninit();
+
+        init(&mut uninit);
+
+        // SAFETY:
+        // - The `init` closure is required to fully initialize `uninit`.
+        // - After `init` returns, the value is assumed to be initialized.
+        uninit.assume_init()
+      })
+    };
+
+    Self {
+      inner: ManuallyDrop::new(inner),
+    }
+  }
+
+  #[inline]
+  fn into_inner(self) -> sdd::Owned<T> {
+    let mut this: ManuallyDrop<Self> = ManuallyDrop::new(self);
+
+    // SAFETY: `this` is never dropped, so the value is moved out once.
+    unsafe { ManuallyDrop::take(&mut this.inner) }
+  }
+}
+
+impl<T> Deref for Owned<T> {
+  type Target = T;
+
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    &self.inner
+  }
+}
+
+impl<T> DerefMut for Owned<T> {
+  #[inline]
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    // SAFETY: The value is uniquely owned, so no other pointer to it exists.
+    unsafe { self.inner.get_mut() }
+  }
+}
+
+impl<T> Drop for Owned<T> {
+  #[inline]
+  fn drop(&mut self) {
+    // SAFETY:
+    // - The value is uniquely owned, so no other pointer to it exists.
+    // - `self.inner` is never used again.
+    unsafe {
+      ManuallyDrop::take(&mut self.inner).drop_in_place();
+    }
+  }
 }
 
 // -----------------------------------------------------------------------------
@@ -114,24 +382,46 @@ pub(crate) struct Shared<'guard, T> {
 }
 
 impl<'guard, T> Shared<'guard, T> {
+  /// Creates a null pointer.
+  #[inline]
+  pub(crate) const fn null() -> Self {
+    Self {
+      inner: sdd::Ptr::null(),
+    }
+  }
+
   /// Returns `true` if the pointer is null.
   #[inline]
   pub(crate) fn is_null(&self) -> bool {
     self.inner.is_null()
   }
 
+  /// Returns the tag bits of the pointer.
+  #[inline]
+  pub(crate) fn tag(&self) -> u8 {
+    match self.inner.tag() {
+      sdd::Tag::None => 0b00,
+      sdd::Tag::First => 0b01,
+      sdd::Tag::Second => 0b10,
+      sdd::Tag::Both => 0b11,
+    }
+  }
+
   /// Returns a shared reference to the value.
   #[inline]
-  pub(crate) const fn as_ref(&self) -> Option<&'guard T> {
-    // SAFETY:
-    // - `self.pointer` is either null or points to a fully initialized `T`
-    //   written via `Atomic::write`.
-    // - The pointer originates from `Box::into_raw`, so it is valid and
-    //   properly aligned for `T`.
-    // - Only shared references to `T` are created, so aliasing rules are not
-    //   violated.
-    // - `self.pointer` does not have any tag bits set.
-    unsafe { self.inner.as_ref_unchecked() }
+  pub(crate) fn as_ref(&self) -> Option<&'guard T> {
+    // Strips the tag bits before dereferencing.
+    self.inner.as_ref()
+  }
+}
+
+#[inline]
+const fn into_tag(bits: u8) -> sdd::Tag {
+  match bits & 0b11 {
+    0b00 => sdd::Tag::None,
+    0b01 => sdd::Tag::First,
+    0b10 => sdd::Tag::Second,
+    _ => sdd::Tag::Both,
   }
 }
 
@@ -153,6 +443,15 @@ impl Guard {
       inner: sdd::Guard::new(),
     }
   }
+
+  /// Executes `f` once all guards alive at the time of the call are dropped.
+  #[inline]
+  pub(crate) fn defer<F>(&self, f: F)
+  where
+    F: FnOnce() + 'static,
+  {
+    self.inner.defer_execute(f);
+  }
 }
 
 impl Default for Guard {
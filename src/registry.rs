@@ -0,0 +1,542 @@
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+use crate::index::Detached;
+use crate::monitor::Monitor;
+use crate::params::DefaultParams;
+use crate::params::Params;
+use crate::public::PTab;
+use crate::utils::Mutex;
+use crate::utils::MutexGuard;
+
+/// A table with unique names bound to its entries.
+///
+/// Modeled on Erlang's process registry: [`register`] binds a name to a live
+/// entry, and [`whereis`] resolves it. A name is unregistered automatically
+/// when its entry leaves the table, whether through [`remove`], a [linked]
+/// removal, or the registry being dropped.
+///
+/// Names are kept in an ordered map behind a lock that is only held for the
+/// map operation itself; entry access through [`with_name`] happens outside
+/// of it.
+///
+/// # Examples
+///
+/// ```
+/// use ptab::Registry;
+///
+/// let registry: Registry<&str, u64> = Registry::new();
+/// let index = registry.table().insert(42).unwrap();
+///
+/// assert!(registry.register("answer", index));
+/// assert_eq!(registry.whereis("answer"), Some(index));
+/// assert_eq!(registry.with_name("answer", |&value| value), Some(42));
+///
+/// registry.remove(index);
+/// assert_eq!(registry.whereis("answer"), None);
+/// ```
+///
+/// [`register`]: Self::register
+/// [`whereis`]: Self::whereis
+/// [`with_name`]: Self::with_name
+/// [`remove`]: Self::remove
+/// [linked]: crate::public::PTab::link
+pub struct Registry<K, T, P = DefaultParams>
+where
+  P: Params + ?Sized,
+{
+  names: Arc<Names<K>>,
+  table: PTab<T, P>,
+}
+
+impl<K, T, P> Registry<K, T, P>
+where
+  K: Clone + Ord + Send + Sync + 'static,
+  P: Params + ?Sized,
+{
+  /// Creates a new, empty registry.
+  #[inline]
+  pub fn new() -> Self {
+    Self {
+      names: Arc::new(Names::new()),
+      table: PTab::new(),
+    }
+  }
+
+  /// Returns the underlying table.
+  ///
+  /// Entries removed directly through the table are unregistered as well.
+  #[inline]
+  pub const fn table(&self) -> &PTab<T, P> {
+    &self.table
+  }
+
+  /// Binds `name` to the entry at `index`.
+  ///
+  /// Returns `false` if `name` is already bound to a live entry, or if no
+  /// entry exists at `index`. An entry may be registered under several names.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::Registry;
+  ///
+  /// let registry: Registry<String, u64> = Registry::new();
+  /// let a = registry.table().insert(1).unwrap();
+  /// let b = registry.table().insert(2).unwrap();
+  ///
+  /// assert!(registry.register("main".to_string(), a));
+  /// assert!(!registry.register("main".to_string(), b));
+  /// ```
+  pub fn register(&self, name: K, index: Detached) -> bool {
+    if !self.names.bind(&self.table, name.clone(), index) {
+      return false;
+    }
+
+    let names: Arc<Names<K>> = Arc::clone(&self.names);
+    let key: K = name.clone();
+
+    // Fires immediately if the entry was removed after the name was bound.
+    let monitor: Monitor = self.table.monitor_with(index, move |_| {
+      names.unbind(&name, index);
+    });
+
+    self.names.keep(&key, monitor);
+
+    true
+  }
+
+  /// Removes the binding for `name`, returning the index it was bound to.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::Registry;
+  ///
+  /// let registry: Registry<&str, u64> = Registry::new();
+  /// let index = registry.table().insert(1).unwrap();
+  ///
+  /// registry.register("main", index);
+  ///
+  /// assert_eq!(registry.unregister("main"), Some(index));
+  /// assert_eq!(registry.unregister("main"), None);
+  /// assert!(registry.table().exists(index));
+  /// ```
+  pub fn unregister<Q>(&self, name: &Q) -> Option<Detached>
+  where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    let index: Detached = self.names.take(name)?;
+
+    self.table.exists(index).then_some(index)
+  }
+
+  /// Returns the index of the entry registered under `name`.
+  #[inline]
+  pub fn whereis<Q>(&self, name: &Q) -> Option<Detached>
+  where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    let index: Detached = self.names.get(name)?;
+
+    // The entry may have been removed before its name was unbound.
+    self.table.exists(index).then_some(index)
+  }
+
+  /// Accesses the entry registered under `name`.
+  ///
+  /// Returns [`None`] if `name` is not registered.
+  #[inline]
+  pub fn with_name<Q, F, R>(&self, name: &Q, f: F) -> Option<R>
+  where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+    F: Fn(&T) -> R,
+  {
+    let index: Detached = self.names.get(name)?;
+
+    self.table.with(index, f)
+  }
+
+  /// Removes the entry at `index`, unregistering all of its names.
+  ///
+  /// See [`PTab::remove`].
+  ///
+  /// [`PTab::remove`]: crate::public::PTab::remove
+  #[inline]
+  pub fn remove(&self, index: Detached) -> bool {
+    self.table.remove(index)
+  }
+
+  /// Returns the names currently bound to live entries.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::Registry;
+  ///
+  /// let registry: Registry<&str, u64> = Registry::new();
+  /// let index = registry.table().insert(1).unwrap();
+  ///
+  /// registry.register("main", index);
+  ///
+  /// assert_eq!(registry.registered(), [("main", index)]);
+  /// ```
+  pub fn registered(&self) -> Vec<(K, Detached)> {
+    self
+      .names
+      .lock()
+      .iter()
+      .filter(|(_, binding)| self.table.exists(binding.index))
+      .map(|(name, binding)| (name.clone(), binding.index))
+      .collect()
+  }
+}
+
+impl<K, T, P> Debug for Registry<K, T, P>
+where
+  K: Clone + Ord + Send + Sync + Debug + 'static,
+  P: Params + ?Sized,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_map().entries(self.registered()).finish()
+  }
+}
+
+impl<K, T, P> Default for Registry<K, T, P>
+where
+  K: Clone + Ord + Send + Sync + 'static,
+  P: Params + ?Sized,
+{
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Name Map
+// -----------------------------------------------------------------------------
+
+/// A map from names to indices.
+///
+/// Each binding keeps the monitor that unbinds it when its entry is removed,
+/// so unbinding a name cancels its monitor.
+struct Names<K> {
+  map: Mutex<BTreeMap<K, Binding>>,
+}
+
+/// The index a name is bound to.
+struct Binding {
+  index: Detached,
+  monitor: Option<Monitor>,
+}
+
+impl<K> Names<K>
+where
+  K: Ord,
+{
+  #[inline]
+  const fn new() -> Self {
+    Self {
+      map: Mutex::new(BTreeMap::new()),
+    }
+  }
+
+  #[inline]
+  fn get<Q>(&self, name: &Q) -> Option<Detached>
+  where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    self.lock().get(name).map(|binding| binding.index)
+  }
+
+  /// Binds `name` unless it is bound to a live entry or `index` is absent.
+  fn bind<T, P>(&self, table: &PTab<T, P>, name: K, index: Detached) -> bool
+  where
+    P: Params + ?Sized,
+  {
+    let mut map: MutexGuard<'_, BTreeMap<K, Binding>> = self.lock();
+
+    if map
+      .get(&name)
+      .is_some_and(|binding| table.exists(binding.index))
+      || !table.exists(index)
+    {
+      return false;
+    }
+
+    let binding: Binding = Binding {
+      index,
+      monitor: None,
+    };
+
+    if let Some(stale) = map.insert(name, binding) {
+      stale.cancel();
+    }
+
+    true
+  }
+
+  /// Keeps the monitor of `name`, or cancels it if `name` was unbound in the
+  /// meantime.
+  fn keep(&self, name: &K, monitor: Monitor) {
+    let mut map: MutexGuard<'_, BTreeMap<K, Binding>> = self.lock();
+
+    match map.get_mut(name) {
+      Some(binding) if binding.index == monitor.index() => {
+        if let Some(stale) = binding.monitor.replace(monitor) {
+          stale.demonitor();
+        }
+      }
+      Some(_) | None => {
+        monitor.demonitor();
+      }
+    }
+  }
+
+  /// Removes `name` if it is still bound to `index`.
+  fn unbind(&self, name: &K, index: Detached) {
+    let mut map: MutexGuard<'_, BTreeMap<K, Binding>> = self.lock();
+
+    if map.get(name).is_some_and(|binding| binding.index == index)
+      && let Some(binding) = map.remove(name)
+    {
+      binding.cancel();
+    }
+  }
+
+  /// Removes `name`, returning the index it was bound to.
+  fn take<Q>(&self, name: &Q) -> Option<Detached>
+  where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    let binding: Binding = self.lock().remove(name)?;
+    let index: Detached = binding.index;
+
+    binding.cancel();
+
+    Some(index)
+  }
+
+  #[inline]
+  fn lock(&self) -> MutexGuard<'_, BTreeMap<K, Binding>> {
+    self.map.lock()
+  }
+}
+
+impl Binding {
+  /// Cancels the monitor that would unbind this binding.
+  #[inline]
+  fn cancel(self) {
+    if let Some(monitor) = self.monitor {
+      monitor.demonitor();
+    }
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(not(any(loom, shuttle)))]
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+  use std::thread;
+  use std::thread::JoinHandle;
+
+  use crate::index::Detached;
+  use crate::params::Capacity;
+  use crate::params::ConstParams;
+  use crate::registry::Registry;
+  use crate::watch::MIN_COMPACT;
+
+  type DefParams = ConstParams<{ Capacity::DEF.as_usize() }>;
+
+  const THREADS: usize = 8;
+
+  #[test]
+  fn register() {
+    let registry: Registry<&str, usize, DefParams> = Registry::new();
+    let index: Detached = registry.table().insert(1).unwrap();
+
+    assert!(registry.register("a", index));
+    assert!(registry.register("b", index));
+
+    assert_eq!(registry.whereis("a"), Some(index));
+    assert_eq!(registry.whereis("b"), Some(index));
+    assert_eq!(registry.whereis("c"), None);
+  }
+
+  #[test]
+  fn register_taken() {
+    let registry: Registry<&str, usize, DefParams> = Registry::new();
+    let a: Detached = registry.table().insert(1).unwrap();
+    let b: Detached = registry.table().insert(2).unwrap();
+
+    assert!(registry.register("name", a));
+    assert!(!registry.register("name", b));
+    assert!(!registry.register("name", a));
+
+    assert_eq!(registry.whereis("name"), Some(a));
+  }
+
+  #[test]
+  fn register_nonexistent() {
+    let registry: Registry<&str, usize, DefParams> = Registry::new();
+    let index: Detached = registry.table().insert(1).unwrap();
+
+    assert!(registry.remove(index));
+    assert!(!registry.register("name", index));
+    assert_eq!(registry.whereis("name"), None);
+  }
+
+  #[test]
+  fn register_after_remove() {
+    let registry: Registry<&str, usize, DefParams> = Registry::new();
+    let a: Detached = registry.table().insert(1).unwrap();
+
+    assert!(registry.register("name", a));
+    assert!(registry.remove(a));
+
+    let b: Detached = registry.table().insert(2).unwrap();
+
+    assert!(registry.register("name", b));
+    assert_eq!(registry.whereis("name"), Some(b));
+  }
+
+  #[test]
+  fn unregister() {
+    let registry: Registry<&str, usize, DefParams> = Registry::new();
+    let index: Detached = registry.table().insert(1).unwrap();
+
+    assert!(registry.register("name", index));
+    assert_eq!(registry.unregister("name"), Some(index));
+    assert_eq!(registry.unregister("name"), None);
+    assert_eq!(registry.whereis("name"), None);
+
+    // A new binding is unaffected by the monitor of the old one.
+    assert!(registry.register("name", index));
+    assert_eq!(registry.whereis("name"), Some(index));
+  }
+
+  #[test]
+  fn unregister_cycles() {
+    let registry: Registry<&str, usize, DefParams> = Registry::new();
+    let index: Detached = registry.table().insert(1).unwrap();
+
+    for _ in 0..1000 {
+      assert!(registry.register("name", index));
+      assert_eq!(registry.unregister("name"), Some(index));
+    }
+
+    let mut watchers: usize = 0;
+
+    registry.table().watched(index, |_| {
+      watchers += 1;
+      false
+    });
+
+    // Cancelled monitors are unlinked once the list doubles in length.
+    assert!(watchers <= MIN_COMPACT);
+
+    assert!(registry.register("name", index));
+    assert!(registry.remove(index));
+    assert_eq!(registry.whereis("name"), None);
+  }
+
+  #[test]
+  fn remove_unregisters() {
+    let registry: Registry<String, usize, DefParams> = Registry::new();
+    let index: Detached = registry.table().insert(1).unwrap();
+
+    assert!(registry.register("name".to_string(), index));
+    assert!(registry.table().remove(index));
+
+    assert_eq!(registry.whereis("name"), None);
+    assert!(registry.registered().is_empty());
+  }
+
+  #[test]
+  fn linked_remove_unregisters() {
+    let registry: Registry<&str, usize, DefParams> = Registry::new();
+    let a: Detached = registry.table().insert(1).unwrap();
+    let b: Detached = registry.table().insert(2).unwrap();
+
+    assert!(registry.register("a", a));
+    assert!(registry.register("b", b));
+    assert!(registry.table().link(a, b));
+    assert!(registry.remove(a));
+
+    assert_eq!(registry.whereis("b"), None);
+    assert!(registry.registered().is_empty());
+  }
+
+  #[test]
+  fn with_name() {
+    let registry: Registry<&str, usize, DefParams> = Registry::new();
+    let index: Detached = registry.table().insert(123).unwrap();
+
+    assert!(registry.register("name", index));
+
+    assert_eq!(registry.with_name("name", |value| value + 1), Some(124));
+    assert_eq!(registry.with_name("other", |value| value + 1), None);
+  }
+
+  #[test]
+  fn registered() {
+    let registry: Registry<usize, usize, DefParams> = Registry::new();
+
+    assert!(registry.registered().is_empty());
+
+    let mut indices: Vec<(usize, Detached)> = (0..4)
+      .map(|name| {
+        let index: Detached = registry.table().insert(name).unwrap();
+        assert!(registry.register(name, index));
+        (name, index)
+      })
+      .collect();
+
+    let mut registered: Vec<(usize, Detached)> = registry.registered();
+
+    registered.sort();
+    indices.sort();
+
+    assert_eq!(registered, indices);
+  }
+
+  #[test]
+  fn concurrent_register() {
+    let registry: Arc<Registry<&str, usize, DefParams>> = Arc::new(Registry::new());
+
+    let threads: Vec<JoinHandle<Option<Detached>>> = (0..THREADS)
+      .map(|value| {
+        let registry: Arc<Registry<&str, usize, DefParams>> = Arc::clone(&registry);
+
+        thread::spawn(move || {
+          let index: Detached = registry.table().insert(value).unwrap();
+          registry.register("name", index).then_some(index)
+        })
+      })
+      .collect();
+
+    let winners: Vec<Detached> = threads
+      .into_iter()
+      .filter_map(|thread| thread.join().unwrap())
+      .collect();
+
+    assert_eq!(winners.len(), 1);
+    assert_eq!(registry.whereis("name"), Some(winners[0]));
+  }
+}
@@ -0,0 +1,297 @@
+use core::fmt::Debug;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::hash::BuildHasher;
+use core::str::FromStr;
+use std::hash::RandomState;
+
+use crate::error::ParseIndexError;
+use crate::index::Detached;
+
+/// The number of Feistel rounds.
+const ROUNDS: u8 = 4;
+
+/// An opaque handle to a table entry.
+///
+/// Created by [`PTab::export`] and turned back into an index by
+/// [`PTab::import`]. The token is the index together with 64 zero bits,
+/// encrypted under a random 128-bit key chosen by the table. The cipher is a
+/// four-round Feistel network with SipHash-2-4 as its round function, which is
+/// a strong pseudorandom permutation as long as SipHash is a pseudorandom
+/// function.
+///
+/// Without the key, tokens therefore reveal nothing about the index, its slot
+/// or the allocation order, and a forged or corrupted token passes the check
+/// of the zero bits with a probability of about 2<sup>-64</sup> per attempt.
+/// These bounds hold for up to around 2<sup>32</sup> tokens exported or
+/// imported per table. A token is a bearer credential: anyone holding a valid
+/// token can import it, so tokens must still be kept from clients that are
+/// not allowed to use the entry.
+///
+/// Tokens are only meaningful to the table that exported them, and only for
+/// the lifetime of that table; a deserialized table uses a new key.
+///
+/// # Examples
+///
+/// ```
+/// use ptab::{PTab, Token};
+///
+/// let table: PTab<&str> = PTab::new();
+/// let index = table.insert("session").unwrap();
+///
+/// let token = table.export(index);
+/// let text = token.to_string();
+///
+/// assert_eq!(table.import(text.parse().unwrap()), Some(index));
+/// assert_eq!(table.import(Token::from_bits(0x1234)), None);
+/// ```
+///
+/// [`PTab::export`]: crate::public::PTab::export
+/// [`PTab::import`]: crate::public::PTab::import
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Token {
+  bits: u128,
+}
+
+impl Token {
+  /// Creates a token from its raw bit representation.
+  #[inline]
+  pub const fn from_bits(bits: u128) -> Self {
+    Self { bits }
+  }
+
+  /// Returns the raw bit representation of this token.
+  #[inline]
+  pub const fn into_bits(self) -> u128 {
+    self.bits
+  }
+}
+
+impl Debug for Token {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_tuple("Token")
+      .field(&format_args!("{self}"))
+      .finish()
+  }
+}
+
+/// Formats the token as 32 lowercase hexadecimal digits.
+impl Display for Token {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    write!(f, "{:032x}", self.bits)
+  }
+}
+
+impl FromStr for Token {
+  type Err = ParseIndexError;
+
+  fn from_str(input: &str) -> Result<Self, Self::Err> {
+    if input.len() != 32 || !input.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+      return Err(ParseIndexError::Invalid);
+    }
+
+    match u128::from_str_radix(input, 16) {
+      Ok(bits) => Ok(Self::from_bits(bits)),
+      Err(_) => Err(ParseIndexError::Invalid),
+    }
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Sealer
+// -----------------------------------------------------------------------------
+
+/// A keyed permutation between indices and tokens.
+///
+/// A balanced Feistel network over 128-bit blocks, using SipHash-2-4 under a
+/// random 128-bit key as the round function. Each round hashes its number
+/// along with the half block, so the rounds use independent functions. The
+/// index occupies the left half of the plaintext and the right half is zero,
+/// which is verified when opening.
+pub(crate) struct Sealer {
+  key: [u64; 2],
+}
+
+impl Sealer {
+  /// Creates a sealer with a random key.
+  ///
+  /// The key is drawn from the standard library's hasher seed, which comes
+  /// from the operating system's random number generator.
+  #[inline]
+  pub(crate) fn new() -> Self {
+    let state: RandomState = RandomState::new();
+
+    Self {
+      key: [state.hash_one(0_u8), state.hash_one(1_u8)],
+    }
+  }
+
+  /// Encrypts `index` into a token.
+  pub(crate) fn seal(&self, index: Detached) -> Token {
+    let mut l: u64 = index.into_bits() as u64;
+    let mut r: u64 = 0;
+
+    for round in 0..ROUNDS {
+      (l, r) = (r, l ^ self.round(round, r));
+    }
+
+    Token::from_bits((u128::from(l) << 64) | u128::from(r))
+  }
+
+  /// Decrypts `token`, returning [`None`] if its redundancy check fails.
+  pub(crate) fn open(&self, token: Token) -> Option<Detached> {
+    let mut l: u64 = (token.into_bits() >> 64) as u64;
+    let mut r: u64 = token.into_bits() as u64;
+
+    for round in (0..ROUNDS).rev() {
+      (l, r) = (r ^ self.round(round, l), l);
+    }
+
+    if r != 0 {
+      return None;
+    }
+
+    usize::try_from(l).ok().map(Detached::from_bits)
+  }
+
+  #[inline]
+  fn round(&self, round: u8, half: u64) -> u64 {
+    siphash(self.key, [half, u64::from(round)])
+  }
+}
+
+// -----------------------------------------------------------------------------
+// SipHash
+// -----------------------------------------------------------------------------
+
+/// Computes SipHash-2-4 of the 16 little-endian bytes of `words`.
+fn siphash(key: [u64; 2], words: [u64; 2]) -> u64 {
+  let mut v: [u64; 4] = [
+    key[0] ^ 0x736f_6d65_7073_6575,
+    key[1] ^ 0x646f_7261_6e64_6f6d,
+    key[0] ^ 0x6c79_6765_6e65_7261,
+    key[1] ^ 0x7465_6462_7974_6573,
+  ];
+
+  // The final block only holds the message length, 16 bytes.
+  for word in [words[0], words[1], 16 << 56] {
+    v[3] ^= word;
+    sipround(&mut v);
+    sipround(&mut v);
+    v[0] ^= word;
+  }
+
+  v[2] ^= 0xff;
+
+  for _ in 0..4 {
+    sipround(&mut v);
+  }
+
+  v[0] ^ v[1] ^ v[2] ^ v[3]
+}
+
+#[inline]
+fn sipround(v: &mut [u64; 4]) {
+  v[0] = v[0].wrapping_add(v[1]);
+  v[1] = v[1].rotate_left(13) ^ v[0];
+  v[0] = v[0].rotate_left(32);
+  v[2] = v[2].wrapping_add(v[3]);
+  v[3] = v[3].rotate_left(16) ^ v[2];
+  v[0] = v[0].wrapping_add(v[3]);
+  v[3] = v[3].rotate_left(21) ^ v[0];
+  v[2] = v[2].wrapping_add(v[1]);
+  v[1] = v[1].rotate_left(17) ^ v[2];
+  v[2] = v[2].rotate_left(32);
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cfg(test)]
+mod tests {
+  use std::collections::HashSet;
+
+  use crate::error::ParseIndexError;
+  use crate::index::Detached;
+  use crate::seal;
+  use crate::seal::Sealer;
+  use crate::seal::Token;
+
+  #[test]
+  fn roundtrip() {
+    let sealer: Sealer = Sealer::new();
+
+    for bits in [0, 1, 2, 1 << 20, usize::MAX] {
+      let index: Detached = Detached::from_bits(bits);
+
+      assert_eq!(sealer.open(sealer.seal(index)), Some(index));
+    }
+  }
+
+  #[test]
+  fn obfuscates() {
+    let sealer: Sealer = Sealer::new();
+
+    // Consecutive indices do not produce related tokens.
+    let tokens: HashSet<u64> = (0..1024)
+      .map(|bits| (sealer.seal(Detached::from_bits(bits)).into_bits() >> 64) as u64)
+      .collect();
+
+    assert_eq!(tokens.len(), 1024);
+    assert_ne!(sealer.seal(Detached::from_bits(0)).into_bits(), 0);
+  }
+
+  #[test]
+  fn rejects_corrupted() {
+    let sealer: Sealer = Sealer::new();
+    let token: Token = sealer.seal(Detached::from_bits(42));
+
+    for bit in 0..128 {
+      assert_eq!(
+        sealer.open(Token::from_bits(token.into_bits() ^ (1 << bit))),
+        None
+      );
+    }
+
+    for bits in 0..1024 {
+      assert_eq!(sealer.open(Token::from_bits(bits)), None);
+    }
+  }
+
+  #[test]
+  fn keyed() {
+    let a: Sealer = Sealer::new();
+    let b: Sealer = Sealer::new();
+    let index: Detached = Detached::from_bits(7);
+
+    assert_ne!(a.seal(index), b.seal(index));
+    assert_eq!(b.open(a.seal(index)), None);
+  }
+
+  #[test]
+  fn siphash() {
+    // The test vector for 16 bytes from the SipHash reference implementation.
+    let key: [u64; 2] = [0x0706_0504_0302_0100, 0x0f0e_0d0c_0b0a_0908];
+    let words: [u64; 2] = [0x0706_0504_0302_0100, 0x0f0e_0d0c_0b0a_0908];
+
+    assert_eq!(seal::siphash(key, words), 0x3f2a_cc7f_57c2_9bdb);
+  }
+
+  #[test]
+  fn parse() {
+    let token: Token = Token::from_bits(0x0123_4567_89ab_cdef_0011_2233_4455_6677);
+    let text: String = token.to_string();
+
+    assert_eq!(text, "0123456789abcdef0011223344556677");
+    assert_eq!(text.parse::<Token>(), Ok(token));
+    assert_eq!("0123".parse::<Token>(), Err(ParseIndexError::Invalid));
+    assert_eq!(
+      "+123456789abcdef0011223344556677".parse::<Token>(),
+      Err(ParseIndexError::Invalid)
+    );
+  }
+}
@@ -0,0 +1,467 @@
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::panic::RefUnwindSafe;
+use core::panic::UnwindSafe;
+
+use crate::array::Array;
+use crate::index::Concrete;
+use crate::index::Detached;
+use crate::params::DefaultParams;
+use crate::params::Params;
+use crate::params::ParamsExt;
+use crate::reclaim::Atomic;
+use crate::reclaim::Guard;
+use crate::reclaim::Shared;
+use crate::sync::atomic::Ordering::AcqRel;
+use crate::sync::atomic::Ordering::Acquire;
+
+/// A side table associating values with the entries of a [`PTab`].
+///
+/// A `SecondaryTable` shares the [`Params`] of its primary table and stores
+/// values in the same slot as the primary entry, so lookups are a direct array
+/// access with the same lock-free read path as [`PTab::with`]. Each value
+/// remembers the full [`Detached`] index it was inserted under, so lookups
+/// through a stale index return [`None`] even after the slot is reused.
+///
+/// The secondary table does not observe the primary table. Values for removed
+/// entries are kept until they are removed or replaced by a value for a newer
+/// entry in the same slot; they are never returned for a different index, and
+/// a stale index cannot overwrite the value of a newer entry.
+///
+/// # Examples
+///
+/// ```
+/// use ptab::{PTab, SecondaryTable};
+///
+/// let table: PTab<&str> = PTab::new();
+/// let names: SecondaryTable<String> = SecondaryTable::new();
+///
+/// let index = table.insert("worker").unwrap();
+/// names.insert(index, "worker-1".to_string()).unwrap();
+///
+/// assert_eq!(names.with(index, |name| name.len()), Some(8));
+///
+/// table.remove(index);
+/// names.remove(index);
+///
+/// assert!(!names.contains(index));
+/// ```
+///
+/// Values are shared between readers, so a table is only [`Sync`] if its
+/// values are:
+///
+/// ```compile_fail
+/// use std::cell::Cell;
+///
+/// use ptab::SecondaryTable;
+///
+/// fn assert_sync<T: Sync>(_: &T) {}
+///
+/// // error: `Cell<u64>` cannot be shared between threads safely
+/// assert_sync(&SecondaryTable::<Cell<u64>>::new());
+/// ```
+///
+/// [`PTab`]: crate::public::PTab
+/// [`PTab::with`]: crate::public::PTab::with
+/// [`Params`]: crate::params::Params
+pub struct SecondaryTable<V, P = DefaultParams>
+where
+  P: Params + ?Sized,
+{
+  data: Array<Atomic<Slot<V>>, P>,
+}
+
+impl<V, P> SecondaryTable<V, P>
+where
+  P: Params + ?Sized,
+{
+  /// Creates a new, empty secondary table.
+  #[inline]
+  pub fn new() -> Self {
+    // SAFETY: A null `Atomic` is represented by the all-zero bit pattern.
+    #[cfg(not(any(loom, shuttle)))]
+    let data: Array<Atomic<Slot<V>>, P> = unsafe { Array::new_zeroed() };
+
+    // The loom/shuttle atomics are not plain integers, so they can't be zeroed.
+    #[cfg(any(loom, shuttle))]
+    let data: Array<Atomic<Slot<V>>, P> = Array::new(|_, slot| {
+      slot.write(Atomic::null());
+    });
+
+    Self { data }
+  }
+
+  /// Returns the number of slots, equal to the capacity of the primary table.
+  #[inline]
+  pub const fn capacity(&self) -> usize {
+    P::LENGTH.as_usize()
+  }
+
+  /// Associates `value` with the entry at `index`.
+  ///
+  /// Replaces a value stored under `index` itself, or under an older
+  /// generation of the same slot. Returns `Ok(true)` if a value for `index`
+  /// itself was replaced.
+  ///
+  /// # Errors
+  ///
+  /// Returns `value` back if the slot holds a value for a newer generation,
+  /// so a stale index never overwrites the value of the entry that reused its
+  /// slot.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use ptab::{PTab, SecondaryTable};
+  ///
+  /// let table: PTab<()> = PTab::new();
+  /// let hits: SecondaryTable<u64> = SecondaryTable::new();
+  /// let index = table.insert(()).unwrap();
+  ///
+  /// assert_eq!(hits.insert(index, 1), Ok(false));
+  /// assert_eq!(hits.insert(index, 2), Ok(true));
+  /// assert_eq!(hits.read(index), Some(2));
+  /// ```
+  pub fn insert(&self, index: Detached, value: V) -> Result<bool, V>
+  where
+    V: 'static,
+  {
+    let guard: Guard = Guard::new();
+    let slot: Slot<V> = Slot {
+      key: index,
+      value: Some(value),
+    };
+
+    let result: Result<Shared<'_, Slot<V>>, Option<V>> = self.slot(index).replace_if(
+      slot,
+      AcqRel,
+      &guard,
+      |slot| slot.is_none_or(|slot| !is_newer::<P>(slot.key, index)),
+      |slot| slot.value.take(),
+    );
+
+    match result {
+      Ok(old) => Ok(old.as_ref().is_some_and(|slot| slot.key == index)),
+      Err(Some(value)) => Err(value),
+      Err(None) => unreachable!("unpublished slots hold a value"),
+    }
+  }
+
+  /// Removes the value associated with the entry at `index`.
+  ///
+  /// Returns `true` if a value was removed. Values stored under a different
+  /// index in the same slot are left untouched.
+  #[inline]
+  pub fn remove(&self, index: Detached) -> bool {
+    self
+      .slot(index)
+      .evict_if(AcqRel, |slot| slot.key == index)
+      .is_some()
+  }
+
+  /// Returns `true` if a value is associated with the entry at `index`.
+  #[inline]
+  pub fn contains(&self, index: Detached) -> bool {
+    !self.find(index, &Guard::new()).is_null()
+  }
+
+  /// Accesses the value associated with the entry at `index`.
+  ///
+  /// Returns [`None`] if no value exists for `index`. The reference remains
+  /// valid for the callback's duration even under concurrent removal.
+  #[inline]
+  pub fn with<F, R>(&self, index: Detached, f: F) -> Option<R>
+  where
+    F: Fn(&V) -> R,
+  {
+    self
+      .find(index, &Guard::new())
+      .as_ref()
+      .and_then(|slot| slot.value.as_ref())
+      .map(f)
+  }
+
+  /// Returns a copy of the value associated with the entry at `index`.
+  #[inline]
+  pub fn read(&self, index: Detached) -> Option<V>
+  where
+    V: Copy,
+  {
+    self.with(index, |value| *value)
+  }
+
+  #[inline]
+  fn slot(&self, index: Detached) -> &Atomic<Slot<V>> {
+    self.data.get(Concrete::from_detached(index))
+  }
+
+  #[inline]
+  fn find<'guard>(&self, index: Detached, guard: &'guard Guard) -> Shared<'guard, Slot<V>> {
+    let slot: Shared<'guard, Slot<V>> = self.slot(index).load(Acquire, guard);
+
+    // Reject values stored under an older generation of this slot.
+    match slot.as_ref() {
+      Some(data) if data.key != index => Shared::null(),
+      Some(_) | None => slot,
+    }
+  }
+}
+
+impl<V, P> Debug for SecondaryTable<V, P>
+where
+  P: Params + ?Sized,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_struct("SecondaryTable")
+      .field("capacity", &self.capacity())
+      .finish_non_exhaustive()
+  }
+}
+
+impl<V, P> Default for SecondaryTable<V, P>
+where
+  P: Params + ?Sized,
+{
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<V, P> Drop for SecondaryTable<V, P>
+where
+  P: Params + ?Sized,
+{
+  fn drop(&mut self) {
+    for slot in self.data.as_mut_slice() {
+      // SAFETY:
+      // - `Drop` provides exclusive access via `&mut self`, so no concurrent
+      //   access can occur.
+      // - Each slot is dropped at most once.
+      unsafe {
+        slot.drop_in_place();
+      }
+    }
+  }
+}
+
+// SAFETY: All mutation is performed via atomics, and owned values are only
+//         transferred between threads, which requires `V: Send`.
+unsafe impl<V, P> Send for SecondaryTable<V, P>
+where
+  V: Send,
+  P: Params + ?Sized,
+{
+}
+
+// SAFETY:
+// - All mutation is performed via atomics.
+// - `with` hands out `&V` to any number of threads at once, which requires
+//   `V: Sync`.
+// - `insert` and `remove` move values in from or out to other threads, and
+//   reclamation may drop them on another thread, which requires `V: Send`.
+unsafe impl<V, P> Sync for SecondaryTable<V, P>
+where
+  V: Send + Sync,
+  P: Params + ?Sized,
+{
+}
+
+// Unconditional for the same reasons as `Table`.
+impl<V, P> RefUnwindSafe for SecondaryTable<V, P> where P: Params + ?Sized {}
+impl<V, P> UnwindSafe for SecondaryTable<V, P> where P: Params + ?Sized {}
+
+/// A value tagged with the index it was inserted under.
+///
+/// The value is only taken out of a slot that was never published, to hand it
+/// back from a rejected insert.
+struct Slot<V> {
+  key: Detached,
+  value: Option<V>,
+}
+
+/// Returns `true` if `key` is a newer generation of the same slot than `than`.
+///
+/// Generations wrap around, so they are compared with serial number arithmetic
+/// over the bits below the table id.
+#[inline]
+const fn is_newer<P>(key: Detached, than: Detached) -> bool
+where
+  P: Params + ?Sized,
+{
+  let key: usize = key.into_bits() << P::ID_TABLE_BITS;
+  let than: usize = than.into_bits() << P::ID_TABLE_BITS;
+
+  key.wrapping_sub(than).cast_signed() > 0
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(not(any(loom, shuttle)))]
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+  use std::thread;
+
+  use crate::index::Concrete;
+  use crate::index::Detached;
+  use crate::params::Capacity;
+  use crate::params::ConstParams;
+  use crate::params::Params;
+  use crate::public::PTab;
+  use crate::secondary::SecondaryTable;
+
+  type DefParams = ConstParams<{ Capacity::DEF.as_usize() }>;
+  type MinParams = ConstParams<{ Capacity::MIN.as_usize() }>;
+
+  const THREADS: usize = 8;
+
+  #[test]
+  fn capacity() {
+    let table: SecondaryTable<usize, MinParams> = SecondaryTable::new();
+
+    assert_eq!(table.capacity(), MinParams::LENGTH.as_usize());
+  }
+
+  #[test]
+  fn insert() {
+    let primary: PTab<(), DefParams> = PTab::new();
+    let table: SecondaryTable<usize, DefParams> = SecondaryTable::new();
+    let index: Detached = primary.insert(()).unwrap();
+
+    assert!(!table.contains(index));
+    assert_eq!(table.insert(index, 1), Ok(false));
+    assert!(table.contains(index));
+    assert_eq!(table.read(index), Some(1));
+
+    assert_eq!(table.insert(index, 2), Ok(true));
+    assert_eq!(table.read(index), Some(2));
+  }
+
+  #[test]
+  fn remove() {
+    let primary: PTab<(), DefParams> = PTab::new();
+    let table: SecondaryTable<usize, DefParams> = SecondaryTable::new();
+    let index: Detached = primary.insert(()).unwrap();
+
+    assert!(!table.remove(index));
+    assert_eq!(table.insert(index, 1), Ok(false));
+    assert!(table.remove(index));
+    assert!(!table.remove(index));
+    assert_eq!(table.read(index), None);
+  }
+
+  #[test]
+  fn with() {
+    let primary: PTab<(), DefParams> = PTab::new();
+    let table: SecondaryTable<String, DefParams> = SecondaryTable::new();
+    let index: Detached = primary.insert(()).unwrap();
+
+    assert_eq!(table.with(index, String::len), None);
+    assert_eq!(table.insert(index, "hello".to_string()), Ok(false));
+    assert_eq!(table.with(index, String::len), Some(5));
+  }
+
+  #[test]
+  fn stale_index() {
+    let primary: PTab<(), MinParams> = PTab::new();
+    let table: SecondaryTable<usize, MinParams> = SecondaryTable::new();
+
+    let old: Detached = primary.insert(()).unwrap();
+    let slot: usize = Concrete::<MinParams>::from_detached(old).get();
+
+    assert_eq!(table.insert(old, 1), Ok(false));
+    assert!(primary.remove(old));
+
+    // Fill the table so the slot of `old` is reused with a new generation.
+    let new: Detached = (0..primary.capacity())
+      .map(|_| primary.insert(()).unwrap())
+      .find(|index| Concrete::<MinParams>::from_detached(*index).get() == slot)
+      .unwrap();
+
+    assert_ne!(old, new);
+
+    // The value is not visible through the new index...
+    assert!(!table.contains(new));
+    assert!(!table.remove(new));
+
+    // ...and is replaced by the first insert for it.
+    assert_eq!(table.insert(new, 2), Ok(false));
+    assert!(!table.contains(old));
+    assert_eq!(table.read(new), Some(2));
+  }
+
+  #[test]
+  fn stale_insert() {
+    let primary: PTab<(), MinParams> = PTab::new();
+    let table: SecondaryTable<usize, MinParams> = SecondaryTable::new();
+
+    let old: Detached = primary.insert(()).unwrap();
+    let slot: usize = Concrete::<MinParams>::from_detached(old).get();
+
+    assert!(primary.remove(old));
+
+    let new: Detached = (0..primary.capacity())
+      .map(|_| primary.insert(()).unwrap())
+      .find(|index| Concrete::<MinParams>::from_detached(*index).get() == slot)
+      .unwrap();
+
+    assert_eq!(table.insert(new, 2), Ok(false));
+
+    // An insert through the stale index hands its value back...
+    assert_eq!(table.insert(old, 1), Err(1));
+    assert!(!table.remove(old));
+
+    // ...and leaves the value of the newer entry in place.
+    assert_eq!(table.read(new), Some(2));
+    assert_eq!(table.read(old), None);
+  }
+
+  #[test]
+  fn drop_values() {
+    let primary: PTab<(), DefParams> = PTab::new();
+    let table: SecondaryTable<Arc<()>, DefParams> = SecondaryTable::new();
+    let value: Arc<()> = Arc::new(());
+
+    for _ in 0..4 {
+      let index: Detached = primary.insert(()).unwrap();
+      assert_eq!(table.insert(index, Arc::clone(&value)), Ok(false));
+    }
+
+    drop(table);
+
+    assert_eq!(Arc::strong_count(&value), 1);
+  }
+
+  #[test]
+  fn concurrent() {
+    let primary: Arc<PTab<(), DefParams>> = Arc::new(PTab::new());
+    let table: Arc<SecondaryTable<usize, DefParams>> = Arc::new(SecondaryTable::new());
+
+    let threads: Vec<_> = (0..THREADS)
+      .map(|thread| {
+        let primary: Arc<PTab<(), DefParams>> = Arc::clone(&primary);
+        let table: Arc<SecondaryTable<usize, DefParams>> = Arc::clone(&table);
+
+        thread::spawn(move || {
+          for value in 0..1000 {
+            let index: Detached = primary.insert(()).unwrap();
+
+            assert_eq!(table.insert(index, thread * value), Ok(false));
+            assert_eq!(table.read(index), Some(thread * value));
+            assert!(table.remove(index));
+            assert!(primary.remove(index));
+          }
+        })
+      })
+      .collect();
+
+    for thread in threads {
+      thread.join().unwrap();
+    }
+  }
+}
@@ -0,0 +1,446 @@
+use alloc::vec::Vec;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::marker::PhantomData;
+
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+use serde::de::Error;
+use serde::de::MapAccess;
+use serde::de::SeqAccess;
+use serde::de::Unexpected;
+use serde::de::Visitor;
+use serde::ser::SerializeStruct;
+
+use crate::index::Detached;
+
+/// The serialized form of a table.
+///
+/// Holds the occupied entries together with the allocator state, which is
+/// enough to rebuild a table that resolves every previously issued index to
+/// the same value and continues the same sequence of generations.
+///
+/// Most positions of the free-id ring still hold the id they started with, so
+/// only the others are recorded; the rest are rebuilt on restore.
+pub(crate) struct Snapshot<V> {
+  /// The table id carried in the high bits of every index.
+  pub(crate) table: usize,
+  /// The number of slots in the table.
+  pub(crate) capacity: usize,
+  /// The allocation cursor into the free-id ring.
+  pub(crate) next_id: u32,
+  /// The release cursor into the free-id ring.
+  pub(crate) free_id: u32,
+  /// The free ring positions whose id differs from the initial one, as
+  /// `(position, id)` pairs.
+  pub(crate) free: Vec<(usize, usize)>,
+  /// The occupied entries and their indices.
+  pub(crate) entries: Vec<(Detached, V)>,
+}
+
+const NAME: &str = "PTab";
+const FIELDS: &[&str] = &["table", "capacity", "next_id", "free_id", "free", "entries"];
+
+impl<V> Serialize for Snapshot<V>
+where
+  V: Serialize,
+{
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut state: S::SerializeStruct = serializer.serialize_struct(NAME, FIELDS.len())?;
+
+    state.serialize_field("table", &self.table)?;
+    state.serialize_field("capacity", &self.capacity)?;
+    state.serialize_field("next_id", &self.next_id)?;
+    state.serialize_field("free_id", &self.free_id)?;
+    state.serialize_field("free", &self.free)?;
+    state.serialize_field("entries", &self.entries)?;
+    state.end()
+  }
+}
+
+impl<'de, V> Deserialize<'de> for Snapshot<V>
+where
+  V: Deserialize<'de>,
+{
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    deserializer.deserialize_struct(NAME, FIELDS, SnapshotVisitor(PhantomData))
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Visitors
+// -----------------------------------------------------------------------------
+
+enum Field {
+  Table,
+  Capacity,
+  NextId,
+  FreeId,
+  Free,
+  Entries,
+}
+
+impl<'de> Deserialize<'de> for Field {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    deserializer.deserialize_identifier(FieldVisitor)
+  }
+}
+
+struct FieldVisitor;
+
+impl Visitor<'_> for FieldVisitor {
+  type Value = Field;
+
+  fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.write_str("a table snapshot field")
+  }
+
+  fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+  where
+    E: Error,
+  {
+    match value {
+      0 => Ok(Field::Table),
+      1 => Ok(Field::Capacity),
+      2 => Ok(Field::NextId),
+      3 => Ok(Field::FreeId),
+      4 => Ok(Field::Free),
+      5 => Ok(Field::Entries),
+      _ => Err(E::invalid_value(Unexpected::Unsigned(value), &self)),
+    }
+  }
+
+  fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+  where
+    E: Error,
+  {
+    match value {
+      "table" => Ok(Field::Table),
+      "capacity" => Ok(Field::Capacity),
+      "next_id" => Ok(Field::NextId),
+      "free_id" => Ok(Field::FreeId),
+      "free" => Ok(Field::Free),
+      "entries" => Ok(Field::Entries),
+      _ => Err(E::unknown_field(value, FIELDS)),
+    }
+  }
+}
+
+struct SnapshotVisitor<V>(PhantomData<fn() -> V>);
+
+impl<'de, V> Visitor<'de> for SnapshotVisitor<V>
+where
+  V: Deserialize<'de>,
+{
+  type Value = Snapshot<V>;
+
+  fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.write_str("a table snapshot")
+  }
+
+  fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+  where
+    A: SeqAccess<'de>,
+  {
+    Ok(Snapshot {
+      table: seq
+        .next_element()?
+        .ok_or_else(|| A::Error::invalid_length(0, &self))?,
+      capacity: seq
+        .next_element()?
+        .ok_or_else(|| A::Error::invalid_length(1, &self))?,
+      next_id: seq
+        .next_element()?
+        .ok_or_else(|| A::Error::invalid_length(2, &self))?,
+      free_id: seq
+        .next_element()?
+        .ok_or_else(|| A::Error::invalid_length(3, &self))?,
+      free: seq
+        .next_element()?
+        .ok_or_else(|| A::Error::invalid_length(4, &self))?,
+      entries: seq
+        .next_element()?
+        .ok_or_else(|| A::Error::invalid_length(5, &self))?,
+    })
+  }
+
+  fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+  where
+    A: MapAccess<'de>,
+  {
+    let mut table: Option<usize> = None;
+    let mut capacity: Option<usize> = None;
+    let mut next_id: Option<u32> = None;
+    let mut free_id: Option<u32> = None;
+    let mut free: Option<Vec<(usize, usize)>> = None;
+    let mut entries: Option<Vec<(Detached, V)>> = None;
+
+    while let Some(field) = map.next_key()? {
+      match field {
+        Field::Table => set_field(&mut table, "table", map.next_value()?)?,
+        Field::Capacity => set_field(&mut capacity, "capacity", map.next_value()?)?,
+        Field::NextId => set_field(&mut next_id, "next_id", map.next_value()?)?,
+        Field::FreeId => set_field(&mut free_id, "free_id", map.next_value()?)?,
+        Field::Free => set_field(&mut free, "free", map.next_value()?)?,
+        Field::Entries => set_field(&mut entries, "entries", map.next_value()?)?,
+      }
+    }
+
+    Ok(Snapshot {
+      table: table.ok_or_else(|| A::Error::missing_field("table"))?,
+      capacity: capacity.ok_or_else(|| A::Error::missing_field("capacity"))?,
+      next_id: next_id.ok_or_else(|| A::Error::missing_field("next_id"))?,
+      free_id: free_id.ok_or_else(|| A::Error::missing_field("free_id"))?,
+      free: free.ok_or_else(|| A::Error::missing_field("free"))?,
+      entries: entries.ok_or_else(|| A::Error::missing_field("entries"))?,
+    })
+  }
+}
+
+fn set_field<T, E>(slot: &mut Option<T>, name: &'static str, value: T) -> Result<(), E>
+where
+  E: Error,
+{
+  if slot.replace(value).is_some() {
+    return Err(E::duplicate_field(name));
+  }
+
+  Ok(())
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(not(any(loom, shuttle)))]
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cfg(test)]
+mod tests {
+  use serde_json::Value;
+
+  use crate::index::Detached;
+  use crate::params::Capacity;
+  use crate::params::ConstParams;
+  use crate::params::Params;
+  use crate::public::PTab;
+
+  type DefParams = ConstParams<{ Capacity::DEF.as_usize() }>;
+  type MaxParams = ConstParams<{ Capacity::MAX.as_usize() }>;
+  type MinParams = ConstParams<{ Capacity::MIN.as_usize() }>;
+
+  fn roundtrip<T, P>(table: &PTab<T, P>) -> PTab<T, P>
+  where
+    T: serde::Serialize + serde::de::DeserializeOwned + 'static,
+    P: Params + ?Sized,
+  {
+    serde_json::from_str(&serde_json::to_string(table).unwrap()).unwrap()
+  }
+
+  #[test]
+  fn empty() {
+    let table: PTab<usize, MinParams> = PTab::new();
+    let restored: PTab<usize, MinParams> = roundtrip(&table);
+
+    assert!(restored.is_empty());
+    assert_eq!(restored.insert(1), table.insert(1));
+  }
+
+  #[test]
+  fn preserves_indices() {
+    let table: PTab<String, DefParams> = PTab::new();
+
+    let keys: Vec<Detached> = (0..100)
+      .map(|value| table.insert(value.to_string()).unwrap())
+      .collect();
+
+    for key in keys.iter().step_by(3) {
+      assert!(table.remove(*key));
+    }
+
+    let restored: PTab<String, DefParams> = roundtrip(&table);
+
+    assert_eq!(restored.len(), table.len());
+
+    for (value, key) in keys.iter().enumerate() {
+      assert_eq!(
+        restored.with(*key, String::clone),
+        table.with(*key, String::clone)
+      );
+
+      if value % 3 != 0 {
+        assert_eq!(restored.with(*key, String::clone), Some(value.to_string()));
+      }
+    }
+  }
+
+  #[test]
+  fn preserves_generations() {
+    let table: PTab<usize, MinParams> = PTab::new();
+
+    // Churn every slot a few times so the generations are non-trivial.
+    for value in 0..table.capacity() * 3 {
+      let key: Detached = table.insert(value).unwrap();
+
+      if value % 5 != 0 {
+        assert!(table.remove(key));
+      }
+    }
+
+    let stale: Detached = table.insert(0).unwrap();
+    assert!(table.remove(stale));
+
+    let restored: PTab<usize, MinParams> = roundtrip(&table);
+
+    assert!(!restored.exists(stale));
+
+    // Both tables continue the same sequence of indices until full.
+    while let Some(key) = table.insert(1) {
+      assert_eq!(restored.insert(1), Some(key));
+      assert_ne!(key, stale);
+    }
+
+    assert!(restored.insert(1).is_none());
+  }
+
+  #[test]
+  fn compact_ring() {
+    let table: PTab<usize, DefParams> = PTab::new();
+    let keys: Vec<Detached> = (0..3).map(|value| table.insert(value).unwrap()).collect();
+
+    // Untouched ring positions are left out.
+    let json: Value = serde_json::to_value(&table).unwrap();
+    assert_eq!(json["free"], Value::Array(Vec::new()));
+
+    // Only the position holding the next generation of the freed id is kept.
+    assert!(table.remove(keys[1]));
+
+    let json: Value = serde_json::to_value(&table).unwrap();
+    assert_eq!(json["free"].as_array().unwrap().len(), 1);
+
+    let restored: PTab<usize, DefParams> = serde_json::from_value(json).unwrap();
+
+    assert_eq!(restored.read(keys[0]), Some(0));
+    assert!(!restored.exists(keys[1]));
+
+    while let Some(key) = table.insert(1) {
+      assert_eq!(restored.insert(1), Some(key));
+    }
+  }
+
+  #[test]
+  fn misplaced_ring_position() {
+    let table: PTab<usize, MinParams> = PTab::new();
+    let key: Detached = table.insert(1).unwrap();
+
+    let mut json: Value = serde_json::to_value(&table).unwrap();
+
+    // Position 0 is allocated, so it is not part of the free ring.
+    json["free"] = Value::from(vec![vec![0, key.into_bits()]]);
+
+    assert!(serde_json::from_value::<PTab<usize, MinParams>>(json).is_err());
+  }
+
+  #[test]
+  fn max_capacity() {
+    let table: PTab<u8, MaxParams> = PTab::new();
+    let key: Detached = table.insert(1).unwrap();
+    let restored: PTab<u8, MaxParams> = roundtrip(&table);
+
+    assert_eq!(restored.read(key), Some(1));
+    assert_eq!(restored.len(), 1);
+    assert_eq!(restored.insert(2), table.insert(2));
+  }
+
+  #[test]
+  fn table_id() {
+    struct TaggedParams;
+
+    impl Params for TaggedParams {
+      const LENGTH: Capacity = Capacity::MIN;
+      const TABLE_ID_BITS: u32 = 8;
+      const CHECKED: bool = true;
+    }
+
+    let table: PTab<u8, TaggedParams> = PTab::new();
+    let other: PTab<u8, TaggedParams> = PTab::new();
+    let key: Detached = table.insert(1).unwrap();
+
+    // The restored table takes over the original id, so the original indices
+    // keep resolving.
+    let restored: PTab<u8, TaggedParams> = roundtrip(&table);
+
+    assert_eq!(restored.read(key), Some(1));
+    assert_eq!(restored.weak_keys().next(), Some(key));
+    assert_eq!(restored.insert(2), table.insert(2));
+    assert_eq!(other.read(key), None);
+
+    // Snapshots of another table cannot adopt foreign entries.
+    let mut json: Value = serde_json::to_value(&table).unwrap();
+    json["table"] = serde_json::to_value(&other).unwrap()["table"].clone();
+
+    assert!(serde_json::from_value::<PTab<u8, TaggedParams>>(json).is_err());
+  }
+
+  #[test]
+  fn capacity_mismatch() {
+    let table: PTab<usize, MinParams> = PTab::new();
+    let json: String = serde_json::to_string(&table).unwrap();
+
+    assert!(serde_json::from_str::<PTab<usize, DefParams>>(&json).is_err());
+  }
+
+  #[test]
+  fn duplicate_slot() {
+    let table: PTab<usize, MinParams> = PTab::new();
+    let key: Detached = table.insert(1).unwrap();
+
+    let mut json: Value = serde_json::to_value(&table).unwrap();
+
+    // Claim a free slot for the entry as well.
+    json["free"] = Value::from(vec![vec![1, key.into_bits()]]);
+
+    assert!(serde_json::from_value::<PTab<usize, MinParams>>(json).is_err());
+  }
+
+  #[test]
+  fn inconsistent_counters() {
+    let table: PTab<usize, MinParams> = PTab::new();
+    table.insert(1).unwrap();
+
+    let mut json: Value = serde_json::to_value(&table).unwrap();
+    json["next_id"] = Value::from(0);
+
+    assert!(serde_json::from_value::<PTab<usize, MinParams>>(json).is_err());
+  }
+
+  #[test]
+  fn sequence_format() {
+    let table: PTab<usize, MinParams> = PTab::new();
+    let key: Detached = table.insert(7).unwrap();
+
+    let json: Value = serde_json::to_value(&table).unwrap();
+    let list: Value = Value::Array(vec![
+      json["table"].clone(),
+      json["capacity"].clone(),
+      json["next_id"].clone(),
+      json["free_id"].clone(),
+      json["free"].clone(),
+      json["entries"].clone(),
+    ]);
+
+    let restored: PTab<usize, MinParams> = serde_json::from_value(list).unwrap();
+
+    assert_eq!(restored.read(key), Some(7));
+  }
+}
@@ -0,0 +1,542 @@
+/// A snapshot of the operation counters of a [`PTab`].
+///
+/// Returned by [`PTab::stats`]. Counters are collected with relaxed atomics
+/// and summed on demand, so a snapshot taken while other threads operate on
+/// the table is approximate; once the table is quiescent it is exact.
+///
+/// Lookups ([`PTab::with`], [`PTab::exists`], etc.) are never counted; they
+/// remain free of shared memory writes.
+///
+/// Without the `std` feature, all threads share a single set of counters.
+///
+/// # Examples
+///
+/// ```
+/// use ptab::PTab;
+///
+/// let table: PTab<i32> = PTab::new();
+/// let index = table.insert(1).unwrap();
+///
+/// table.remove(index);
+///
+/// let stats = table.stats();
+///
+/// assert_eq!(stats.inserts(), 1);
+/// assert_eq!(stats.removes(), 1);
+/// assert_eq!(stats.high_water(), 1);
+/// ```
+///
+/// [`PTab`]: crate::public::PTab
+/// [`PTab::stats`]: crate::public::PTab::stats
+/// [`PTab::with`]: crate::public::PTab::with
+/// [`PTab::exists`]: crate::public::PTab::exists
+/// [`pending_reclaim`]: Self::pending_reclaim
+/// [`removes`]: Self::removes
+#[cfg(feature = "stats")]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Stats {
+  pub(crate) inserts: usize,
+  pub(crate) failed_inserts: usize,
+  pub(crate) removes: usize,
+  pub(crate) reserve_retries: usize,
+  pub(crate) acquire_retries: usize,
+  pub(crate) release_retries: usize,
+  pub(crate) high_water: usize,
+  pub(crate) pending_reclaim: usize,
+}
+
+#[cfg(feature = "stats")]
+impl Stats {
+  /// Returns the number of successful inserts.
+  #[inline]
+  pub const fn inserts(&self) -> usize {
+    self.inserts
+  }
+
+  /// Returns the number of inserts rejected because the table was full.
+  #[inline]
+  pub const fn failed_inserts(&self) -> usize {
+    self.failed_inserts
+  }
+
+  /// Returns the number of successful removals.
+  #[inline]
+  pub const fn removes(&self) -> usize {
+    self.removes
+  }
+
+  /// Returns the number of times a rejected insert had to retry rolling back
+  /// the entry count.
+  #[inline]
+  pub const fn reserve_retries(&self) -> usize {
+    self.reserve_retries
+  }
+
+  /// Returns the number of times an insert found its free-list slot still
+  /// reserved by a concurrent operation and had to move on.
+  #[inline]
+  pub const fn acquire_retries(&self) -> usize {
+    self.acquire_retries
+  }
+
+  /// Returns the number of times a removal had to retry publishing its slot
+  /// to the free list.
+  #[inline]
+  pub const fn release_retries(&self) -> usize {
+    self.release_retries
+  }
+
+  /// Returns the highest number of entries observed in the table.
+  #[inline]
+  pub const fn high_water(&self) -> usize {
+    self.high_water
+  }
+
+  /// Returns the number of removed values whose memory has not yet been
+  /// reclaimed.
+  #[inline]
+  pub const fn pending_reclaim(&self) -> usize {
+    self.pending_reclaim
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Counters
+// -----------------------------------------------------------------------------
+
+#[cfg(feature = "stats")]
+pub(crate) use self::enabled::Counters;
+
+#[cfg(not(feature = "stats"))]
+pub(crate) use self::disabled::Counters;
+
+#[cfg(feature = "stats")]
+mod enabled {
+  // Statistics are diagnostic only and never synchronize table state, so plain
+  // `core` atomics are used even when testing with loom/shuttle.
+  use alloc::boxed::Box;
+  use core::ptr;
+  use core::sync::atomic::AtomicPtr;
+  use core::sync::atomic::AtomicUsize;
+  use core::sync::atomic::Ordering::AcqRel;
+  use core::sync::atomic::Ordering::Acquire;
+  use core::sync::atomic::Ordering::Relaxed;
+  use core::sync::atomic::Ordering::Release;
+  use core::sync::atomic::fence;
+
+  use crate::padded::CachePadded;
+  use crate::reclaim::Guard;
+  use crate::stats::Stats;
+
+  /// The number of independent counter cells.
+  const STRIPES: usize = 16;
+
+  /// Assigns each thread a fixed stripe, spreading threads round-robin.
+  #[cfg(feature = "std")]
+  #[inline]
+  fn stripe() -> usize {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+    std::thread_local! {
+      static STRIPE: usize = NEXT.fetch_add(1, Relaxed) % STRIPES;
+    }
+
+    STRIPE.with(|stripe| *stripe)
+  }
+
+  /// Without thread-locals every thread shares the first stripe, so the
+  /// counters are not striped.
+  #[cfg(not(feature = "std"))]
+  #[inline]
+  fn stripe() -> usize {
+    0
+  }
+
+  struct Stripe {
+    inserts: AtomicUsize,
+    failed_inserts: AtomicUsize,
+    removes: AtomicUsize,
+    /// The number of values removed through this stripe and not yet
+    /// reclaimed, plus one while the table is alive.
+    ///
+    /// Doubles as the reference count of the stripe, so deferred reclamation
+    /// never touches a counter shared by all threads.
+    pending: AtomicUsize,
+    reserve_retries: AtomicUsize,
+    acquire_retries: AtomicUsize,
+    release_retries: AtomicUsize,
+  }
+
+  impl Stripe {
+    const fn new() -> Self {
+      Self {
+        inserts: AtomicUsize::new(0),
+        failed_inserts: AtomicUsize::new(0),
+        removes: AtomicUsize::new(0),
+        pending: AtomicUsize::new(1),
+        reserve_retries: AtomicUsize::new(0),
+        acquire_retries: AtomicUsize::new(0),
+        release_retries: AtomicUsize::new(0),
+      }
+    }
+  }
+
+  /// The counter cells, freed once the table and every pending reclamation
+  /// released them.
+  struct Stripes {
+    cells: [CachePadded<Stripe>; STRIPES],
+    /// The number of stripes with a non-zero `pending` count.
+    live: AtomicUsize,
+  }
+
+  impl Stripes {
+    /// Releases one reference to the stripe at `index`.
+    ///
+    /// # Safety
+    ///
+    /// `this` must point to a live allocation from `Counters::stripes`, and
+    /// the caller must own the released reference.
+    unsafe fn release(this: *mut Self, index: usize) {
+      // SAFETY: The caller owns a reference, so the allocation is alive.
+      let stripes: &Self = unsafe { &*this };
+
+      if stripes.cells[index].pending.fetch_sub(1, Release) != 1 {
+        return;
+      }
+
+      fence(Acquire);
+
+      if stripes.live.fetch_sub(1, AcqRel) == 1 {
+        // SAFETY: Every stripe was released, and no stripe gains references
+        //         once the table released its own, so this is the last access.
+        drop(unsafe { Box::from_raw(this) });
+      }
+    }
+  }
+
+  /// Per-thread striped operation counters.
+  ///
+  /// Each thread only ever writes to its own stripe, so updates never contend
+  /// with other threads unless more than `STRIPES` threads are active.
+  pub(crate) struct Counters {
+    // Shared with deferred reclamation callbacks, which may outlive the table.
+    // Holds a pointer from `Box::into_raw`, allocated on first use so tables
+    // can be created in `const` contexts.
+    stripes: AtomicPtr<Stripes>,
+    high_water: AtomicUsize,
+  }
+
+  impl Counters {
+    #[inline]
+    pub(crate) const fn new() -> Self {
+      Self {
+        stripes: AtomicPtr::new(ptr::null_mut()),
+        high_water: AtomicUsize::new(0),
+      }
+    }
+
+    #[inline]
+    fn stripes(&self) -> *mut Stripes {
+      let stripes: *mut Stripes = self.stripes.load(Acquire);
+
+      if !stripes.is_null() {
+        return stripes;
+      }
+
+      let fresh: *mut Stripes = Box::into_raw(Box::new(Stripes {
+        cells: [const { CachePadded::new(Stripe::new()) }; STRIPES],
+        live: AtomicUsize::new(STRIPES),
+      }));
+
+      match self
+        .stripes
+        .compare_exchange(ptr::null_mut(), fresh, AcqRel, Acquire)
+      {
+        Ok(_) => fresh,
+        Err(current) => {
+          // SAFETY: `fresh` came from `Box::into_raw` and was never shared.
+          drop(unsafe { Box::from_raw(fresh) });
+          current
+        }
+      }
+    }
+
+    #[inline]
+    fn cells(&self) -> &[CachePadded<Stripe>; STRIPES] {
+      // SAFETY: The pointer is non-null and the counters hold a reference to
+      //         every stripe until they are dropped.
+      unsafe { &(*self.stripes()).cells }
+    }
+
+    #[inline]
+    fn local(&self) -> &Stripe {
+      &self.cells()[stripe()]
+    }
+
+    #[inline]
+    pub(crate) fn insert(&self, len: usize) {
+      self.local().inserts.fetch_add(1, Relaxed);
+
+      // Avoid writing the shared cache line unless we set a new record.
+      if len > self.high_water.load(Relaxed) {
+        self.high_water.fetch_max(len, Relaxed);
+      }
+    }
+
+    #[inline]
+    pub(crate) fn insert_failed(&self) {
+      self.local().failed_inserts.fetch_add(1, Relaxed);
+    }
+
+    /// Counts a removal, and its reclamation once the current guards are
+    /// dropped.
+    #[inline]
+    pub(crate) fn remove(&self) {
+      let index: usize = stripe();
+      let stripe: &Stripe = &self.cells()[index];
+
+      stripe.removes.fetch_add(1, Relaxed);
+      stripe.pending.fetch_add(1, Relaxed);
+
+      let stripes: *mut Stripes = self.stripes();
+
+      // Deferred after the removed value is retired, so this runs once the
+      // readers that could still observe the value are gone.
+      Guard::new().defer(move || {
+        // SAFETY: The reference taken above keeps the stripes alive, and is
+        //         released exactly once.
+        unsafe { Stripes::release(stripes, index) }
+      });
+    }
+
+    #[inline]
+    pub(crate) fn reserve_retry(&self) {
+      self.local().reserve_retries.fetch_add(1, Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn acquire_retry(&self) {
+      self.local().acquire_retries.fetch_add(1, Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn release_retry(&self) {
+      self.local().release_retries.fetch_add(1, Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> Stats {
+      let mut stats: Stats = Stats::default();
+      let mut pending: usize = 0;
+
+      for stripe in self.cells() {
+        stats.inserts += stripe.inserts.load(Relaxed);
+        stats.failed_inserts += stripe.failed_inserts.load(Relaxed);
+        stats.removes += stripe.removes.load(Relaxed);
+        stats.reserve_retries += stripe.reserve_retries.load(Relaxed);
+        stats.acquire_retries += stripe.acquire_retries.load(Relaxed);
+        stats.release_retries += stripe.release_retries.load(Relaxed);
+
+        // Excludes the reference held by the table itself.
+        pending += stripe.pending.load(Relaxed) - 1;
+      }
+
+      stats.high_water = self.high_water.load(Relaxed);
+      stats.pending_reclaim = pending;
+      stats
+    }
+  }
+
+  impl Drop for Counters {
+    fn drop(&mut self) {
+      let stripes: *mut Stripes = *self.stripes.get_mut();
+
+      if stripes.is_null() {
+        return;
+      }
+
+      for index in 0..STRIPES {
+        // SAFETY: The counters own one reference to every stripe, each of
+        //         which is released exactly once.
+        unsafe { Stripes::release(stripes, index) }
+      }
+    }
+  }
+}
+
+#[cfg(not(feature = "stats"))]
+mod disabled {
+  /// No-op counters used when the `stats` feature is disabled.
+  pub(crate) struct Counters;
+
+  impl Counters {
+    #[inline]
+    pub(crate) const fn new() -> Self {
+      Self
+    }
+
+    #[inline]
+    pub(crate) fn insert(&self, _len: usize) {}
+
+    #[inline]
+    pub(crate) fn insert_failed(&self) {}
+
+    #[inline]
+    pub(crate) fn remove(&self) {}
+
+    #[inline]
+    pub(crate) fn reserve_retry(&self) {}
+
+    #[inline]
+    pub(crate) fn acquire_retry(&self) {}
+
+    #[inline]
+    pub(crate) fn release_retry(&self) {}
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(all(feature = "stats", not(any(loom, shuttle))))]
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+  use std::sync::Barrier;
+  use std::thread;
+  use std::thread::JoinHandle;
+
+  use crate::index::Detached;
+  use crate::params::Capacity;
+  use crate::params::ConstParams;
+  use crate::reclaim;
+  use crate::stats::Stats;
+  use crate::table::Table;
+
+  type DefParams = ConstParams<{ Capacity::DEF.as_usize() }>;
+  type MinParams = ConstParams<{ Capacity::MIN.as_usize() }>;
+
+  const THREADS: usize = 8;
+
+  #[test]
+  fn empty() {
+    let table: Table<usize, DefParams> = Table::new();
+
+    assert_eq!(table.stats(), Stats::default());
+  }
+
+  #[test]
+  fn inserts_and_removes() {
+    let table: Table<usize, DefParams> = Table::new();
+    let mut keys: Vec<Detached> = Vec::with_capacity(8);
+
+    for index in 0..8 {
+      keys.push(table.insert(index).unwrap());
+    }
+
+    for key in keys.drain(..4) {
+      assert!(table.remove(key));
+    }
+
+    assert!(!table.remove(Detached::from_bits(0)));
+
+    let stats: Stats = table.stats();
+
+    assert_eq!(stats.inserts(), 8);
+    assert_eq!(stats.removes(), 4);
+    assert_eq!(stats.failed_inserts(), 0);
+    assert_eq!(stats.high_water(), 8);
+  }
+
+  #[test]
+  fn failed_inserts() {
+    let table: Table<usize, MinParams> = Table::new();
+
+    for index in 0..table.cap() {
+      assert!(table.insert(index).is_some());
+    }
+
+    assert!(table.insert(0).is_none());
+    assert!(table.insert(0).is_none());
+
+    let stats: Stats = table.stats();
+
+    assert_eq!(stats.inserts(), table.cap());
+    assert_eq!(stats.failed_inserts(), 2);
+    assert_eq!(stats.high_water(), table.cap());
+  }
+
+  #[test]
+  fn high_water_is_sticky() {
+    let table: Table<usize, DefParams> = Table::new();
+    let a: Detached = table.insert(1).unwrap();
+    let b: Detached = table.insert(2).unwrap();
+
+    assert!(table.remove(a));
+    assert!(table.remove(b));
+    assert!(table.insert(3).is_some());
+
+    assert_eq!(table.stats().high_water(), 2);
+  }
+
+  #[test]
+  fn pending_reclaim() {
+    let table: Table<usize, DefParams> = Table::new();
+    let index: Detached = table.insert(1).unwrap();
+
+    assert!(table.remove(index));
+    assert!(table.stats().pending_reclaim() <= 1);
+
+    reclaim::try_reclaim();
+
+    assert_eq!(table.stats().pending_reclaim(), 0);
+  }
+
+  #[test]
+  fn lookups_are_not_counted() {
+    let table: Table<usize, DefParams> = Table::new();
+    let index: Detached = table.insert(1).unwrap();
+    let before: Stats = table.stats();
+
+    for _ in 0..16 {
+      assert_eq!(table.read(index, &reclaim::Guard::new()), Some(1));
+      assert!(table.exists(index, &reclaim::Guard::new()));
+    }
+
+    assert_eq!(table.stats(), before);
+  }
+
+  #[test]
+  fn concurrent_totals() {
+    let table: Arc<Table<usize, DefParams>> = Arc::new(Table::new());
+    let barrier: Arc<Barrier> = Arc::new(Barrier::new(THREADS));
+
+    let threads: Vec<JoinHandle<()>> = (0..THREADS)
+      .map(|_| {
+        let table: Arc<Table<usize, DefParams>> = Arc::clone(&table);
+        let barrier: Arc<Barrier> = Arc::clone(&barrier);
+
+        thread::spawn(move || {
+          barrier.wait();
+
+          for index in 0..128 {
+            let key: Detached = table.insert(index).unwrap();
+            assert!(table.remove(key));
+          }
+        })
+      })
+      .collect();
+
+    for thread in threads {
+      thread.join().unwrap();
+    }
+
+    let stats: Stats = table.stats();
+
+    assert_eq!(stats.inserts(), THREADS * 128);
+    assert_eq!(stats.removes(), THREADS * 128);
+    assert!(stats.high_water() >= 1);
+    assert!(stats.high_water() <= THREADS);
+  }
+}
@@ -1,46 +1,88 @@
+use alloc::sync::Arc;
+use alloc::vec::Vec;
 use core::fmt::Debug;
 use core::fmt::DebugMap;
 use core::fmt::Formatter;
 use core::fmt::Result as FmtResult;
 use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
 use core::mem::MaybeUninit;
 use core::panic::RefUnwindSafe;
 use core::panic::UnwindSafe;
+use core::ptr;
 use core::ptr::NonNull;
+#[cfg(feature = "std")]
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+#[cfg(feature = "std")]
+use std::time::Instant;
 
 use crate::array::Array;
+use crate::entry::Entry;
+use crate::error::InsertAtError;
+#[cfg(feature = "std")]
+use crate::expiry::Expiry;
+use crate::flags::Flags;
+use crate::hooks::Handler;
+use crate::hooks::Hooks;
 use crate::index::Abstract;
 use crate::index::Concrete;
 use crate::index::Detached;
-use crate::padded::CachePadded;
-use crate::params::CACHE_LINE_SLOTS;
-use crate::params::Capacity;
+use crate::index::slot_to_id;
+use crate::padded::Line;
 use crate::params::Params;
 use crate::params::ParamsExt;
-use crate::reclaim::sdd::Atomic;
-use crate::reclaim::sdd::Guard;
-use crate::reclaim::sdd::Shared;
+use crate::reason::Reason;
+use crate::reclaim::Atomic;
+use crate::reclaim::Guard;
+use crate::reclaim::Owned;
+use crate::reclaim::Shared;
+#[cfg(feature = "std")]
+use crate::seal::Sealer;
+#[cfg(feature = "std")]
+use crate::seal::Token;
+#[cfg(feature = "serde")]
+use crate::snapshot::Snapshot;
+use crate::stats::Counters;
 use crate::sync::atomic::AtomicU32;
 use crate::sync::atomic::AtomicUsize;
 use crate::sync::atomic::Ordering::AcqRel;
 use crate::sync::atomic::Ordering::Acquire;
 use crate::sync::atomic::Ordering::Relaxed;
 use crate::sync::atomic::Ordering::Release;
+use crate::watch::Link;
+use crate::watch::Watch;
 
 /// Marker indicating a slot is reserved for an in-progress allocation.
-const RESERVED: usize = usize::MAX;
+pub(crate) const RESERVED: usize = usize::MAX;
+
+/// Source of table ids, see [`Params::TABLE_ID_BITS`].
+///
+/// This is intentionally not modeled by `loom`/`shuttle`: table ids only need
+/// to differ between tables, not to synchronize anything. Id `0` is reserved
+/// for tables created in `const` contexts.
+static TABLE_IDS: core::sync::atomic::AtomicUsize = core::sync::atomic::AtomicUsize::new(1);
 
 // -----------------------------------------------------------------------------
 // Table State
 // -----------------------------------------------------------------------------
 
 #[repr(C)]
-pub(crate) struct Table<T, P>
+pub(crate) struct Table<T, P, S = ReadOnly<T, P>>
 where
   P: Params + ?Sized,
+  S: Storage<T, P>,
 {
-  volatile: CachePadded<Volatile<P>>,
-  readonly: CachePadded<ReadOnly<T, P>>,
+  readonly: S,
+  counters: Counters,
+  handler: Option<Arc<Handler<T>>>,
+  #[cfg(feature = "std")]
+  expiry: OnceLock<Expiry<P>>,
+  #[cfg(feature = "std")]
+  sealer: OnceLock<Sealer>,
+  tag: usize,
+  phantom: PhantomData<fn(P)>,
 }
 
 impl<T, P> Table<T, P>
@@ -49,36 +91,288 @@ where
 {
   #[inline]
   pub(crate) fn new() -> Self {
+    Self::with_handler(None)
+  }
+
+  #[inline]
+  pub(crate) fn with_hooks<H>(hooks: H) -> Self
+  where
+    T: 'static,
+    H: Hooks<T> + 'static,
+  {
+    Self::with_handler(Some(Arc::new(Handler::new(hooks))))
+  }
+
+  #[inline]
+  fn with_handler(handler: Option<Arc<Handler<T>>>) -> Self {
+    Self::from_parts(ReadOnly::new(), handler, next_tag::<P>())
+  }
+
+  #[inline]
+  fn from_parts(readonly: ReadOnly<T, P>, handler: Option<Arc<Handler<T>>>, tag: usize) -> Self {
+    Self {
+      readonly,
+      counters: Counters::new(),
+      handler,
+      #[cfg(feature = "std")]
+      expiry: OnceLock::new(),
+      #[cfg(feature = "std")]
+      sealer: OnceLock::new(),
+      tag,
+      phantom: PhantomData,
+    }
+  }
+
+  /// Rebuilds a table from a snapshot taken by [`Table::snapshot`].
+  ///
+  /// Every entry is restored in its original slot and generation, and the
+  /// table keeps the id of the snapshotted table, so the indices it issued
+  /// resolve to the same values.
+  #[cfg(feature = "serde")]
+  pub(crate) fn restore(snapshot: Snapshot<T>) -> Result<Self, &'static str>
+  where
+    T: 'static,
+  {
+    let Snapshot {
+      table,
+      capacity,
+      next_id,
+      free_id,
+      free,
+      entries,
+    } = snapshot;
+
+    if capacity != P::LENGTH.as_usize() {
+      return Err("snapshot does not match table capacity");
+    }
+
+    if table & P::ID_MASK_LOCAL != 0
+      || entries
+        .iter()
+        .any(|(key, _)| key.into_bits() & !P::ID_MASK_LOCAL != table)
+    {
+      return Err("snapshot does not match table id");
+    }
+
+    if next_id.wrapping_sub(free_id) as usize != entries.len()
+      || entries.len() > P::ENTRIES_LIMIT as usize
+    {
+      return Err("snapshot allocator state is inconsistent");
+    }
+
+    // Ring positions missing from the snapshot still hold their initial id.
+    let mut moved: Vec<Option<usize>> = alloc::vec![None; P::LENGTH.as_usize()];
+
+    for (position, id) in free {
+      if moved
+        .get_mut(position)
+        .is_none_or(|slot| slot.replace(id).is_some())
+      {
+        return Err("snapshot allocator state is inconsistent");
+      }
+    }
+
+    let mut ring: Vec<usize> = Vec::with_capacity(P::LENGTH.as_usize() - entries.len());
+
+    for offset in 0..P::LENGTH.as_usize() - entries.len() {
+      let position: usize = (next_id as usize).wrapping_add(offset) & P::ID_MASK_ENTRY;
+      let concrete_idx: Concrete<P> = Concrete::from_abstract(Abstract::new(position));
+
+      ring.push(moved[position].take().unwrap_or(initial_id(concrete_idx)));
+    }
+
+    // Every recorded position must lie within the free part of the ring.
+    if moved.iter().any(Option::is_some) {
+      return Err("snapshot allocator state is inconsistent");
+    }
+
+    let mut used: Vec<bool> = alloc::vec![false; P::LENGTH.as_usize()];
+
+    let mut claim = |index: usize| -> bool { !core::mem::replace(&mut used[index], true) };
+
+    for (key, _) in &entries {
+      if !claim(Concrete::<P>::from_detached(*key).get()) {
+        return Err("snapshot contains duplicate slots");
+      }
+    }
+
+    for id in &ring {
+      if *id == RESERVED || !claim(Concrete::from_abstract(Abstract::<P>::new(*id)).get()) {
+        return Err("snapshot contains duplicate slots");
+      }
+    }
+
+    let mut this: Self = Self::new();
+    this
+      .volatile()
+      .entries
+      .store(P::ENTRIES_BASE.wrapping_add(entries.len() as u32), Relaxed);
+    this.volatile().next_id.store(next_id, Relaxed);
+    this.volatile().free_id.store(free_id, Relaxed);
+
+    this.tag = table;
+
+    for index in 0..P::LENGTH.as_usize() {
+      this.readonly.store_slot(Concrete::new(index), RESERVED);
+    }
+
+    for (offset, id) in ring.into_iter().enumerate() {
+      let abstract_idx: Abstract<P> = Abstract::new((next_id as usize).wrapping_add(offset));
+      let concrete_idx: Concrete<P> = Concrete::from_abstract(abstract_idx);
+
+      this.readonly.store_slot(concrete_idx, id);
+    }
+
+    for (key, value) in entries {
+      let key: Detached = Detached::from_bits((key.into_bits() & P::ID_MASK_LOCAL) | this.tag);
+
+      this.publish(key, |maybe| {
+        maybe.write(value);
+      });
+    }
+
+    Ok(this)
+  }
+
+  /// Moves the allocator state and arrays out of the table.
+  ///
+  /// Watchers of the remaining entries are notified as if the table had been
+  /// dropped, flags are cleared, and hooks are not invoked for the moved
+  /// values.
+  pub(crate) fn into_raw(self) -> RawTable<T, P> {
+    let len: u32 = self.len();
+    let next_id: u32 = self.volatile().next_id.load(Relaxed);
+    let free_id: u32 = self.volatile().free_id.load(Relaxed);
+    let tag: usize = self.tag;
+
+    let this: ManuallyDrop<Self> = ManuallyDrop::new(self);
+
+    // SAFETY: `this` is never dropped, so every field is moved out exactly
+    //         once, and `Table::drop` does not run for the moved arrays.
+    let (readonly, counters, handler) = unsafe {
+      (
+        ptr::read(&raw const this.readonly),
+        ptr::read(&raw const this.counters),
+        ptr::read(&raw const this.handler),
+      )
+    };
+
+    // SAFETY: See above.
+    #[cfg(feature = "std")]
+    let (expiry, sealer) = unsafe {
+      (
+        ptr::read(&raw const this.expiry),
+        ptr::read(&raw const this.sealer),
+      )
+    };
+
+    drop((counters, handler));
+
+    #[cfg(feature = "std")]
+    drop((expiry, sealer));
+
+    let ReadOnly {
+      volatile,
+      data,
+      slot,
+    } = readonly;
+
+    drop(volatile);
+
+    RawTable {
+      tag,
+      len,
+      next_id,
+      free_id,
+      slot: slot.map(|offset| offset.load(Relaxed)),
+      data: data.map(|entry| {
+        let mut entry: Option<Owned<Entry<T>>> = entry.into_owned();
+
+        if let Some(entry) = entry.as_mut() {
+          entry.clear_watchers();
+        }
+
+        entry
+      }),
+    }
+  }
+
+  /// Rebuilds a table around the arrays moved out by [`Table::into_raw`],
+  /// keeping the table id of `raw`.
+  pub(crate) fn from_raw(raw: RawTable<T, P>) -> Self
+  where
+    T: 'static,
+  {
+    let RawTable {
+      tag,
+      len,
+      next_id,
+      free_id,
+      slot,
+      data,
+    } = raw;
+
+    let volatile: Volatile<P> = Volatile::new();
+
+    volatile
+      .entries
+      .store(P::ENTRIES_BASE.wrapping_add(len), Relaxed);
+    volatile.next_id.store(next_id, Relaxed);
+    volatile.free_id.store(free_id, Relaxed);
+
+    let readonly: ReadOnly<T, P> = ReadOnly {
+      volatile: Line::new(volatile),
+      data: data.map(Atomic::from_owned),
+      slot: slot.map(AtomicUsize::new),
+    };
+
+    Self::from_parts(readonly, None, tag)
+  }
+}
+
+impl<T, P, S> Table<T, P, S>
+where
+  P: Params + ?Sized,
+  S: Storage<T, P>,
+{
+  /// Creates a table backed by `storage`, which must be zeroed.
+  ///
+  /// Usable in `const` contexts, so tables created this way have no hooks and
+  /// always use table id `0`.
+  #[cfg(not(any(loom, shuttle)))]
+  #[inline]
+  pub(crate) const fn with_storage(storage: S) -> Self {
     Self {
-      volatile: CachePadded::new(Volatile::new()),
-      readonly: CachePadded::new(ReadOnly::new()),
+      readonly: storage,
+      counters: Counters::new(),
+      handler: None,
+      #[cfg(feature = "std")]
+      expiry: OnceLock::new(),
+      #[cfg(feature = "std")]
+      sealer: OnceLock::new(),
+      tag: 0,
+      phantom: PhantomData,
     }
   }
 
   #[inline]
   pub(crate) const fn cap(&self) -> usize {
-    // See `Volatile::new`
-    if P::LENGTH.as_usize() == Capacity::MAX.as_usize() {
-      P::LENGTH.as_usize().wrapping_sub(1)
-    } else {
-      P::LENGTH.as_usize()
-    }
+    P::ENTRIES_LIMIT as usize
   }
 
   #[inline]
-  pub(crate) fn len(&self) -> u32 {
-    let mut len: u32 = self.volatile.load_entries();
-    let mut max: u32 = P::LENGTH.as_u32();
+  fn volatile(&self) -> &Volatile<P> {
+    self.readonly.volatile()
+  }
 
+  #[inline]
+  pub(crate) fn len(&self) -> u32 {
     // See `Volatile::new`
-    if max == Capacity::MAX.as_u32() {
-      len = len.wrapping_sub(1);
-      max = max.wrapping_sub(1);
-    }
+    let len: u32 = self.volatile().load_entries().wrapping_sub(P::ENTRIES_BASE);
 
     // We may see an invalid `len` from a concurrent insert attempt; fix it here
-    if len > max {
-      return max;
+    if len > P::ENTRIES_LIMIT {
+      return P::ENTRIES_LIMIT;
     }
 
     len
@@ -107,29 +401,220 @@ where
   {
     let claim_permit: Permit<'_, T, P> = self.reserve_slot()?;
     let abstract_idx: Abstract<P> = self.acquire_slot(claim_permit);
-    let concrete_idx: Concrete<P> = Concrete::from_abstract(abstract_idx);
-    let detached_idx: Detached = Detached::from_abstract(abstract_idx);
+    let detached_idx: Detached = self.detach(abstract_idx);
+
+    self.publish(detached_idx, |maybe| init(maybe, detached_idx));
 
+    Some(detached_idx)
+  }
+
+  /// Inserts `value` at exactly `key`, claiming its slot and generation.
+  ///
+  /// Requires exclusive access, since the claimed id has to be removed from
+  /// the middle of the free-id ring.
+  pub(crate) fn insert_at(&mut self, key: Detached, value: T) -> Result<(), InsertAtError>
+  where
+    T: 'static,
+  {
+    if key.into_bits() & !P::ID_MASK_LOCAL != self.tag {
+      return Err(InsertAtError::Foreign);
+    }
+
+    let concrete_idx: Concrete<P> = Concrete::from_detached(key);
+
+    if !self.load(concrete_idx, &Guard::new()).is_null() {
+      return Err(InsertAtError::Occupied);
+    }
+
+    if self.len() as usize >= self.cap() {
+      return Err(InsertAtError::Full);
+    }
+
+    let next_id: u32 = self.volatile().next_id.load(Relaxed);
+    let free_id: u32 = self.volatile().free_id.load(Relaxed);
+
+    // The free ids occupy the ring from `next_id` onwards; find the one that
+    // maps to the requested slot.
+    let Some(position) = (0..P::LENGTH.as_usize() - next_id.wrapping_sub(free_id) as usize)
+      .map(|offset| Concrete::from_abstract(Abstract::new((next_id as usize).wrapping_add(offset))))
+      .find(|position| {
+        let id: usize = self.readonly.load_slot(*position);
+        Concrete::from_abstract(Abstract::<P>::new(id)) == concrete_idx
+      })
+    else {
+      return Err(InsertAtError::Occupied);
+    };
+
+    if is_stale::<P>(key, self.readonly.load_slot(position)) {
+      return Err(InsertAtError::Stale);
+    }
+
+    // Move the id at the head of the ring into the claimed position, then
+    // consume the head as if it had been allocated normally.
+    let head: Concrete<P> = Concrete::from_abstract(Abstract::new(next_id as usize));
+    let data: usize = self.readonly.load_slot(head);
+
+    self.readonly.store_slot(position, data);
+    self.readonly.store_slot(head, RESERVED);
+
+    self.volatile().fetch_next_id();
+
+    let prev: u32 = self.volatile().incr_entries();
+    // See `Volatile::new`
+    let len: u32 = prev.wrapping_sub(P::ENTRIES_BASE).wrapping_add(1);
+    self.counters.insert(len as usize);
+
+    self.publish(key, |maybe| {
+      maybe.write(value);
+    });
+
+    Ok(())
+  }
+
+  /// Initializes the entry for `key` in its slot and reports the insertion.
+  #[inline]
+  fn publish<F>(&self, key: Detached, init: F)
+  where
+    T: 'static,
+    F: FnOnce(&mut MaybeUninit<T>),
+  {
     self
       .readonly
-      .data
-      .get(concrete_idx)
-      .write(Release, |maybe| init(maybe, detached_idx));
+      .entry(Concrete::from_detached(key))
+      .write(Release, |maybe| {
+        let value: &T = Entry::init(maybe, key, init);
 
-    Some(detached_idx)
+        if let Some(handler) = self.handler.as_deref() {
+          handler.on_insert(key, value);
+        }
+      });
   }
 
   #[inline]
   pub(crate) fn remove(&self, key: Detached) -> bool {
+    self.remove_with(key, Reason::Normal)
+  }
+
+  /// Removes the entry at `key`, reporting `reason` to hooks and watchers.
+  pub(crate) fn remove_with(&self, key: Detached, reason: Reason) -> bool {
+    let mut pending: Vec<(Detached, Reason)> = Vec::new();
+    let removed: bool = self.evict(key, reason, &mut pending);
+
+    // Cascade through linked entries iteratively, so long chains of links
+    // cannot overflow the stack.
+    while let Some((key, reason)) = pending.pop() {
+      self.evict(key, reason, &mut pending);
+    }
+
+    removed
+  }
+
+  /// Removes the entry at `key`, queueing its linked entries onto `pending`.
+  fn evict(&self, key: Detached, reason: Reason, pending: &mut Vec<(Detached, Reason)>) -> bool {
     let index: Concrete<P> = Concrete::from_detached(key);
-    let entry: &Atomic<T> = self.readonly.data.get(index);
+    let entry: &Atomic<Entry<T>> = self.readonly.entry(index);
+
+    let Some(evicted) = entry.evict_if(AcqRel, |entry| entry.key().matches::<P>(key)) else {
+      return false;
+    };
+
+    if let Some(handler) = self.handler.as_ref() {
+      handler.on_remove(key, evicted.get(), reason);
+    }
+
+    let watchers: Vec<Watch> = evicted.watchers().close(&Guard::new()).cloned().collect();
+
+    match self.handler.as_ref() {
+      Some(handler) => handler.retire(evicted),
+      None => drop(evicted),
+    }
+
+    self.counters.remove();
+
+    self.release_slot(Abstract::from_detached(Detached::from_bits(
+      key.into_bits() & P::ID_MASK_LOCAL,
+    )));
+
+    // Notify watchers once the slot is reusable, so they observe a consistent
+    // table if they react by operating on it.
+    for watch in watchers {
+      match watch {
+        Watch::Monitor(_) | Watch::Quota(_) => watch.fire(reason),
+        Watch::Link(link) => {
+          if link.sever() {
+            pending.push((link.peer(key), Reason::Linked(key)));
+          }
+        }
+      }
+    }
+
+    true
+  }
+
+  /// Inserts `value`, scheduling it to expire `ttl` from now.
+  #[cfg(feature = "std")]
+  pub(crate) fn insert_with_ttl(&self, value: T, ttl: Duration) -> Option<Detached>
+  where
+    T: 'static,
+  {
+    let key: Detached = self.insert(value)?;
+
+    self
+      .expiry
+      .get_or_init(Expiry::new)
+      .schedule(key, ttl, Instant::now());
+
+    Some(key)
+  }
 
-    if entry.evict(AcqRel) {
-      self.release_slot(Abstract::from_detached(key));
-      true
-    } else {
-      false
+  /// Extends the lease of the entry at `key` by its original TTL.
+  #[cfg(feature = "std")]
+  #[inline]
+  pub(crate) fn touch(&self, key: Detached) -> bool {
+    self.exists(key, &Guard::new())
+      && self
+        .expiry
+        .get()
+        .is_some_and(|expiry| expiry.touch(key, Instant::now()))
+  }
+
+  /// Returns the remaining lifetime of the entry at `key`.
+  #[cfg(feature = "std")]
+  #[inline]
+  pub(crate) fn ttl(&self, key: Detached) -> Option<Duration> {
+    if !self.exists(key, &Guard::new()) {
+      return None;
     }
+
+    self.expiry.get()?.remaining(key, Instant::now())
+  }
+
+  /// Removes every entry whose lease expired at or before `now`.
+  #[cfg(feature = "std")]
+  pub(crate) fn sweep(&self, now: Instant) -> usize {
+    let Some(expiry) = self.expiry.get() else {
+      return 0;
+    };
+
+    // Removal runs outside of the wheel lock, since hooks and watchers may
+    // insert new entries.
+    expiry
+      .expired(now)
+      .into_iter()
+      .filter(|key| self.remove_with(*key, Reason::Expired))
+      .count()
+  }
+
+  #[cfg(feature = "std")]
+  #[inline]
+  pub(crate) fn export(&self, key: Detached) -> Token {
+    self.sealer.get_or_init(Sealer::new).seal(key)
+  }
+
+  #[cfg(feature = "std")]
+  #[inline]
+  pub(crate) fn import(&self, token: Token) -> Option<Detached> {
+    self.sealer.get_or_init(Sealer::new).open(token)
   }
 
   #[inline]
@@ -137,7 +622,53 @@ where
   where
     F: Fn(&T) -> R,
   {
-    self.find(key, guard).as_ref().map(f)
+    self.find(key, guard).as_ref().map(|entry| f(entry.get()))
+  }
+
+  #[inline]
+  pub(crate) fn get<'guard>(&self, key: Detached, guard: &'guard Guard) -> Option<&'guard T> {
+    self.find(key, guard).as_ref().map(Entry::get)
+  }
+
+  #[inline]
+  pub(crate) fn with_flags<F, R>(&self, key: Detached, guard: &Guard, f: F) -> Option<R>
+  where
+    F: Fn(&T, Flags) -> R,
+  {
+    let entry: Shared<'_, Entry<T>> = self.find(key, guard);
+    let flags: Flags = Flags::from_bits_truncate(entry.tag());
+
+    entry.as_ref().map(|entry| f(entry.get(), flags))
+  }
+
+  /// Atomically updates the flags of the entry at `key` with `f`.
+  ///
+  /// Returns the previous flags if `f` returned `Some`, like
+  /// `AtomicU8::fetch_update`. Fails with [`None`] if the entry does not exist.
+  pub(crate) fn update_flags<F>(&self, key: Detached, mut f: F) -> Result<Flags, Option<Flags>>
+  where
+    F: FnMut(Flags) -> Option<Flags>,
+  {
+    let guard: Guard = Guard::new();
+    let atomic: &Atomic<Entry<T>> = self.readonly.entry(Concrete::from_detached(key));
+
+    loop {
+      let entry: Shared<'_, Entry<T>> = atomic.load(Acquire, &guard);
+
+      match entry.as_ref() {
+        Some(data) if data.key().matches::<P>(key) => {}
+        Some(_) | None => return Err(None),
+      }
+
+      let flags: Flags = Flags::from_bits_truncate(entry.tag());
+      let Some(next) = f(flags) else {
+        return Err(Some(flags));
+      };
+
+      if atomic.update_tag(&entry, next.bits(), AcqRel) {
+        return Ok(flags);
+      }
+    }
   }
 
   #[inline]
@@ -153,88 +684,251 @@ where
     self.with(key, guard, |data| *data)
   }
 
+  /// Attaches `watch` to the entry at `key`, returning it if the entry does
+  /// not exist or is already being removed.
   #[inline]
-  pub(crate) fn weak_keys(&self, guard: Guard) -> WeakKeys<'_, T, P> {
-    WeakKeys::new(guard, self)
-  }
+  pub(crate) fn watch(&self, key: Detached, watch: Watch) -> Result<(), Watch> {
+    let guard: Guard = Guard::new();
 
-  #[inline]
-  fn find<'guard>(&self, key: Detached, guard: &'guard Guard) -> Shared<'guard, T> {
-    self.load(Concrete::from_detached(key), guard)
+    match self.find(key, &guard).as_ref() {
+      Some(entry) => entry.watchers().push(watch, &guard),
+      None => Err(watch),
+    }
   }
 
+  /// Returns `true` if a watch attached to the entry at `key` satisfies `f`.
   #[inline]
-  fn load<'guard>(&self, index: Concrete<P>, guard: &'guard Guard) -> Shared<'guard, T> {
-    self.readonly.data.get(index).load(Acquire, guard)
+  pub(crate) fn watched<F>(&self, key: Detached, f: F) -> bool
+  where
+    F: FnMut(&Watch) -> bool,
+  {
+    let guard: Guard = Guard::new();
+
+    self
+      .find(key, &guard)
+      .as_ref()
+      .is_some_and(|entry| entry.watchers().iter(&guard).any(f))
   }
 
-  #[inline]
-  fn reserve_slot(&self) -> Option<Permit<'_, T, P>> {
-    let prev: u32 = self.volatile.incr_entries();
+  /// Links the entries at `a` and `b`, returning `false` if either does not
+  /// exist.
+  pub(crate) fn link(&self, a: Detached, b: Detached) -> bool {
+    let guard: Guard = Guard::new();
 
-    if prev < P::LENGTH.as_u32() {
-      return Some(Permit::new(self));
+    let (Some(this), Some(that)) = (self.find(a, &guard).as_ref(), self.find(b, &guard).as_ref())
+    else {
+      return false;
+    };
+
+    if a == b || Self::linked(this, a, b, &guard) {
+      return true;
     }
 
-    // Table is full; undo the increment.
-    let mut current: u32 = prev.wrapping_add(1);
+    let link: Arc<Link> = Arc::new(Link::new(a, b));
 
-    while let Err(next) = self.volatile.swap_entries(current, current.wrapping_sub(1)) {
-      current = next;
+    if this
+      .watchers()
+      .push(Watch::Link(Arc::clone(&link)), &guard)
+      .is_err()
+    {
+      return false;
     }
 
-    None
+    // `b` was removed before the link was established; disable the half
+    // attached to `a` so it does not cascade.
+    if that
+      .watchers()
+      .push(Watch::Link(Arc::clone(&link)), &guard)
+      .is_err()
+    {
+      link.sever();
+      return false;
+    }
+
+    true
   }
 
-  #[inline]
-  fn acquire_slot(&self, _permit: Permit<'_, T, P>) -> Abstract<P> {
-    loop {
-      let abstract_idx: Abstract<P> = self.volatile.fetch_next_id();
-      let concrete_idx: Concrete<P> = Concrete::from_abstract(abstract_idx);
+  /// Removes the link between the entries at `a` and `b`, returning `true` if
+  /// they were linked.
+  pub(crate) fn unlink(&self, a: Detached, b: Detached) -> bool {
+    let guard: Guard = Guard::new();
 
-      let atomic: &AtomicUsize = self.readonly.slot.get(concrete_idx);
-      let result: usize = atomic.swap(RESERVED, Relaxed);
+    let Some(entry) = self.find(a, &guard).as_ref() else {
+      return false;
+    };
 
-      if result == RESERVED {
-        continue;
+    let mut unlinked: bool = false;
+
+    for watch in entry.watchers().iter(&guard) {
+      if let Watch::Link(link) = watch
+        && link.connects(a, b)
+      {
+        unlinked |= link.sever();
       }
+    }
 
-      break Abstract::new(result);
+    // Drop the severed halves from both ends, so repeatedly linking and
+    // unlinking the same entries does not grow their watcher lists.
+    if unlinked {
+      entry.watchers().compact(&guard);
+
+      if let Some(peer) = self.find(b, &guard).as_ref() {
+        peer.watchers().compact(&guard);
+      }
     }
+
+    unlinked
   }
 
   #[inline]
-  fn release_slot(&self, index: Abstract<P>) {
-    let data: usize = self.generate_next_slot(index);
-
-    while self
-      .readonly
-      .slot
-      .get(Concrete::from_abstract(self.volatile.fetch_free_id()))
-      .compare_exchange_weak(RESERVED, data, Relaxed, Relaxed)
-      .is_err()
-    {}
-
-    self.volatile.decr_entries();
+  fn linked(entry: &Entry<T>, a: Detached, b: Detached, guard: &Guard) -> bool {
+    entry.watchers().iter(guard).any(|watch| match watch {
+      Watch::Link(link) => link.connects(a, b),
+      Watch::Monitor(_) | Watch::Quota(_) => false,
+    })
   }
 
   #[inline]
-  const fn generate_next_slot(&self, index: Abstract<P>) -> usize {
-    let mut data: usize = index.get();
+  pub(crate) fn weak_keys(&self, guard: Guard) -> WeakKeys<'_, T, P> {
+    WeakKeys::new(guard, self)
+  }
 
-    data = data.wrapping_add(P::LENGTH.as_usize());
+  /// Captures the occupied entries and allocator state of the table.
+  ///
+  /// The result is only consistent if no other thread modifies the table
+  /// while the snapshot is taken.
+  #[cfg(feature = "serde")]
+  pub(crate) fn snapshot<'guard>(&self, guard: &'guard Guard) -> Snapshot<&'guard T> {
+    let next_id: u32 = self.volatile().load_next_id() as u32;
+    let free_id: u32 = self.volatile().load_free_id() as u32;
+
+    // The ring holds the free ids in allocation order, starting at `next_id`.
+    // Only positions whose id moved past the initial one are recorded.
+    let used: usize = next_id.wrapping_sub(free_id) as usize;
+    let free: Vec<(usize, usize)> = (0..P::LENGTH.as_usize().saturating_sub(used))
+      .filter_map(|offset| {
+        let position: usize = (next_id as usize).wrapping_add(offset) & P::ID_MASK_ENTRY;
+        let concrete_idx: Concrete<P> = Concrete::from_abstract(Abstract::new(position));
+        let id: usize = self.readonly.load_slot(concrete_idx);
+
+        (id != initial_id(concrete_idx)).then_some((position, id))
+      })
+      .collect();
 
-    if data == RESERVED {
-      data = data.wrapping_add(P::LENGTH.as_usize());
+    let entries: Vec<(Detached, &'guard T)> = self
+      .readonly
+      .data()
+      .iter()
+      .filter_map(|entry| entry.load(Acquire, guard).as_ref())
+      .map(|entry| (entry.key(), entry.get()))
+      .collect();
+
+    Snapshot {
+      table: self.tag,
+      capacity: P::LENGTH.as_usize(),
+      next_id,
+      free_id,
+      free,
+      entries,
     }
+  }
 
-    data
+  #[cfg(feature = "stats")]
+  #[inline]
+  pub(crate) fn stats(&self) -> crate::stats::Stats {
+    self.counters.snapshot()
   }
-}
 
-impl<T, P> Drop for Table<T, P>
-where
+  #[inline]
+  fn find<'guard>(&self, key: Detached, guard: &'guard Guard) -> Shared<'guard, Entry<T>> {
+    let entry: Shared<'guard, Entry<T>> = self.load(Concrete::from_detached(key), guard);
+
+    // Reject stale indices whose slot has since been reused.
+    match entry.as_ref() {
+      Some(data) if !data.key().matches::<P>(key) => Shared::null(),
+      Some(_) | None => entry,
+    }
+  }
+
+  #[inline]
+  fn load<'guard>(&self, index: Concrete<P>, guard: &'guard Guard) -> Shared<'guard, Entry<T>> {
+    self.readonly.entry(index).load(Acquire, guard)
+  }
+
+  #[inline]
+  fn reserve_slot(&self) -> Option<Permit<'_, T, P>> {
+    let prev: u32 = self.volatile().incr_entries();
+
+    if prev < P::LENGTH.as_u32() {
+      // See `Volatile::new`
+      let len: u32 = prev.wrapping_sub(P::ENTRIES_BASE).wrapping_add(1);
+      self.counters.insert(len as usize);
+      return Some(Permit::new(self));
+    }
+
+    // Table is full; undo the increment.
+    let mut current: u32 = prev.wrapping_add(1);
+
+    while let Err(next) = self
+      .volatile()
+      .swap_entries(current, current.wrapping_sub(1))
+    {
+      self.counters.reserve_retry();
+      current = next;
+    }
+
+    self.counters.insert_failed();
+
+    None
+  }
+
+  #[inline]
+  fn acquire_slot(&self, _permit: Permit<'_, T, P>) -> Abstract<P> {
+    loop {
+      let abstract_idx: Abstract<P> = self.volatile().fetch_next_id();
+      let concrete_idx: Concrete<P> = Concrete::from_abstract(abstract_idx);
+
+      let result: usize = self.readonly.swap_slot(concrete_idx, RESERVED);
+
+      if result == RESERVED {
+        self.counters.acquire_retry();
+        continue;
+      }
+
+      break Abstract::new(result);
+    }
+  }
+
+  /// Converts an allocated id into the index handed out by this table.
+  #[inline]
+  fn detach(&self, index: Abstract<P>) -> Detached {
+    Detached::from_bits((Detached::from_abstract(index).into_bits() & P::ID_MASK_LOCAL) | self.tag)
+  }
+
+  #[inline]
+  fn release_slot(&self, index: Abstract<P>) {
+    let data: usize = self.generate_next_slot(index);
+
+    while !self.readonly.fill_slot(
+      Concrete::from_abstract(self.volatile().fetch_free_id()),
+      data,
+    ) {
+      self.counters.release_retry();
+    }
+
+    self.volatile().decr_entries();
+  }
+
+  #[inline]
+  const fn generate_next_slot(&self, index: Abstract<P>) -> usize {
+    next_generation(index)
+  }
+}
+
+impl<T, P, S> Drop for Table<T, P, S>
+where
   P: Params + ?Sized,
+  S: Storage<T, P>,
 {
   #[inline]
   fn drop(&mut self) {
@@ -244,12 +938,20 @@ where
       return;
     }
 
-    for entry in self.readonly.data.as_mut_slice() {
+    for entry in self.readonly.data_mut() {
       // SAFETY:
       // - `Drop` provides exclusive access via `&mut self`, so no concurrent
       //   access can occur.
       // - Each slot is dropped at most once.
-      if unsafe { entry.drop_in_place() } {
+      let dropped: bool = match self.handler.as_ref() {
+        // Route remaining values through `on_reclaim` as well.
+        Some(handler) => unsafe { entry.take_with(Entry::take) }
+          .map(|value| handler.on_reclaim(value))
+          .is_some(),
+        None => unsafe { entry.drop_in_place() },
+      };
+
+      if dropped {
         count = count.wrapping_sub(1);
 
         if count == 0 {
@@ -265,10 +967,11 @@ where
 // - Memory reclamation is handled through epoch-based reclamation.
 // - Transferring ownership of `Table` between threads is safe provided
 //   `T: Send`, so contained values may be transferred across threads.
-unsafe impl<T, P> Send for Table<T, P>
+unsafe impl<T, P, S> Send for Table<T, P, S>
 where
   T: Send,
   P: Params + ?Sized,
+  S: Storage<T, P>,
 {
 }
 
@@ -277,22 +980,35 @@ where
 // - Methods only yield shared references tied to a `Guard`.
 // - Values may be accessed from multiple threads provided `T: Send`, which is
 //   sufficient because no `&mut T` is ever exposed.
-unsafe impl<T, P> Sync for Table<T, P>
+unsafe impl<T, P, S> Sync for Table<T, P, S>
 where
   T: Send,
   P: Params + ?Sized,
+  S: Storage<T, P>,
 {
 }
 
 // Unconditional because `Table` provides only shared access to `T` via `with`,
 // and epoch-based reclamation handles panic unwind safely.
-impl<T, P> RefUnwindSafe for Table<T, P> where P: Params + ?Sized {}
-impl<T, P> UnwindSafe for Table<T, P> where P: Params + ?Sized {}
+impl<T, P, S> RefUnwindSafe for Table<T, P, S>
+where
+  P: Params + ?Sized,
+  S: Storage<T, P>,
+{
+}
+
+impl<T, P, S> UnwindSafe for Table<T, P, S>
+where
+  P: Params + ?Sized,
+  S: Storage<T, P>,
+{
+}
 
-impl<T, P> Debug for Table<T, P>
+impl<T, P, S> Debug for Table<T, P, S>
 where
   T: Debug,
   P: Params + ?Sized,
+  S: Storage<T, P>,
 {
   fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
     let guard: Guard = Guard::new();
@@ -302,8 +1018,8 @@ where
       let abstract_idx: Abstract<P> = Abstract::new(index);
       let concrete_idx: Concrete<P> = Concrete::from_abstract(abstract_idx);
 
-      if let Some(value) = self.load(concrete_idx, &guard).as_ref() {
-        debug.entry(&Detached::from_abstract(abstract_idx), value);
+      if let Some(entry) = self.load(concrete_idx, &guard).as_ref() {
+        debug.entry(&entry.key(), entry.get());
       }
     }
 
@@ -311,12 +1027,103 @@ where
   }
 }
 
+/// Returns the high bits that tag every index issued by a new table.
+#[inline]
+pub(crate) fn next_tag<P>() -> usize
+where
+  P: Params + ?Sized,
+{
+  if P::ID_TABLE_BITS == 0 {
+    return 0;
+  }
+
+  table_tag::<P>(TABLE_IDS.fetch_add(1, core::sync::atomic::Ordering::Relaxed))
+}
+
+/// Returns the tag for the `count`-th table id handed out, starting at `1`.
+///
+/// Ids wrap around once every tag has been handed out, skipping the id `0`
+/// reserved for tables created in `const` contexts.
+#[inline]
+const fn table_tag<P>(count: usize) -> usize
+where
+  P: Params + ?Sized,
+{
+  let max: usize = usize::MAX >> (usize::BITS - P::ID_TABLE_BITS);
+  let id: usize = 1 + count.wrapping_sub(1) % max;
+
+  id << (usize::BITS - P::ID_TABLE_BITS)
+}
+
+/// Returns the id that replaces `index` in the free-id ring once it is freed.
+#[inline]
+pub(crate) const fn next_generation<P>(index: Abstract<P>) -> usize
+where
+  P: Params + ?Sized,
+{
+  let mut data: usize = index.get();
+
+  data = data.wrapping_add(P::LENGTH.as_usize());
+
+  if data == RESERVED {
+    data = data.wrapping_add(P::LENGTH.as_usize());
+  }
+
+  data
+}
+
+/// Returns `true` if the generation of `key` is behind that of the free `id`
+/// of its slot.
+///
+/// Generations wrap around, so they are compared by their distance: `key` is
+/// stale if `id` is less than half the generation space ahead of it.
+#[inline]
+const fn is_stale<P>(key: Detached, id: usize) -> bool
+where
+  P: Params + ?Sized,
+{
+  let mask: usize = P::ID_MASK_LOCAL >> P::ID_MASK_BITS;
+  let key: usize = (Abstract::<P>::from_detached(key).get() >> P::ID_MASK_BITS) & mask;
+  let id: usize = (id >> P::ID_MASK_BITS) & mask;
+  let ahead: usize = id.wrapping_sub(key) & mask;
+
+  ahead != 0 && ahead <= mask / 2
+}
+
+/// The state of a table moved out in an exclusive context.
+///
+/// [`LocalPTab`] encodes its free-id ring the same way and allocates entries
+/// like the reclamation backend, so both tables convert into each other by
+/// moving the arrays, without revalidating any index or copying any value.
+///
+/// [`LocalPTab`]: crate::local::LocalPTab
+pub(crate) struct RawTable<T, P>
+where
+  P: Params + ?Sized,
+{
+  /// The table id carried in the high bits of every index.
+  pub(crate) tag: usize,
+  /// The number of occupied entries.
+  pub(crate) len: u32,
+  /// The allocation cursor into the free-id ring.
+  pub(crate) next_id: u32,
+  /// The release cursor into the free-id ring.
+  pub(crate) free_id: u32,
+  /// The free-id ring, stored as offsets from [`initial_id`].
+  pub(crate) slot: Array<usize, P>,
+  /// The entries, by slot.
+  pub(crate) data: Array<Option<Owned<Entry<T>>>, P>,
+}
+
 // -----------------------------------------------------------------------------
 // Volatile State
 // -----------------------------------------------------------------------------
 
+/// The allocator state of a table, written by every insertion and removal.
+///
+/// Kept on a cache line of its own by the [`Storage`] of the table.
 #[repr(C)]
-struct Volatile<P>
+pub(crate) struct Volatile<P>
 where
   P: Params + ?Sized,
 {
@@ -330,12 +1137,27 @@ impl<P> Volatile<P>
 where
   P: Params + ?Sized,
 {
+  // Slots beyond `P::ENTRIES_LIMIT` are permanently reserved by starting the
+  // entry count at `P::ENTRIES_BASE`, so a full table still tops out at
+  // `P::LENGTH`. At `Capacity::MAX`, at least one slot is reserved because we
+  // can't produce enough unique identifiers.
+  #[cfg(not(any(loom, shuttle)))]
   #[inline]
-  fn new() -> Self {
-    // At `Capacity::MAX`, one slot is permanently reserved because we can't
-    // produce enough unique identifiers.
+  pub(crate) const fn new() -> Self {
+    Self {
+      entries: AtomicU32::new(P::ENTRIES_BASE),
+      next_id: AtomicU32::new(0),
+      free_id: AtomicU32::new(0),
+      phantom: PhantomData,
+    }
+  }
+
+  // The loom/shuttle atomics can't be created in `const` contexts.
+  #[cfg(any(loom, shuttle))]
+  #[inline]
+  pub(crate) fn new() -> Self {
     Self {
-      entries: AtomicU32::new(u32::from(P::LENGTH == Capacity::MAX)),
+      entries: AtomicU32::new(P::ENTRIES_BASE),
       next_id: AtomicU32::new(0),
       free_id: AtomicU32::new(0),
       phantom: PhantomData,
@@ -375,14 +1197,14 @@ where
   }
 
   #[allow(dead_code, reason = "not used by loom/shuttle tests")]
-  #[cfg(test)]
+  #[cfg(any(test, feature = "serde"))]
   #[inline]
   fn load_next_id(&self) -> usize {
     self.next_id.load(Relaxed) as usize
   }
 
   #[allow(dead_code, reason = "not used by loom/shuttle tests")]
-  #[cfg(test)]
+  #[cfg(any(test, feature = "serde"))]
   #[inline]
   fn load_free_id(&self) -> usize {
     self.free_id.load(Relaxed) as usize
@@ -393,12 +1215,118 @@ where
 // Read-only State
 // -----------------------------------------------------------------------------
 
+/// The backing arrays of a table, along with its [`Volatile`] state.
+///
+/// # Safety
+///
+/// [`data`] and [`slot`] must return slices of exactly `P::LENGTH` elements,
+/// aligned to `P::LINE_WIDTH`, that start out zeroed. [`volatile`] must
+/// return state that starts out as [`Volatile::new`], and does not share a
+/// cache line of `P::LINE_WIDTH` with anything else.
+///
+/// [`data`]: Self::data
+/// [`slot`]: Self::slot
+/// [`volatile`]: Self::volatile
+pub(crate) unsafe trait Storage<T, P>
+where
+  P: Params + ?Sized,
+{
+  /// Returns the allocator state.
+  fn volatile(&self) -> &Volatile<P>;
+
+  /// Returns the entry array.
+  fn data(&self) -> &[Atomic<Entry<T>>];
+
+  /// Returns the entry array for exclusive access.
+  fn data_mut(&mut self) -> &mut [Atomic<Entry<T>>];
+
+  /// Returns the free-id ring.
+  fn slot(&self) -> &[AtomicUsize];
+
+  /// Returns the entry at `index`.
+  #[inline]
+  fn entry(&self, index: Concrete<P>) -> &Atomic<Entry<T>> {
+    // SAFETY: `Concrete<P>` guarantees `index.get() < P::LENGTH`, which is the
+    //         length of the array.
+    unsafe { self.data().get_unchecked(index.get()) }
+  }
+
+  /// Loads the id stored in the slot at `index`.
+  #[inline]
+  fn load_slot(&self, index: Concrete<P>) -> usize {
+    let offset: usize = slot_at(self, index).load(Relaxed);
+    offset.wrapping_add(initial_id(index))
+  }
+
+  /// Stores `id` into the slot at `index`.
+  #[inline]
+  fn store_slot(&self, index: Concrete<P>, id: usize) {
+    let offset: usize = id.wrapping_sub(initial_id(index));
+    slot_at(self, index).store(offset, Relaxed);
+  }
+
+  /// Stores `id` into the slot at `index`, returning the previous id.
+  #[inline]
+  fn swap_slot(&self, index: Concrete<P>, id: usize) -> usize {
+    let offset: usize = id.wrapping_sub(initial_id(index));
+    let previous: usize = slot_at(self, index).swap(offset, Relaxed);
+
+    previous.wrapping_add(initial_id(index))
+  }
+
+  /// Stores `id` into the slot at `index` if it is currently [`RESERVED`].
+  ///
+  /// May fail spuriously.
+  #[inline]
+  fn fill_slot(&self, index: Concrete<P>, id: usize) -> bool {
+    let initial: usize = initial_id(index);
+
+    slot_at(self, index)
+      .compare_exchange_weak(
+        RESERVED.wrapping_sub(initial),
+        id.wrapping_sub(initial),
+        Relaxed,
+        Relaxed,
+      )
+      .is_ok()
+  }
+}
+
+/// Returns the free-id ring slot at `index`.
+#[inline]
+fn slot_at<T, P, S>(storage: &S, index: Concrete<P>) -> &AtomicUsize
+where
+  P: Params + ?Sized,
+  S: Storage<T, P> + ?Sized,
+{
+  // SAFETY: `Concrete<P>` guarantees `index.get() < P::LENGTH`, which is the
+  //         length of the ring.
+  unsafe { storage.slot().get_unchecked(index.get()) }
+}
+
+/// Returns the id initially stored in the slot at `index`.
+///
+/// Slots store their id as an offset from this value, so a zeroed ring already
+/// holds the initial permutation and creating it does not touch any memory.
+#[inline]
+pub(crate) const fn initial_id<P>(index: Concrete<P>) -> usize
+where
+  P: Params + ?Sized,
+{
+  slot_to_id::<P>(index.get())
+}
+
+/// Heap-allocated table arrays and state.
+///
+/// Only holds pointers, which are never written after creation, so it needs
+/// no padding of its own.
 #[repr(C)]
-struct ReadOnly<T, P>
+pub(crate) struct ReadOnly<T, P>
 where
   P: Params + ?Sized,
 {
-  data: Array<Atomic<T>, P>,
+  volatile: Line<Volatile<P>, P>,
+  data: Array<Atomic<Entry<T>>, P>,
   slot: Array<AtomicUsize, P>,
 }
 
@@ -409,30 +1337,73 @@ where
   #[inline]
   fn new() -> Self {
     Self {
+      volatile: Line::new(Volatile::new()),
       data: Self::new_data_array(),
       slot: Self::new_slot_array(),
     }
   }
 
+  #[cfg(not(any(loom, shuttle)))]
+  #[inline]
+  fn new_data_array() -> Array<Atomic<Entry<T>>, P> {
+    // SAFETY: A null `Atomic` is represented by the all-zero bit pattern.
+    unsafe { Array::new_zeroed() }
+  }
+
+  // The loom/shuttle atomics are not plain integers, so they can't be zeroed.
+  #[cfg(any(loom, shuttle))]
   #[inline]
-  fn new_data_array() -> Array<Atomic<T>, P> {
+  fn new_data_array() -> Array<Atomic<Entry<T>>, P> {
     Array::new(|_, slot| {
       slot.write(Atomic::null());
     })
   }
 
+  /// Creates the free-id ring, see [`initial_id`].
+  #[cfg(not(any(loom, shuttle)))]
   #[inline]
   fn new_slot_array() -> Array<AtomicUsize, P> {
-    Array::new(|offset, item| {
-      let block: usize = offset / CACHE_LINE_SLOTS;
-      let index: usize = offset % CACHE_LINE_SLOTS;
-      let value: usize = index * P::BLOCKS.get() + block;
+    // SAFETY: `AtomicUsize` has the same in-memory representation as `usize`.
+    unsafe { Array::new_zeroed() }
+  }
 
-      item.write(AtomicUsize::new(value));
+  #[cfg(any(loom, shuttle))]
+  #[inline]
+  fn new_slot_array() -> Array<AtomicUsize, P> {
+    Array::new(|_, item| {
+      item.write(AtomicUsize::new(0));
     })
   }
 }
 
+// SAFETY: Both arrays hold `P::LENGTH` elements, are aligned to a cache line
+//         and are zeroed (or written with the equivalent values) on creation.
+//         The volatile state fills a `Line` of its own.
+unsafe impl<T, P> Storage<T, P> for ReadOnly<T, P>
+where
+  P: Params + ?Sized,
+{
+  #[inline]
+  fn volatile(&self) -> &Volatile<P> {
+    &self.volatile
+  }
+
+  #[inline]
+  fn data(&self) -> &[Atomic<Entry<T>>] {
+    self.data.as_slice()
+  }
+
+  #[inline]
+  fn data_mut(&mut self) -> &mut [Atomic<Entry<T>>] {
+    self.data.as_mut_slice()
+  }
+
+  #[inline]
+  fn slot(&self) -> &[AtomicUsize] {
+    self.slot.as_slice()
+  }
+}
+
 // -----------------------------------------------------------------------------
 // Permit
 // -----------------------------------------------------------------------------
@@ -449,7 +1420,10 @@ where
   P: Params + ?Sized,
 {
   #[inline]
-  const fn new(_table: &'table Table<T, P>) -> Self {
+  const fn new<S>(_table: &'table Table<T, P, S>) -> Self
+  where
+    S: Storage<T, P>,
+  {
     Self {
       marker: PhantomData,
     }
@@ -480,7 +1454,7 @@ pub struct WeakKeys<'table, T, P>
 where
   P: Params + ?Sized,
 {
-  array: NonNull<Atomic<T>>,
+  array: NonNull<Atomic<Entry<T>>>,
   guard: Guard,
   total: usize,
   index: usize,
@@ -492,11 +1466,14 @@ where
   P: Params + ?Sized,
 {
   #[inline]
-  pub(crate) fn new(guard: Guard, table: &'table Table<T, P>) -> Self {
+  pub(crate) fn new<S>(guard: Guard, table: &'table Table<T, P, S>) -> Self
+  where
+    S: Storage<T, P>,
+  {
     Self {
-      array: table.readonly.data.as_non_null(),
+      array: NonNull::from(table.readonly.data()).cast(),
       guard,
-      total: table.cap(),
+      total: P::LENGTH.as_usize(),
       index: 0,
       table: PhantomData,
     }
@@ -531,28 +1508,28 @@ where
 
       index += 1;
 
-      let ptr: Shared<'_, T> = {
+      let ptr: Shared<'_, Entry<T>> = {
         // SAFETY:
         // - `Concrete<P>` guarantees `concrete_idx.get() < P::LENGTH`.
         // - `self.array` points to a contiguous allocation of `P::LENGTH` elements.
-        let raw: NonNull<Atomic<T>> = unsafe { self.array.add(concrete_idx.get()) };
+        let raw: NonNull<Atomic<Entry<T>>> = unsafe { self.array.add(concrete_idx.get()) };
 
         // SAFETY:
         // - `raw` was derived from a valid allocation.
         // - The pointer is properly aligned for `Atomic<T>`.
         // - The iterator only performs shared access.
-        let data: &Atomic<T> = unsafe { raw.as_ref() };
+        let data: &Atomic<Entry<T>> = unsafe { raw.as_ref() };
 
         data.load(Acquire, guard)
       };
 
-      if ptr.is_null() {
+      let Some(entry) = ptr.as_ref() else {
         continue;
-      }
+      };
 
       self.index = index;
 
-      return Some(Detached::from_abstract(abstract_idx));
+      return Some(entry.key());
     }
 
     self.index = index;
@@ -569,6 +1546,7 @@ where
 #[cfg_attr(coverage_nightly, coverage(off))]
 #[cfg(test)]
 mod tests {
+  use core::ptr;
   use std::collections::HashSet;
   use std::sync::Arc;
   use std::sync::Barrier;
@@ -576,23 +1554,30 @@ mod tests {
   use std::thread::JoinHandle;
 
   use crate::array::Array;
+  use crate::entry::Entry;
+  use crate::error::InsertAtError;
+  use crate::flags::Flags;
   use crate::index::Abstract;
   use crate::index::Concrete;
   use crate::index::Detached;
   use crate::params::CACHE_LINE_SLOTS;
   use crate::params::Capacity;
   use crate::params::ConstParams;
+  use crate::params::ExactParams;
   use crate::params::Params;
   use crate::params::ParamsExt;
-  use crate::reclaim::sdd;
-  use crate::reclaim::sdd::Atomic;
-  use crate::reclaim::sdd::Guard;
+  use crate::params::SlotLayout;
+  use crate::reclaim;
+  use crate::reclaim::Atomic;
+  use crate::reclaim::Guard;
   use crate::sync::atomic::AtomicUsize;
   use crate::sync::atomic::Ordering;
   use crate::table;
   use crate::table::Permit;
   use crate::table::RESERVED;
+  use crate::table::Storage;
   use crate::table::Table;
+  use crate::table::table_tag;
 
   type DefParams = ConstParams<{ Capacity::DEF.as_usize() }>;
   type MaxParams = ConstParams<{ Capacity::MAX.as_usize() }>;
@@ -600,6 +1585,21 @@ mod tests {
 
   type ReadOnly<P = DefParams> = table::ReadOnly<u64, P>;
 
+  struct SequentialParams;
+
+  impl Params for SequentialParams {
+    const LENGTH: Capacity = Capacity::MIN;
+    const SLOT_LAYOUT: SlotLayout = SlotLayout::Sequential;
+  }
+
+  struct TaggedParams;
+
+  impl Params for TaggedParams {
+    const LENGTH: Capacity = Capacity::MIN;
+    const TABLE_ID_BITS: u32 = 8;
+    const CHECKED: bool = true;
+  }
+
   const THREADS: usize = 8;
 
   macro_rules! refute {
@@ -640,8 +1640,8 @@ mod tests {
 
   #[test]
   fn new_data_array() {
-    let array: Array<Atomic<u64>, DefParams> = ReadOnly::new_data_array();
-    let slice: &[Atomic<u64>] = array.as_slice();
+    let array: Array<Atomic<Entry<u64>>, DefParams> = ReadOnly::new_data_array();
+    let slice: &[Atomic<Entry<u64>>] = array.as_slice();
     let guard: Guard = Guard::new();
 
     for atomic in slice {
@@ -651,19 +1651,56 @@ mod tests {
 
   #[test]
   fn new_slot_array() {
-    let array: Array<AtomicUsize, DefParams> = ReadOnly::new_slot_array();
-    let slice: &[AtomicUsize] = array.as_slice();
+    let readonly: ReadOnly = ReadOnly::new();
 
     let mut offset: usize = 0;
 
     for block in 0..DefParams::BLOCKS.get() {
       for slot in 0..CACHE_LINE_SLOTS {
         let expected: usize = slot * DefParams::BLOCKS.get() + block;
-        let received: usize = slice[offset].load(Ordering::Relaxed);
+        let received: usize = readonly.load_slot(Concrete::new(offset));
         assert_eq!(received, expected);
         offset += 1;
       }
     }
+
+    // The initial permutation is not materialized in memory.
+    for atomic in ReadOnly::<DefParams>::new_slot_array().as_slice() {
+      assert_eq!(atomic.load(Ordering::Relaxed), 0);
+    }
+  }
+
+  #[test]
+  fn slot_offsets() {
+    let readonly: ReadOnly = ReadOnly::new();
+    let index: Concrete<DefParams> = Concrete::new(3);
+
+    readonly.store_slot(index, RESERVED);
+    assert_eq!(readonly.load_slot(index), RESERVED);
+
+    // `fill_slot` may fail spuriously.
+    while !readonly.fill_slot(index, 7) {}
+
+    refute!(readonly.fill_slot(index, 8));
+    assert_eq!(readonly.swap_slot(index, RESERVED), 7);
+    assert_eq!(readonly.load_slot(index), RESERVED);
+  }
+
+  #[test]
+  fn volatile_line() {
+    struct Line64;
+
+    impl Params for Line64 {
+      const LENGTH: Capacity = Capacity::MIN;
+      const CACHE_LINE: usize = 64;
+    }
+
+    let table: Table<usize, Line64> = Table::new();
+    let address: usize = ptr::from_ref(table.volatile()).addr();
+
+    assert!(address.is_multiple_of(64));
+    assert_eq!(align_of::<Table<usize, Line64>>(), align_of::<usize>());
+    assert_eq!(table.len(), 0);
   }
 
   #[test]
@@ -707,313 +1744,918 @@ mod tests {
       }));
     }
 
-    barrier.wait();
+    barrier.wait();
+
+    for thread in threads {
+      thread.join().unwrap();
+    }
+
+    assert_eq!(table.len(), table.cap() as u32);
+    assert_eq!(table.cap(), PERMITS.load(Ordering::Relaxed));
+  }
+
+  #[test]
+  fn acquire_slot() {
+    let table: Table<usize, DefParams> = Table::new();
+    let mut indices: HashSet<usize> = HashSet::with_capacity(table.cap());
+
+    for _ in 0..table.cap() {
+      let reserved: Permit<'_, usize, DefParams> = table.reserve_slot().unwrap();
+      let acquired: Abstract<DefParams> = table.acquire_slot(reserved);
+
+      assert!(indices.insert(acquired.get()));
+    }
+
+    assert_eq!(table.len(), indices.len() as u32);
+    assert_eq!(table.cap(), table.volatile().load_next_id());
+  }
+
+  #[test]
+  fn release_slot() {
+    let table: Table<usize, DefParams> = Table::new();
+
+    for _ in 0..table.cap() {
+      let reserved: Permit<'_, usize, DefParams> = table.reserve_slot().unwrap();
+      let acquired: Abstract<DefParams> = table.acquire_slot(reserved);
+
+      table.release_slot(acquired);
+    }
+
+    assert!(table.is_empty());
+    assert!(table.volatile().load_free_id().is_multiple_of(table.cap()));
+  }
+
+  #[test]
+  fn generate_next_slot() {
+    let table: Table<usize, DefParams> = Table::new();
+
+    for index in 0..table.cap() {
+      let old: Abstract<DefParams> = Abstract::new(index);
+      let new: Abstract<DefParams> = Abstract::new(table.generate_next_slot(old));
+
+      assert_ne!(old, new);
+    }
+  }
+
+  #[test]
+  fn generate_next_slot_uniqueness() {
+    const GENERATIONS: usize = 10;
+
+    let table: Table<usize, DefParams> = Table::new();
+    let mut indices: HashSet<usize> = HashSet::with_capacity(table.cap());
+    let mut uniques: HashSet<Detached> = HashSet::with_capacity(GENERATIONS * table.cap());
+
+    for generation in 0..GENERATIONS {
+      let gen_index: usize = generation * table.cap();
+
+      for index in 0..table.cap() {
+        let old: Abstract<DefParams> = Abstract::new(gen_index + index);
+        let new: Abstract<DefParams> = Abstract::new(table.generate_next_slot(old));
+        let _in: bool = indices.insert(Concrete::from_abstract(new).get());
+
+        assert!(uniques.insert(Detached::from_abstract(new)));
+      }
+    }
+
+    assert_eq!(uniques.len(), GENERATIONS * table.cap());
+    assert_eq!(indices.len(), table.cap());
+  }
+
+  #[test]
+  fn generate_next_slot_skips_reserved() {
+    let table: Table<usize, DefParams> = Table::new();
+    let index: Abstract<DefParams> = Abstract::new(RESERVED - table.cap());
+    let value: usize = table.generate_next_slot(index);
+
+    assert_ne!(value, RESERVED);
+  }
+
+  // Scenario: The table fills up as multiple threads manipulate slots.
+  // Expected:
+  // - We never hand out `Permit`s beyond the available capacity.
+  // - Every `Permit` is honored in `acquire_slot`.
+  // - All keys are unique.
+  // - The table count stays in bounds.
+  #[test]
+  fn slot_churn() {
+    static PERMITS: AtomicUsize = AtomicUsize::new(0);
+    static UNIQUES: AtomicUsize = AtomicUsize::new(0);
+
+    let table: Arc<Table<usize, DefParams>> = Arc::new(Table::new());
+    let barrier: Arc<Barrier> = Arc::new(Barrier::new(THREADS + 1));
+    let capacity: usize = table.cap();
+
+    let mut threads: Vec<JoinHandle<Vec<usize>>> = Vec::with_capacity(THREADS);
+    let mut uniques: HashSet<usize> = HashSet::with_capacity(capacity);
+
+    for _ in 0..THREADS {
+      let barrier: Arc<Barrier> = Arc::clone(&barrier);
+      let table: Arc<Table<usize, DefParams>> = Arc::clone(&table);
+
+      threads.push(thread::spawn(move || {
+        let mut track: Vec<usize> = Vec::with_capacity(capacity);
+        let mut index: usize = 0;
+
+        barrier.wait();
+
+        for _ in 0..4 {
+          for _ in 0..capacity {
+            if let Some(permit) = table.reserve_slot() {
+              track.push(table.acquire_slot(permit).get());
+              index += 1;
+
+              assert!(table.len() <= capacity as u32);
+              assert!(PERMITS.fetch_add(1, Ordering::Relaxed) <= capacity);
+
+              UNIQUES.fetch_add(1, Ordering::Relaxed);
+            }
+
+            if index.is_multiple_of(2)
+              && let Some(index) = track[index..].first()
+            {
+              table.release_slot(Abstract::new(*index));
+              PERMITS.fetch_sub(1, Ordering::Relaxed);
+            }
+
+            thread::yield_now();
+          }
+        }
+
+        track
+      }));
+    }
+
+    barrier.wait();
+
+    for thread in threads {
+      for key in thread.join().unwrap() {
+        assert!(uniques.insert(key));
+      }
+    }
+
+    assert_eq!(uniques.len(), UNIQUES.load(Ordering::Relaxed));
+    assert_eq!(table.cap(), PERMITS.load(Ordering::Relaxed));
+  }
+
+  // ---------------------------------------------------------------------------
+  // API
+  // ---------------------------------------------------------------------------
+
+  #[test]
+  fn cap() {
+    let table: Table<usize, DefParams> = Table::new();
+    assert_eq!(table.cap(), Capacity::DEF.as_usize());
+
+    let table: Table<usize, MaxParams> = Table::new();
+    assert_eq!(table.cap(), Capacity::MAX.as_usize() - 1);
+
+    let table: Table<usize, MinParams> = Table::new();
+    assert_eq!(table.cap(), Capacity::MIN.as_usize());
+  }
+
+  #[test]
+  fn len() {
+    let table: Table<usize, DefParams> = Table::new();
+    assert_eq!(table.len(), 0);
+
+    let table: Table<usize, MaxParams> = Table::new();
+    assert_eq!(table.len(), 0);
+
+    let table: Table<usize, MinParams> = Table::new();
+    assert_eq!(table.len(), 0);
+  }
+
+  // Scenario: The table is temporarily above capacity due to concurrent writes.
+  // Expected: The `len` never surpasses `cap`.
+  #[test]
+  fn len_clamp() {
+    let table: Table<usize, DefParams> = Table::new();
+
+    table
+      .volatile()
+      .entries
+      .store(DefParams::LENGTH.as_u32() + 1, Ordering::Relaxed);
+
+    assert_eq!(table.len(), table.cap() as u32);
+  }
+
+  #[test]
+  fn is_empty() {
+    let table: Table<usize, DefParams> = Table::new();
+    assert!(table.is_empty());
+
+    let table: Table<usize, MaxParams> = Table::new();
+    assert!(table.is_empty());
+
+    let table: Table<usize, MinParams> = Table::new();
+    assert!(table.is_empty());
+  }
+
+  #[test]
+  fn insert() {
+    let table: Table<usize, DefParams> = Table::new();
+
+    assert_ne!(table.insert(123), None);
+    assert_eq!(table.len(), 1);
+
+    for index in 0..16 {
+      assert_ne!(table.insert(123), None);
+      assert_eq!(table.len(), index + 2);
+    }
+
+    assert_eq!(table.len(), 17);
+  }
+
+  #[test]
+  fn insert_beyond_capacity() {
+    let table: Table<usize, DefParams> = Table::new();
+
+    for _ in 0..table.cap() {
+      assert_ne!(table.insert(123), None);
+    }
+
+    assert_eq!(table.len(), table.cap() as u32);
+    assert_eq!(table.insert(123), None);
+    assert_eq!(table.len(), table.cap() as u32);
+  }
+
+  #[test]
+  fn insert_unique_ids() {
+    let table: Table<usize, DefParams> = Table::new();
+    let mut keys: HashSet<Detached> = HashSet::with_capacity(table.cap());
+
+    for _ in 0..table.cap() {
+      assert!(keys.insert(table.insert(123).unwrap()));
+    }
+  }
+
+  #[test]
+  fn write_callback_correct_index() {
+    let table: Table<usize, DefParams> = Table::new();
+    let guard: Guard = Guard::new();
+
+    let index: Detached = table
+      .write(|uninit, index| {
+        uninit.write(index.into_bits());
+      })
+      .unwrap();
+
+    assert_eq!(table.read(index, &guard), Some(index.into_bits()));
+  }
+
+  #[test]
+  fn remove() {
+    let table: Table<usize, DefParams> = Table::new();
+    let index: Detached = table.insert(123).unwrap();
+
+    assert_eq!(table.len(), 1);
+    assert!(!table.is_empty());
+
+    assert!(table.remove(index));
+
+    assert_eq!(table.len(), 0);
+    assert!(table.is_empty());
+  }
+
+  #[test]
+  fn remove_nonexistent() {
+    let table: Table<usize, DefParams> = Table::new();
+    let index: Detached = table.insert(123).unwrap();
+
+    assert!(table.remove(index));
+    refute!(table.remove(index));
+  }
+
+  #[test]
+  fn remove_recycling() {
+    let table: Table<usize, DefParams> = Table::new();
+    let guard: Guard = Guard::new();
+    let mut keys: Vec<Detached> = Vec::with_capacity(table.cap());
+
+    for index in 0..table.cap() {
+      keys.push(table.insert(index).unwrap());
+    }
+
+    assert!(table.insert(123).is_none());
+    assert!(table.remove(keys[0]));
+
+    let index: Detached = table.insert(456).unwrap();
+
+    assert!(table.exists(index, &guard));
+    assert_eq!(table.read(index, &guard), Some(456));
+
+    for key in keys.drain(1..).rev() {
+      assert!(table.remove(key));
+    }
+
+    for index in 0..table.cap() - 1 {
+      assert!(table.insert(index).is_some());
+    }
+  }
+
+  #[test]
+  fn insert_at() {
+    let source: Table<usize, DefParams> = Table::new();
+    let keys: Vec<Detached> = (0..64).map(|index| source.insert(index).unwrap()).collect();
+
+    let mut table: Table<usize, DefParams> = Table::new();
+    let guard: Guard = Guard::new();
+
+    // Restore in reverse so claimed ids come from the middle of the ring.
+    for (index, key) in keys.iter().enumerate().rev() {
+      assert_eq!(table.insert_at(*key, index), Ok(()));
+    }
+
+    assert_eq!(table.len(), 64);
+
+    for (index, key) in keys.iter().enumerate() {
+      assert_eq!(table.read(*key, &guard), Some(index));
+    }
+
+    // Later allocations never hand out a claimed slot.
+    let mut slots: HashSet<usize> = keys
+      .iter()
+      .map(|key| Concrete::<DefParams>::from_detached(*key).get())
+      .collect();
+
+    while let Some(key) = table.insert(0) {
+      assert!(slots.insert(Concrete::<DefParams>::from_detached(key).get()));
+    }
+
+    assert_eq!(slots.len(), table.cap());
+  }
+
+  #[test]
+  fn insert_at_generation() {
+    let source: Table<usize, MinParams> = Table::new();
+
+    // Advance the generation of the first slot.
+    let stale: Detached = source.insert(0).unwrap();
+    assert!(source.remove(stale));
+
+    let keys: Vec<Detached> = (0..source.cap())
+      .map(|index| source.insert(index).unwrap())
+      .collect();
+
+    let key: Detached = keys
+      .iter()
+      .copied()
+      .find(|key| Concrete::<MinParams>::from_detached(*key) == Concrete::from_detached(stale))
+      .unwrap();
+
+    let mut table: Table<usize, MinParams> = Table::new();
+    let guard: Guard = Guard::new();
+
+    assert_eq!(table.insert_at(key, 1), Ok(()));
+    assert!(!table.exists(stale, &guard));
+    assert!(table.exists(key, &guard));
+
+    // Removing the restored entry bumps the generation past `key`.
+    assert!(table.remove(key));
+
+    while let Some(index) = table.insert(0) {
+      assert_ne!(index, key);
+      assert_ne!(index, stale);
+    }
+  }
+
+  #[test]
+  fn insert_at_occupied() {
+    let mut table: Table<usize, MinParams> = Table::new();
+    let key: Detached = table.insert(1).unwrap();
+
+    assert_eq!(table.insert_at(key, 2), Err(InsertAtError::Occupied));
+
+    // A different generation of the same slot is rejected as well.
+    let other: Detached = Detached::from_bits(key.into_bits() + MinParams::LENGTH.as_usize());
+    assert_eq!(table.insert_at(other, 2), Err(InsertAtError::Occupied));
+
+    assert_eq!(table.read(key, &Guard::new()), Some(1));
+    assert_eq!(table.len(), 1);
+  }
+
+  #[test]
+  fn insert_at_stale() {
+    let mut table: Table<usize, MinParams> = Table::new();
+    let stale: Detached = table.insert(1).unwrap();
+
+    assert!(table.remove(stale));
+
+    // The removed index must not be revived once its slot moved on.
+    assert_eq!(table.insert_at(stale, 2), Err(InsertAtError::Stale));
+    assert!(table.is_empty());
+
+    // Later generations of the slot can still be claimed.
+    let ahead: Detached = Detached::from_bits(stale.into_bits() + 2 * MinParams::LENGTH.as_usize());
+
+    assert_eq!(table.insert_at(ahead, 2), Ok(()));
+    assert_eq!(table.read(ahead, &Guard::new()), Some(2));
+  }
+
+  #[test]
+  fn insert_at_full() {
+    let mut table: Table<usize, MaxParams> = Table::new();
+    let spare: Detached = Detached::from_abstract(Abstract::<MaxParams>::new(table.cap()));
+
+    for index in 0..table.cap() {
+      assert!(table.insert(index).is_some());
+    }
+
+    // One slot is permanently reserved at `Capacity::MAX`.
+    assert_eq!(table.insert_at(spare, 0), Err(InsertAtError::Full));
+  }
 
-    for thread in threads {
-      thread.join().unwrap();
-    }
+  #[cfg(feature = "std")]
+  #[test]
+  fn export_import() {
+    let a: Table<usize, DefParams> = Table::new();
+    let b: Table<usize, DefParams> = Table::new();
+    let key: Detached = a.insert(1).unwrap();
 
-    assert_eq!(table.len(), table.cap() as u32);
-    assert_eq!(table.cap(), PERMITS.load(Ordering::Relaxed));
+    assert_eq!(a.import(a.export(key)), Some(key));
+    assert_eq!(a.export(key), a.export(key));
+
+    // Tokens are bound to the exporting table.
+    assert_eq!(b.import(a.export(key)), None);
+    assert_eq!(a.import(b.export(key)), None);
   }
 
   #[test]
-  fn acquire_slot() {
-    let table: Table<usize, DefParams> = Table::new();
-    let mut indices: HashSet<usize> = HashSet::with_capacity(table.cap());
+  fn table_id_tagged() {
+    let a: Table<usize, TaggedParams> = Table::new();
+    let b: Table<usize, TaggedParams> = Table::new();
 
-    for _ in 0..table.cap() {
-      let reserved: Permit<'_, usize, DefParams> = table.reserve_slot().unwrap();
-      let acquired: Abstract<DefParams> = table.acquire_slot(reserved);
+    let key_a: Detached = a.insert(1).unwrap();
+    let key_b: Detached = b.insert(2).unwrap();
 
-      assert!(indices.insert(acquired.get()));
-    }
+    // Same slot and generation, different table.
+    assert_eq!(
+      Concrete::<TaggedParams>::from_detached(key_a),
+      Concrete::<TaggedParams>::from_detached(key_b)
+    );
+    assert_ne!(key_a, key_b);
 
-    assert_eq!(table.len(), indices.len() as u32);
-    assert_eq!(table.cap(), table.volatile.load_next_id());
+    assert_eq!(a.with(key_a, &Guard::new(), |value| *value), Some(1));
+    assert_eq!(a.with(key_b, &Guard::new(), |value| *value), None);
+    refute!(a.exists(key_b, &Guard::new()));
+    refute!(a.remove(key_b));
+
+    assert_eq!(a.len(), 1);
+    assert!(a.remove(key_a));
   }
 
   #[test]
-  fn release_slot() {
-    let table: Table<usize, DefParams> = Table::new();
+  fn table_id_reuse() {
+    let table: Table<usize, TaggedParams> = Table::new();
+    let tag: usize = table.insert(0).unwrap().into_bits() & !TaggedParams::ID_MASK_LOCAL;
 
-    for _ in 0..table.cap() {
-      let reserved: Permit<'_, usize, DefParams> = table.reserve_slot().unwrap();
-      let acquired: Abstract<DefParams> = table.acquire_slot(reserved);
+    assert_ne!(tag, 0);
 
-      table.release_slot(acquired);
+    // Released slots keep the tag through every generation.
+    for value in 0..table.cap() * 4 {
+      let key: Detached = table.insert(value).unwrap();
+
+      assert_eq!(key.into_bits() & !TaggedParams::ID_MASK_LOCAL, tag);
+      assert!(table.remove(key));
     }
 
-    assert!(table.is_empty());
-    assert!(table.volatile.load_free_id().is_multiple_of(table.cap()));
+    assert_eq!(table.len(), 1);
   }
 
   #[test]
-  fn generate_next_slot() {
-    let table: Table<usize, DefParams> = Table::new();
-
-    for index in 0..table.cap() {
-      let old: Abstract<DefParams> = Abstract::new(index);
-      let new: Abstract<DefParams> = Abstract::new(table.generate_next_slot(old));
+  fn table_id_wrap() {
+    struct NarrowTagParams;
 
-      assert_ne!(old, new);
+    impl Params for NarrowTagParams {
+      const LENGTH: Capacity = Capacity::MIN;
+      const TABLE_ID_BITS: u32 = 2;
+      const CHECKED: bool = true;
     }
+
+    let shift: u32 = usize::BITS - 2;
+    let tags: Vec<usize> = (1..=7).map(table_tag::<NarrowTagParams>).collect();
+
+    // The ids cycle through 1..=3, never reaching the static table id 0.
+    assert_eq!(tags, [1, 2, 3, 1, 2, 3, 1].map(|id: usize| id << shift));
   }
 
   #[test]
-  fn generate_next_slot_uniqueness() {
-    const GENERATIONS: usize = 10;
+  fn table_id_unchecked() {
+    struct UncheckedParams;
 
-    let table: Table<usize, DefParams> = Table::new();
-    let mut indices: HashSet<usize> = HashSet::with_capacity(table.cap());
-    let mut uniques: HashSet<Detached> = HashSet::with_capacity(GENERATIONS * table.cap());
+    impl Params for UncheckedParams {
+      const LENGTH: Capacity = Capacity::MIN;
+      const TABLE_ID_BITS: u32 = 8;
+    }
 
-    for generation in 0..GENERATIONS {
-      let gen_index: usize = generation * table.cap();
+    let a: Table<usize, UncheckedParams> = Table::new();
+    let b: Table<usize, UncheckedParams> = Table::new();
+    let guard: Guard = Guard::new();
 
-      for index in 0..table.cap() {
-        let old: Abstract<DefParams> = Abstract::new(gen_index + index);
-        let new: Abstract<DefParams> = Abstract::new(table.generate_next_slot(old));
-        let _in: bool = indices.insert(Concrete::from_abstract(new).get());
+    let key: Detached = a.insert(1).unwrap();
+    let other: Detached = b.insert(2).unwrap();
 
-        assert!(uniques.insert(Detached::from_abstract(new)));
-      }
-    }
+    // The id bits are reserved in every build...
+    assert_eq!(UncheckedParams::ID_TABLE_BITS, 8);
+    assert_ne!(key.into_bits() & !UncheckedParams::ID_MASK_LOCAL, 0);
+    assert_eq!(
+      key.into_bits() & UncheckedParams::ID_MASK_LOCAL,
+      other.into_bits() & UncheckedParams::ID_MASK_LOCAL
+    );
 
-    assert_eq!(uniques.len(), GENERATIONS * table.cap());
-    assert_eq!(indices.len(), table.cap());
+    // ...but only compared in debug builds.
+    assert_eq!(a.read(other, &guard).is_some(), !cfg!(debug_assertions));
   }
 
   #[test]
-  fn generate_next_slot_skips_reserved() {
-    let table: Table<usize, DefParams> = Table::new();
-    let index: Abstract<DefParams> = Abstract::new(RESERVED - table.cap());
-    let value: usize = table.generate_next_slot(index);
+  fn table_id_insert_at() {
+    let a: Table<usize, TaggedParams> = Table::new();
+    let mut b: Table<usize, TaggedParams> = Table::new();
 
-    assert_ne!(value, RESERVED);
+    let key: Detached = a.insert(1).unwrap();
+
+    assert_eq!(b.insert_at(key, 1), Err(InsertAtError::Foreign));
   }
 
-  // Scenario: The table fills up as multiple threads manipulate slots.
-  // Expected:
-  // - We never hand out `Permit`s beyond the available capacity.
-  // - Every `Permit` is honored in `acquire_slot`.
-  // - All keys are unique.
-  // - The table count stays in bounds.
   #[test]
-  fn slot_churn() {
-    static PERMITS: AtomicUsize = AtomicUsize::new(0);
-    static UNIQUES: AtomicUsize = AtomicUsize::new(0);
+  fn custom_line_width() {
+    struct Narrow;
 
-    let table: Arc<Table<usize, DefParams>> = Arc::new(Table::new());
-    let barrier: Arc<Barrier> = Arc::new(Barrier::new(THREADS + 1));
-    let capacity: usize = table.cap();
+    impl Params for Narrow {
+      const LENGTH: Capacity = Capacity::MIN;
+      const CACHE_LINE: usize = 32;
+    }
 
-    let mut threads: Vec<JoinHandle<Vec<usize>>> = Vec::with_capacity(THREADS);
-    let mut uniques: HashSet<usize> = HashSet::with_capacity(capacity);
+    let table: Table<usize, Narrow> = Table::new();
 
-    for _ in 0..THREADS {
-      let barrier: Arc<Barrier> = Arc::clone(&barrier);
-      let table: Arc<Table<usize, DefParams>> = Arc::clone(&table);
+    assert_eq!(
+      table.readonly.data().as_ptr().addr() % Narrow::LINE_WIDTH,
+      0
+    );
 
-      threads.push(thread::spawn(move || {
-        let mut track: Vec<usize> = Vec::with_capacity(capacity);
-        let mut index: usize = 0;
+    let keys: HashSet<Detached> = (0..table.cap())
+      .map(|index| table.insert(index).unwrap())
+      .collect();
 
-        barrier.wait();
+    assert_eq!(keys.len(), table.cap());
 
-        for _ in 0..4 {
-          for _ in 0..capacity {
-            if let Some(permit) = table.reserve_slot() {
-              track.push(table.acquire_slot(permit).get());
-              index += 1;
+    // The first ids are spread across lines of `LINE_SLOTS` slots.
+    let lines: HashSet<usize> = keys
+      .iter()
+      .filter(|key| Abstract::<Narrow>::from_detached(**key).get() < Narrow::BLOCKS.get())
+      .map(|key| Concrete::<Narrow>::from_detached(*key).get() / Narrow::LINE_SLOTS)
+      .collect();
 
-              assert!(table.len() <= capacity as u32);
-              assert!(PERMITS.fetch_add(1, Ordering::Relaxed) <= capacity);
+    assert_eq!(lines.len(), Narrow::BLOCKS.get());
 
-              UNIQUES.fetch_add(1, Ordering::Relaxed);
-            }
+    for key in keys {
+      assert!(table.remove(key));
+    }
+  }
 
-            if index.is_multiple_of(2)
-              && let Some(index) = track[index..].first()
-            {
-              table.release_slot(Abstract::new(*index));
-              PERMITS.fetch_sub(1, Ordering::Relaxed);
-            }
+  #[test]
+  fn exact_limit() {
+    type Exact = ExactParams<20>;
 
-            thread::yield_now();
-          }
-        }
+    let table: Table<usize, Exact> = Table::new();
 
-        track
-      }));
+    assert_eq!(table.cap(), 20);
+    assert_eq!(table.readonly.data().len(), 32);
+
+    let keys: HashSet<Detached> = (0..table.cap())
+      .map(|index| table.insert(index).unwrap())
+      .collect();
+
+    assert_eq!(keys.len(), 20);
+    assert_eq!(table.len(), 20);
+    assert!(table.insert(20).is_none());
+    assert_eq!(table.len(), 20);
+
+    // Freed slots are handed out again without exceeding the limit.
+    for key in keys {
+      assert!(table.remove(key));
+      assert!(table.insert(0).is_some());
+      assert!(table.insert(0).is_none());
     }
 
-    barrier.wait();
+    assert_eq!(table.len(), 20);
 
-    for thread in threads {
-      for key in thread.join().unwrap() {
-        assert!(uniques.insert(key));
-      }
+    // Entries have moved past the first `cap` slots by now.
+    assert_eq!(table.weak_keys(Guard::new()).count(), 20);
+  }
+
+  #[test]
+  fn sequential_layout() {
+    let table: Table<usize, SequentialParams> = Table::new();
+
+    let keys: Vec<Detached> = (0..table.cap())
+      .map(|index| table.insert(index).unwrap())
+      .collect();
+
+    // The first generation of slots is handed out in order.
+    for (index, key) in keys.iter().enumerate() {
+      assert_eq!(
+        Concrete::<SequentialParams>::from_detached(*key).get(),
+        index
+      );
     }
 
-    assert_eq!(uniques.len(), UNIQUES.load(Ordering::Relaxed));
-    assert_eq!(table.cap(), PERMITS.load(Ordering::Relaxed));
+    for key in keys {
+      assert!(table.remove(key));
+    }
+
+    let key: Detached = table.insert(0).unwrap();
+    assert_eq!(Concrete::<SequentialParams>::from_detached(key).get(), 0);
   }
 
-  // ---------------------------------------------------------------------------
-  // API
-  // ---------------------------------------------------------------------------
+  #[test]
+  fn link_cascade() {
+    let table: Table<usize, DefParams> = Table::new();
+    let a: Detached = table.insert(1).unwrap();
+    let b: Detached = table.insert(2).unwrap();
+    let c: Detached = table.insert(3).unwrap();
+
+    assert!(table.link(a, b));
+    assert!(table.remove(b));
+
+    assert_eq!(table.len(), 1);
+    refute!(table.exists(a, &Guard::new()));
+    assert!(table.exists(c, &Guard::new()));
+  }
 
   #[test]
-  fn cap() {
+  fn link_chain() {
     let table: Table<usize, DefParams> = Table::new();
-    assert_eq!(table.cap(), Capacity::DEF.as_usize());
+    let size: usize = table.cap().min(4096);
+    let keys: Vec<Detached> = (0..size)
+      .map(|index| table.insert(index).unwrap())
+      .collect();
 
-    let table: Table<usize, MaxParams> = Table::new();
-    assert_eq!(table.cap(), Capacity::MAX.as_usize() - 1);
+    for pair in keys.windows(2) {
+      assert!(table.link(pair[0], pair[1]));
+    }
 
-    let table: Table<usize, MinParams> = Table::new();
-    assert_eq!(table.cap(), Capacity::MIN.as_usize());
+    assert!(table.remove(keys[size / 2]));
+    assert!(table.is_empty());
   }
 
   #[test]
-  fn len() {
+  fn link_cycle() {
     let table: Table<usize, DefParams> = Table::new();
-    assert_eq!(table.len(), 0);
+    let a: Detached = table.insert(1).unwrap();
+    let b: Detached = table.insert(2).unwrap();
+    let c: Detached = table.insert(3).unwrap();
 
-    let table: Table<usize, MaxParams> = Table::new();
-    assert_eq!(table.len(), 0);
+    assert!(table.link(a, b));
+    assert!(table.link(b, c));
+    assert!(table.link(c, a));
 
-    let table: Table<usize, MinParams> = Table::new();
-    assert_eq!(table.len(), 0);
+    assert!(table.remove(a));
+    assert!(table.is_empty());
   }
 
-  // Scenario: The table is temporarily above capacity due to concurrent writes.
-  // Expected: The `len` never surpasses `cap`.
   #[test]
-  fn len_clamp() {
+  fn link_nonexistent() {
     let table: Table<usize, DefParams> = Table::new();
+    let a: Detached = table.insert(1).unwrap();
+    let b: Detached = table.insert(2).unwrap();
 
-    table
-      .volatile
-      .entries
-      .store(DefParams::LENGTH.as_u32() + 1, Ordering::Relaxed);
+    assert!(table.remove(b));
 
-    assert_eq!(table.len(), table.cap() as u32);
+    refute!(table.link(a, b));
+    refute!(table.link(b, a));
+
+    // Reusing the slot does not revive the stale index.
+    let c: Detached = table.insert(3).unwrap();
+
+    refute!(table.link(a, b));
+    assert!(table.link(a, c));
   }
 
   #[test]
-  fn is_empty() {
+  fn link_self() {
     let table: Table<usize, DefParams> = Table::new();
-    assert!(table.is_empty());
+    let a: Detached = table.insert(1).unwrap();
 
-    let table: Table<usize, MaxParams> = Table::new();
-    assert!(table.is_empty());
+    assert!(table.link(a, a));
+    refute!(table.unlink(a, a));
+    assert!(table.remove(a));
+  }
 
-    let table: Table<usize, MinParams> = Table::new();
-    assert!(table.is_empty());
+  #[test]
+  fn link_idempotent() {
+    let table: Table<usize, DefParams> = Table::new();
+    let a: Detached = table.insert(1).unwrap();
+    let b: Detached = table.insert(2).unwrap();
+
+    assert!(table.link(a, b));
+    assert!(table.link(b, a));
+
+    assert!(table.unlink(a, b));
+    refute!(table.unlink(a, b));
+
+    assert!(table.remove(a));
+    assert!(table.exists(b, &Guard::new()));
   }
 
   #[test]
-  fn insert() {
+  fn unlink() {
     let table: Table<usize, DefParams> = Table::new();
+    let a: Detached = table.insert(1).unwrap();
+    let b: Detached = table.insert(2).unwrap();
+    let c: Detached = table.insert(3).unwrap();
 
-    assert_ne!(table.insert(123), None);
-    assert_eq!(table.len(), 1);
+    refute!(table.unlink(a, b));
 
-    for index in 0..16 {
-      assert_ne!(table.insert(123), None);
-      assert_eq!(table.len(), index + 2);
-    }
+    assert!(table.link(a, b));
+    assert!(table.link(a, c));
+    assert!(table.unlink(b, a));
 
-    assert_eq!(table.len(), 17);
+    assert!(table.remove(a));
+    assert!(table.exists(b, &Guard::new()));
+    refute!(table.exists(c, &Guard::new()));
   }
 
   #[test]
-  fn insert_beyond_capacity() {
+  fn unlink_cycles() {
     let table: Table<usize, DefParams> = Table::new();
+    let a: Detached = table.insert(1).unwrap();
+    let b: Detached = table.insert(2).unwrap();
 
-    for _ in 0..table.cap() {
-      assert_ne!(table.insert(123), None);
+    for _ in 0..1000 {
+      assert!(table.link(a, b));
+      assert!(table.unlink(a, b));
     }
 
-    assert_eq!(table.len(), table.cap() as u32);
-    assert_eq!(table.insert(123), None);
-    assert_eq!(table.len(), table.cap() as u32);
+    for key in [a, b] {
+      let mut watchers: usize = 0;
+
+      table.watched(key, |_| {
+        watchers += 1;
+        false
+      });
+
+      assert_eq!(watchers, 0);
+    }
+
+    assert!(table.remove(a));
+    assert!(table.exists(b, &Guard::new()));
   }
 
   #[test]
-  fn insert_unique_ids() {
-    let table: Table<usize, DefParams> = Table::new();
-    let mut keys: HashSet<Detached> = HashSet::with_capacity(table.cap());
+  fn link_remove_race() {
+    let table: Arc<Table<usize, DefParams>> = Arc::new(Table::new());
+    let barrier: Arc<Barrier> = Arc::new(Barrier::new(2));
 
-    for _ in 0..table.cap() {
-      assert!(keys.insert(table.insert(123).unwrap()));
+    for _ in 0..256 {
+      let a: Detached = table.insert(1).unwrap();
+      let b: Detached = table.insert(2).unwrap();
+
+      let thread: JoinHandle<()> = thread::spawn({
+        let table: Arc<Table<usize, DefParams>> = Arc::clone(&table);
+        let barrier: Arc<Barrier> = Arc::clone(&barrier);
+
+        move || {
+          barrier.wait();
+          table.remove(b);
+        }
+      });
+
+      barrier.wait();
+      let linked: bool = table.link(a, b);
+      thread.join().unwrap();
+
+      // Either the link was established before `b` was removed and cascaded,
+      // or it failed and `a` is left untouched.
+      assert_eq!(table.exists(a, &Guard::new()), !linked);
+      table.remove(a);
     }
+
+    assert!(table.is_empty());
   }
 
   #[test]
-  fn write_callback_correct_index() {
+  fn flags() {
     let table: Table<usize, DefParams> = Table::new();
     let guard: Guard = Guard::new();
+    let index: Detached = table.insert(123).unwrap();
 
-    let index: Detached = table
-      .write(|uninit, index| {
-        uninit.write(index.into_bits());
-      })
-      .unwrap();
+    let flags = |table: &Table<usize, DefParams>| table.with_flags(index, &guard, |_, flags| flags);
 
-    assert_eq!(table.read(index, &guard), Some(index.into_bits()));
+    assert_eq!(flags(&table), Some(Flags::EMPTY));
+
+    assert_eq!(
+      table.update_flags(index, |flags| Some(flags | Flags::FIRST)),
+      Ok(Flags::EMPTY)
+    );
+    assert_eq!(flags(&table), Some(Flags::FIRST));
+
+    assert_eq!(table.update_flags(index, |_| None), Err(Some(Flags::FIRST)));
+    assert_eq!(table.with(index, &guard, |item| *item), Some(123));
   }
 
   #[test]
-  fn remove() {
+  fn flags_nonexistent() {
     let table: Table<usize, DefParams> = Table::new();
+    let guard: Guard = Guard::new();
     let index: Detached = table.insert(123).unwrap();
 
-    assert_eq!(table.len(), 1);
-    assert!(!table.is_empty());
-
     assert!(table.remove(index));
 
-    assert_eq!(table.len(), 0);
-    assert!(table.is_empty());
+    assert_eq!(table.with_flags(index, &guard, |_, flags| flags), None);
+    assert_eq!(table.update_flags(index, |_| Some(Flags::ALL)), Err(None));
   }
 
   #[test]
-  fn remove_nonexistent() {
-    let table: Table<usize, DefParams> = Table::new();
+  fn flags_remove() {
+    let table: Table<usize, MinParams> = Table::new();
+    let guard: Guard = Guard::new();
     let index: Detached = table.insert(123).unwrap();
 
+    assert_eq!(
+      table.update_flags(index, |_| Some(Flags::ALL)),
+      Ok(Flags::EMPTY)
+    );
     assert!(table.remove(index));
-    refute!(table.remove(index));
+
+    // Reusing every slot starts each entry with empty flags.
+    for value in 0..table.cap() {
+      let index: Detached = table.insert(value).unwrap();
+      assert_eq!(
+        table.with_flags(index, &guard, |_, flags| flags),
+        Some(Flags::EMPTY)
+      );
+    }
   }
 
   #[test]
-  fn remove_recycling() {
+  fn flags_concurrent() {
+    let table: Arc<Table<usize, DefParams>> = Arc::new(Table::new());
+
+    for _ in 0..64 {
+      let index: Detached = table.insert(123).unwrap();
+      let barrier: Arc<Barrier> = Arc::new(Barrier::new(2));
+
+      let threads: Vec<JoinHandle<()>> = [Flags::FIRST, Flags::SECOND]
+        .into_iter()
+        .map(|flag| {
+          let table: Arc<Table<usize, DefParams>> = Arc::clone(&table);
+          let barrier: Arc<Barrier> = Arc::clone(&barrier);
+
+          thread::spawn(move || {
+            barrier.wait();
+            assert!(
+              table
+                .update_flags(index, |flags| Some(flags | flag))
+                .is_ok()
+            );
+          })
+        })
+        .collect();
+
+      for thread in threads {
+        thread.join().unwrap();
+      }
+
+      let flags: Option<Flags> = table.with_flags(index, &Guard::new(), |_, flags| flags);
+
+      assert_eq!(flags, Some(Flags::ALL));
+      assert!(table.remove(index));
+    }
+  }
+
+  #[test]
+  fn remove_stale() {
     let table: Table<usize, DefParams> = Table::new();
     let guard: Guard = Guard::new();
-    let mut keys: Vec<Detached> = Vec::with_capacity(table.cap());
 
-    for index in 0..table.cap() {
-      keys.push(table.insert(index).unwrap());
+    for index in 0..table.cap() - 1 {
+      assert!(table.insert(index).is_some());
     }
 
-    assert!(table.insert(123).is_none());
-    assert!(table.remove(keys[0]));
+    let stale: Detached = table.insert(123).unwrap();
 
+    assert!(table.remove(stale));
+
+    // The only free slot is the one `stale` referred to.
     let index: Detached = table.insert(456).unwrap();
 
-    assert!(table.exists(index, &guard));
+    assert_ne!(index, stale);
+    assert_eq!(
+      Concrete::<DefParams>::from_detached(index),
+      Concrete::<DefParams>::from_detached(stale)
+    );
+
+    refute!(table.exists(stale, &guard));
+    refute!(table.remove(stale));
+    assert_eq!(table.read(stale, &guard), None);
     assert_eq!(table.read(index, &guard), Some(456));
+    assert_eq!(table.len(), table.cap() as u32);
 
-    for key in keys.drain(1..).rev() {
-      assert!(table.remove(key));
-    }
+    let keys: HashSet<Detached> = table.weak_keys(Guard::new()).collect();
 
-    for index in 0..table.cap() - 1 {
-      assert!(table.insert(index).is_some());
-    }
+    assert!(keys.contains(&index));
+    refute!(keys.contains(&stale));
   }
 
   #[test]
@@ -1118,8 +2760,9 @@ mod tests {
 
     // Force garbage collection - this should always succeed
     // for our use-case since we are single-threaded
-    sdd::try_reclaim();
+    reclaim::try_reclaim();
 
+    // Removed values are reclaimed with and without `std`.
     assert_eq!(DropMe::load(), table.cap() as u32 / 2);
     assert_eq!(DropMe::load(), table.len());
     drop(table);
@@ -0,0 +1,117 @@
+#[cfg(feature = "std")]
+pub(crate) use self::std_lock::Mutex;
+#[cfg(feature = "std")]
+pub(crate) use self::std_lock::MutexGuard;
+
+#[cfg(not(feature = "std"))]
+pub(crate) use self::spin_lock::Mutex;
+#[cfg(not(feature = "std"))]
+pub(crate) use self::spin_lock::MutexGuard;
+
+#[cfg(feature = "std")]
+mod std_lock {
+  use std::sync::PoisonError;
+
+  pub(crate) use std::sync::MutexGuard;
+
+  /// A mutex that ignores poisoning.
+  #[repr(transparent)]
+  pub(crate) struct Mutex<T> {
+    inner: std::sync::Mutex<T>,
+  }
+
+  impl<T> Mutex<T> {
+    #[inline]
+    pub(crate) const fn new(value: T) -> Self {
+      Self {
+        inner: std::sync::Mutex::new(value),
+      }
+    }
+
+    #[inline]
+    pub(crate) fn lock(&self) -> MutexGuard<'_, T> {
+      self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+  }
+}
+
+#[cfg(not(feature = "std"))]
+mod spin_lock {
+  use core::cell::UnsafeCell;
+  use core::hint;
+  use core::ops::Deref;
+  use core::ops::DerefMut;
+  use core::sync::atomic::AtomicBool;
+  use core::sync::atomic::Ordering::Acquire;
+  use core::sync::atomic::Ordering::Relaxed;
+  use core::sync::atomic::Ordering::Release;
+
+  /// A minimal spin lock for targets without an OS mutex.
+  ///
+  /// Only guards short critical sections that never block.
+  pub(crate) struct Mutex<T> {
+    locked: AtomicBool,
+    value: UnsafeCell<T>,
+  }
+
+  // SAFETY: Access to `value` is serialized by `locked`.
+  unsafe impl<T: Send> Send for Mutex<T> {}
+
+  // SAFETY: Access to `value` is serialized by `locked`.
+  unsafe impl<T: Send> Sync for Mutex<T> {}
+
+  impl<T> Mutex<T> {
+    #[inline]
+    pub(crate) const fn new(value: T) -> Self {
+      Self {
+        locked: AtomicBool::new(false),
+        value: UnsafeCell::new(value),
+      }
+    }
+
+    #[inline]
+    pub(crate) fn lock(&self) -> MutexGuard<'_, T> {
+      while self
+        .locked
+        .compare_exchange_weak(false, true, Acquire, Relaxed)
+        .is_err()
+      {
+        while self.locked.load(Relaxed) {
+          hint::spin_loop();
+        }
+      }
+
+      MutexGuard { mutex: self }
+    }
+  }
+
+  /// Releases the lock when dropped.
+  pub(crate) struct MutexGuard<'a, T> {
+    mutex: &'a Mutex<T>,
+  }
+
+  impl<T> Deref for MutexGuard<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+      // SAFETY: The lock is held for the lifetime of the guard.
+      unsafe { &*self.mutex.value.get() }
+    }
+  }
+
+  impl<T> DerefMut for MutexGuard<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+      // SAFETY: The lock is held for the lifetime of the guard.
+      unsafe { &mut *self.mutex.value.get() }
+    }
+  }
+
+  impl<T> Drop for MutexGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+      self.mutex.locked.store(false, Release);
+    }
+  }
+}
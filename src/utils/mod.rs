@@ -1,8 +1,11 @@
+mod lock;
 #[cfg(test)]
 mod macros;
 mod models;
 
+pub(crate) use self::lock::Mutex;
+pub(crate) use self::lock::MutexGuard;
 #[cfg(test)]
 pub(crate) use self::macros::each_capacity;
-pub(crate) use self::models::alloc;
+pub(crate) use self::models::heap;
 pub(crate) use self::models::sync;
@@ -2,19 +2,21 @@
 compile_error!("cannot use loom and shuttle at once");
 
 #[cfg(loom)]
-pub(crate) mod alloc {
+pub(crate) mod heap {
+  pub(crate) use ::alloc::alloc::handle_alloc_error;
   pub(crate) use ::loom::alloc::Layout;
   pub(crate) use ::loom::alloc::alloc;
+  pub(crate) use ::loom::alloc::alloc_zeroed;
   pub(crate) use ::loom::alloc::dealloc;
-  pub(crate) use ::std::alloc::handle_alloc_error;
 }
 
 #[cfg(not(loom))]
-pub(crate) mod alloc {
-  pub(crate) use ::std::alloc::Layout;
-  pub(crate) use ::std::alloc::alloc;
-  pub(crate) use ::std::alloc::dealloc;
-  pub(crate) use ::std::alloc::handle_alloc_error;
+pub(crate) mod heap {
+  pub(crate) use ::alloc::alloc::Layout;
+  pub(crate) use ::alloc::alloc::alloc;
+  pub(crate) use ::alloc::alloc::alloc_zeroed;
+  pub(crate) use ::alloc::alloc::dealloc;
+  pub(crate) use ::alloc::alloc::handle_alloc_error;
 }
 
 #[cfg(not(any(loom, shuttle)))]
@@ -0,0 +1,413 @@
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::marker::PhantomData;
+use core::ptr;
+use core::sync::atomic::AtomicBool;
+use core::sync::atomic::AtomicPtr;
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering::AcqRel;
+use core::sync::atomic::Ordering::Acquire;
+use core::sync::atomic::Ordering::Relaxed;
+use core::sync::atomic::Ordering::Release;
+
+use crate::index::Detached;
+use crate::monitor::State;
+use crate::quota::Usage;
+use crate::reason::Reason;
+use crate::reclaim::Guard;
+
+/// An observer notified when an entry leaves the table.
+#[derive(Clone)]
+pub(crate) enum Watch {
+  Monitor(Arc<State>),
+  Link(Arc<Link>),
+  Quota(Arc<Usage>),
+}
+
+impl Watch {
+  /// Returns `false` once the watch can no longer observe anything: the
+  /// monitor was cancelled or the link severed.
+  #[inline]
+  pub(crate) fn is_live(&self) -> bool {
+    match self {
+      Self::Monitor(state) => !state.is_cancelled(),
+      Self::Link(link) => link.active.load(Acquire),
+      Self::Quota(_) => true,
+    }
+  }
+
+  /// Delivers the removal notification to a monitor or quota.
+  ///
+  /// Links are resolved by the table itself, so this has no effect on them.
+  #[inline]
+  pub(crate) fn fire(&self, reason: Reason) {
+    match self {
+      Self::Monitor(state) => state.fire(reason),
+      Self::Link(_) => {}
+      Self::Quota(usage) => usage.release(),
+    }
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Link
+// -----------------------------------------------------------------------------
+
+/// A bidirectional connection between two entries.
+///
+/// Both endpoints hold a [`Watch::Link`] sharing the same state, so severing
+/// the link from either side disables it for both.
+pub(crate) struct Link {
+  ends: [Detached; 2],
+  active: AtomicBool,
+}
+
+impl Link {
+  #[inline]
+  pub(crate) const fn new(a: Detached, b: Detached) -> Self {
+    Self {
+      ends: [a, b],
+      active: AtomicBool::new(true),
+    }
+  }
+
+  /// Returns `true` if the link is active and connects `a` and `b`.
+  #[inline]
+  pub(crate) fn connects(&self, a: Detached, b: Detached) -> bool {
+    let [x, y] = self.ends;
+    ((x == a && y == b) || (x == b && y == a)) && self.active.load(Acquire)
+  }
+
+  /// Returns the endpoint opposite to `key`.
+  #[inline]
+  pub(crate) const fn peer(&self, key: Detached) -> Detached {
+    let [x, y] = self.ends;
+    if x.into_bits() == key.into_bits() {
+      y
+    } else {
+      x
+    }
+  }
+
+  /// Disables the link, returning `true` if it was active.
+  #[inline]
+  pub(crate) fn sever(&self) -> bool {
+    self.active.swap(false, AcqRel)
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Watcher List
+// -----------------------------------------------------------------------------
+
+// Three tag bits are packed into the list head.
+#[repr(align(8))]
+struct Node {
+  next: AtomicPtr<Node>,
+  /// The length of the list when this node was pushed or last compacted,
+  /// including the node itself.
+  len: AtomicUsize,
+  /// The length at which pushing onto this node compacts the list.
+  limit: AtomicUsize,
+  watch: Watch,
+}
+
+/// Tag bit set on the list head once the list no longer accepts watchers.
+const CLOSED: usize = 0b001;
+
+/// Tag bit on the list head reserved for the owning entry, see
+/// [`Watchers::mark`].
+const MARKED: usize = 0b010;
+
+/// Tag bit set on the list head while a thread compacts the list.
+const COMPACTING: usize = 0b100;
+
+const TAGS: usize = CLOSED | MARKED | COMPACTING;
+
+/// The shortest list that a push compacts.
+pub(crate) const MIN_COMPACT: usize = 16;
+
+const _: () = assert!(align_of::<Node>() > TAGS);
+
+#[inline]
+fn is_closed(head: *mut Node) -> bool {
+  head.addr() & CLOSED != 0
+}
+
+#[inline]
+fn untagged(head: *mut Node) -> *mut Node {
+  head.map_addr(|addr| addr & !TAGS)
+}
+
+/// A lock-free, closable stack of watchers attached to a single entry.
+///
+/// Watchers are pushed concurrently until the list is closed by the thread
+/// that removes the entry. Watchers that can no longer fire, such as cancelled
+/// monitors and severed links, are unlinked by [`Watchers::compact`]. Pushes
+/// compact the list once it doubles in length since it was last compacted,
+/// so the list stays within twice its live watchers, or [`MIN_COMPACT`], at
+/// an amortized constant cost per push. Unlinked nodes are reclaimed once
+/// every [`Guard`] that could observe them is dropped, so all operations on a
+/// shared list require one.
+pub(crate) struct Watchers {
+  head: AtomicPtr<Node>,
+}
+
+impl Watchers {
+  #[inline]
+  pub(crate) const fn new() -> Self {
+    Self {
+      head: AtomicPtr::new(ptr::null_mut()),
+    }
+  }
+
+  /// Attaches `watch` to the list, or returns it if the list is closed.
+  ///
+  /// Compacts the list afterwards if it reached its compaction limit, so
+  /// repeatedly attaching and cancelling watchers does not grow it without
+  /// bound.
+  pub(crate) fn push(&self, watch: Watch, guard: &Guard) -> Result<(), Watch> {
+    let node: *mut Node = Box::into_raw(Box::new(Node {
+      next: AtomicPtr::new(ptr::null_mut()),
+      len: AtomicUsize::new(1),
+      limit: AtomicUsize::new(MIN_COMPACT),
+      watch,
+    }));
+
+    let mut head: *mut Node = self.head.load(Acquire);
+    let mut len: usize;
+    let mut limit: usize;
+
+    loop {
+      if is_closed(head) {
+        // SAFETY: `node` was never published, so we still own it.
+        let node: Box<Node> = unsafe { Box::from_raw(node) };
+        return Err(node.watch);
+      }
+
+      // SAFETY: Unlinked nodes are not freed while `guard` is alive.
+      (len, limit) = match unsafe { untagged(head).as_ref() } {
+        Some(top) => (top.len.load(Relaxed) + 1, top.limit.load(Relaxed)),
+        None => (1, MIN_COMPACT),
+      };
+
+      // SAFETY: `node` was never published, so we have exclusive access.
+      unsafe {
+        (*node).next.store(untagged(head), Relaxed);
+        (*node).len.store(len, Relaxed);
+        (*node).limit.store(limit, Relaxed);
+      }
+
+      // Keep the tags of the previous head; only the pointer changes.
+      let tagged: *mut Node = node.map_addr(|addr| addr | (head.addr() & TAGS));
+
+      match self
+        .head
+        .compare_exchange_weak(head, tagged, Release, Acquire)
+      {
+        Ok(_) => break,
+        Err(next) => head = next,
+      }
+    }
+
+    if len >= limit {
+      self.compact(guard);
+    }
+
+    Ok(())
+  }
+
+  /// Unlinks every watcher that is no longer live.
+  ///
+  /// Does nothing if the list is closed or another thread is compacting it.
+  pub(crate) fn compact(&self, guard: &Guard) {
+    let mut head: *mut Node = self.head.load(Acquire);
+
+    loop {
+      if head.addr() & (CLOSED | COMPACTING) != 0 {
+        return;
+      }
+
+      match self.head.compare_exchange_weak(
+        head,
+        head.map_addr(|addr| addr | COMPACTING),
+        Acquire,
+        Acquire,
+      ) {
+        Ok(_) => break,
+        Err(next) => head = next,
+      }
+    }
+
+    let mut head: *mut Node = head.map_addr(|addr| addr | COMPACTING);
+
+    // SAFETY:
+    // - Only the compacting thread writes `next` of a published node, and
+    //   pushes only replace the head, so the nodes we visit stay in the list
+    //   until we move past them.
+    // - Unlinked nodes are freed only after `guard` and every other guard
+    //   alive now are dropped, so concurrent readers never see a freed node.
+    unsafe {
+      // Pop dead watchers off the top; a racing push makes us re-check the
+      // new head instead.
+      while let Some(this) = untagged(head).as_ref()
+        && !this.watch.is_live()
+        && !is_closed(head)
+      {
+        let next: *mut Node = this
+          .next
+          .load(Acquire)
+          .map_addr(|addr| addr | (head.addr() & TAGS));
+
+        match self.head.compare_exchange_weak(head, next, AcqRel, Acquire) {
+          Ok(_) => {
+            let node: *mut Node = untagged(head);
+            guard.defer(move || drop(Box::from_raw(node)));
+            head = next;
+          }
+          Err(current) => head = current,
+        }
+      }
+
+      let top: *mut Node = untagged(head);
+      let mut prev: *mut Node = top;
+      let mut len: usize = 1;
+
+      while let Some(this) = prev.as_ref() {
+        let node: *mut Node = this.next.load(Acquire);
+
+        let Some(that) = node.as_ref() else {
+          break;
+        };
+
+        if that.watch.is_live() {
+          prev = node;
+          len += 1;
+          continue;
+        }
+
+        this.next.store(that.next.load(Acquire), Release);
+
+        guard.defer(move || drop(Box::from_raw(node)));
+      }
+
+      // Pushes racing with this store may copy a stale length, which only
+      // makes the next compaction happen early.
+      if let Some(top) = top.as_ref() {
+        top.len.store(len, Relaxed);
+        top
+          .limit
+          .store(len.saturating_mul(2).max(MIN_COMPACT), Relaxed);
+      }
+    }
+
+    let mut head: *mut Node = self.head.load(Relaxed);
+
+    loop {
+      match self.head.compare_exchange_weak(
+        head,
+        head.map_addr(|addr| addr & !COMPACTING),
+        Release,
+        Relaxed,
+      ) {
+        Ok(_) => break,
+        Err(next) => head = next,
+      }
+    }
+  }
+
+  /// Closes the list, returning the attached watchers.
+  ///
+  /// Only the first call observes any watchers.
+  pub(crate) fn close<'a>(&'a self, _guard: &'a Guard) -> Iter<'a> {
+    let mut head: *mut Node = self.head.load(Acquire);
+
+    loop {
+      if is_closed(head) {
+        return Iter::new(ptr::null_mut());
+      }
+
+      let closed: *mut Node = head.map_addr(|addr| addr | CLOSED);
+
+      match self
+        .head
+        .compare_exchange_weak(head, closed, AcqRel, Acquire)
+      {
+        Ok(_) => return Iter::new(untagged(head)),
+        Err(next) => head = next,
+      }
+    }
+  }
+
+  /// Returns an iterator over the currently attached watchers.
+  #[inline]
+  pub(crate) fn iter<'a>(&'a self, _guard: &'a Guard) -> Iter<'a> {
+    Iter::new(untagged(self.head.load(Acquire)))
+  }
+
+  /// Sets a spare tag bit of the list head on behalf of the owning entry.
+  ///
+  /// Lets the entry keep a flag without growing every entry by a word.
+  #[inline]
+  pub(crate) fn mark(&mut self) {
+    let head: &mut *mut Node = self.head.get_mut();
+    *head = head.map_addr(|addr| addr | MARKED);
+  }
+
+  /// Returns `true` if [`Watchers::mark`] was called.
+  #[inline]
+  pub(crate) fn is_marked(&mut self) -> bool {
+    self.head.get_mut().addr() & MARKED != 0
+  }
+}
+
+impl Drop for Watchers {
+  fn drop(&mut self) {
+    let head: *mut Node = *self.head.get_mut();
+    let closed: bool = is_closed(head);
+    let mut node: *mut Node = untagged(head);
+
+    while !node.is_null() {
+      // SAFETY: We have exclusive access, and each node is visited once.
+      let mut this: Box<Node> = unsafe { Box::from_raw(node) };
+
+      // Dropped without being closed: the table itself is going away.
+      if !closed {
+        this.watch.fire(Reason::Shutdown);
+      }
+
+      node = *this.next.get_mut();
+    }
+  }
+}
+
+/// An iterator over the watchers of a list.
+pub(crate) struct Iter<'a> {
+  node: *mut Node,
+  marker: PhantomData<&'a Watchers>,
+}
+
+impl Iter<'_> {
+  #[inline]
+  const fn new(node: *mut Node) -> Self {
+    Self {
+      node,
+      marker: PhantomData,
+    }
+  }
+}
+
+impl<'a> Iterator for Iter<'a> {
+  type Item = &'a Watch;
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    // SAFETY: Nodes are freed when the list is dropped, which cannot happen
+    //         while it is borrowed, or after the guard borrowed alongside it.
+    let node: &'a Node = unsafe { self.node.as_ref()? };
+
+    self.node = node.next.load(Acquire);
+
+    Some(&node.watch)
+  }
+}